@@ -0,0 +1,186 @@
+//! Cellular automata, on grids of any dimension or on hexagonal grids.
+//!
+//! Copied from utils, only with the dense storage and the totalistic rules,
+//! with which the background stays dead.
+
+/// Coordinates of a cell.
+pub type Point<const N: usize> = [i32; N];
+
+fn add<const N: usize>(p: &Point<N>, offset: &Point<N>) -> Point<N> {
+    std::array::from_fn(|d| p[d] + offset[d])
+}
+
+/// The cells whose states give the next state of a cell, as offsets from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Neighborhood<const N: usize> {
+    offsets: Vec<Point<N>>,
+}
+
+impl<const N: usize> Neighborhood<N> {
+    /// All the cells in the block of size 3 around the cell, including the cell itself,
+    /// in lexicographic order. With `[row, col]` points, it's the reading order.
+    pub fn block() -> Self {
+        let mut offsets = vec![[0; N]];
+        for d in 0..N {
+            offsets = offsets
+                .into_iter()
+                .flat_map(|offset| {
+                    (-1..=1).map(move |v| {
+                        let mut offset = offset;
+                        offset[d] = v;
+                        offset
+                    })
+                })
+                .collect();
+        }
+        Self { offsets }
+    }
+
+    /// The 3^N - 1 cells touching the cell, diagonals included.
+    pub fn moore() -> Self {
+        let mut neighborhood = Self::block();
+        neighborhood.offsets.retain(|offset| *offset != [0; N]);
+        neighborhood
+    }
+}
+
+/// How the next state of a cell is computed from its state and the ones of its neighbors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// Depends only on the number of alive neighbors: A dead cell becomes alive if this number is in `birth`,
+    /// an alive one stays alive if it's in `survive`.
+    Totalistic {
+        birth: Vec<usize>,
+        survive: Vec<usize>,
+    },
+}
+
+impl Rule {
+    /// Conway's Game of Life.
+    pub fn life() -> Self {
+        Self::parse("B3/S23")
+    }
+
+    /// A totalistic rule in the B/S notation, like "B3/S23" for the Game of Life.
+    pub fn parse(notation: &str) -> Self {
+        let counts = |part: &str, prefix: char| -> Vec<usize> {
+            part.strip_prefix(prefix)
+                .expect("Invalid rule notation")
+                .chars()
+                .map(|c| c.to_digit(10).expect("Invalid count in rule") as usize)
+                .collect()
+        };
+        let (birth, survive) = notation.split_once('/').expect("Invalid rule notation");
+        Self::Totalistic {
+            birth: counts(birth, 'B'),
+            survive: counts(survive, 'S'),
+        }
+    }
+
+    fn next(&self, alive: bool, neighbors: impl Iterator<Item = bool>) -> bool {
+        match self {
+            Self::Totalistic { birth, survive } => {
+                let count = neighbors.filter(|n| *n).count();
+                if alive {
+                    survive.contains(&count)
+                } else {
+                    birth.contains(&count)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton<const N: usize> {
+    neighborhood: Neighborhood<N>,
+    rule: Rule,
+    // All the cells inside the bounds, the ones outside being dead.
+    size: [usize; N],
+    states: Vec<bool>,
+}
+
+impl<const N: usize> Automaton<N> {
+    /// An automaton on the cells with coordinates from 0 to `size` excluded, all dead.
+    pub fn dense(size: [usize; N], neighborhood: Neighborhood<N>, rule: Rule) -> Self {
+        Self {
+            neighborhood,
+            rule,
+            size,
+            states: vec![false; size.iter().product()],
+        }
+    }
+
+    /// Sets the cells from a text, each line being a row.
+    /// The points are `[row, col, 0, ...]`, so N must be at least 2.
+    pub fn set_text(&mut self, input: &str, alive: char) {
+        for (row, line) in input.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                let mut p = [0; N];
+                p[0] = i32::try_from(row).unwrap();
+                p[1] = i32::try_from(col).unwrap();
+                self.set(p, c == alive);
+            }
+        }
+    }
+
+    fn index(size: &[usize; N], p: &Point<N>) -> Option<usize> {
+        let mut index = 0;
+        for d in 0..N {
+            let v = usize::try_from(p[d]).ok().filter(|v| *v < size[d])?;
+            index = index * size[d] + v;
+        }
+        Some(index)
+    }
+
+    fn point(size: &[usize; N], mut index: usize) -> Point<N> {
+        let mut p = [0; N];
+        for d in (0..N).rev() {
+            p[d] = i32::try_from(index % size[d]).unwrap();
+            index /= size[d];
+        }
+        p
+    }
+
+    pub fn get(&self, p: &Point<N>) -> bool {
+        Self::index(&self.size, p).is_some_and(|index| self.states[index])
+    }
+
+    /// Sets the state of a cell, which must be inside the bounds.
+    pub fn set(&mut self, p: Point<N>, alive: bool) {
+        let index = Self::index(&self.size, &p).expect("Cell outside of the automaton");
+        self.states[index] = alive;
+    }
+
+    /// The bounds of the automaton.
+    pub fn size(&self) -> [usize; N] {
+        self.size
+    }
+
+    pub fn alive_count(&self) -> usize {
+        self.states.iter().filter(|s| **s).count()
+    }
+
+    fn next_state(&self, p: &Point<N>) -> bool {
+        let neighbors = self
+            .neighborhood
+            .offsets
+            .iter()
+            .map(|offset| self.get(&add(p, offset)));
+        self.rule.next(self.get(p), neighbors)
+    }
+
+    /// Computes the next generation.
+    pub fn step(&mut self) {
+        self.states = (0..self.states.len())
+            .map(|index| self.next_state(&Self::point(&self.size, index)))
+            .collect();
+    }
+
+    /// Computes that many generations.
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use std::io::{self, Read};
 
+mod cycle;
+
 #[derive(Debug)]
 enum Moves {
     Spin(usize),
@@ -77,26 +79,12 @@ fn dance(moves: &[Moves], original_programs: &[char]) -> Vec<char> {
 }
 
 fn dance_a_lot<const DANCE_COUNT: usize>(moves: &[Moves], original_programs: &[char]) -> Vec<char> {
-    let mut programs = original_programs.to_vec();
-    // At some point, we go back to the first dance, find the period
-    let mut period = 0;
-    loop {
-        period += 1;
-        dance_once(moves, &mut programs);
-        if programs == original_programs {
-            break;
-        }
-    }
-    // Jump in future
-    let idx = (DANCE_COUNT / period) * period;
-    programs = original_programs.to_vec();
-    // now at dance idx, just below the target, programs is same as the original.
-
-    // Just finish
-    for _ in idx..DANCE_COUNT {
-        dance_once(moves, &mut programs);
-    }
-    programs
+    // At some point, we go back to a previous dance, so we can jump in the future.
+    cycle::state_at(
+        original_programs.to_vec(),
+        |programs| dance_once(moves, programs),
+        DANCE_COUNT,
+    )
 }
 
 fn main() {
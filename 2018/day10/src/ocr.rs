@@ -0,0 +1,248 @@
+//! Recognition of the block letters drawn by some puzzles.
+//!
+//! Two fonts are used in Advent of Code:
+//! - 6 rows high, letters usually 4 pixels wide (2016 day 8, 2019 days 8 and 11, 2021 day 13, 2022 day 10).
+//! - 10 rows high, letters 6 pixels wide (2018 day 10).
+//!
+//! Copied from utils, with only the recognition from dense images and points.
+//! Margins around the text are ignored. Letters of the 6 rows font are read on their grid of 5 columns,
+//! otherwise they must be separated by at least one empty column, the spacing between them not mattering.
+
+use std::fmt;
+
+const FONT_6: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const FONT_10: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OcrError {
+    /// No lit pixel at all.
+    Empty,
+    /// Text height doesn't match any font.
+    UnsupportedHeight(usize),
+    /// A glyph that isn't in the font, rendered with `#` and `.`.
+    UnknownGlyph { index: usize, glyph: String },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "No text in the image"),
+            OcrError::UnsupportedHeight(height) => {
+                write!(f, "No font for letters {height} pixels high")
+            }
+            OcrError::UnknownGlyph { index, glyph } => {
+                write!(f, "Unknown letter at position {index}:\n{glyph}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+// Width of the cell of each letter in the 6 rows font, spacing included.
+const FONT_6_PITCH: usize = 5;
+
+fn font(height: usize) -> Option<&'static [(char, &'static str)]> {
+    match height {
+        6 => Some(&FONT_6),
+        10 => Some(&FONT_10),
+        _ => None,
+    }
+}
+
+fn render(pixels: &[Vec<bool>], cols: std::ops::Range<usize>) -> String {
+    pixels
+        .iter()
+        .map(|row| {
+            row[cols.clone()]
+                .iter()
+                .map(|lit| if *lit { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Recognizes the text in a dense image, given as rows of pixels.
+/// Rows don't need to have the same length.
+pub fn recognize(pixels: &[Vec<bool>]) -> Result<String, OcrError> {
+    // Removing the margins.
+    let lit_rows: Vec<usize> = (0..pixels.len())
+        .filter(|r| pixels[*r].iter().any(|lit| *lit))
+        .collect();
+    let (Some(&top), Some(&bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        return Err(OcrError::Empty);
+    };
+    let width = pixels.iter().map(Vec::len).max().unwrap_or(0);
+    let pixels: Vec<Vec<bool>> = pixels[top..=bottom]
+        .iter()
+        .map(|row| {
+            let mut row = row.clone();
+            row.resize(width, false);
+            row
+        })
+        .collect();
+    let height = pixels.len();
+    let font = font(height).ok_or(OcrError::UnsupportedHeight(height))?;
+
+    let column_lit = |c: usize| pixels.iter().any(|row| row[c]);
+
+    // The letters of the 6 rows font are on a grid of 5 columns. As Y fills its whole cell,
+    // there may be no empty column between letters, so splitting on the grid is tried first.
+    if height == 6 {
+        let first = (0..width).find(|c| column_lit(*c)).unwrap();
+        for origin in (first.saturating_sub(FONT_6_PITCH - 1)..=first).rev() {
+            let cells = (origin..width)
+                .step_by(FONT_6_PITCH)
+                .map(|start| start..(start + FONT_6_PITCH).min(width));
+            if let Ok(text) = read_cells(&pixels, font, cells) {
+                return Ok(text);
+            }
+        }
+    }
+
+    // Otherwise letters are separated by empty columns.
+    let mut cells = Vec::new();
+    let mut col = 0;
+    while col < width {
+        if !column_lit(col) {
+            col += 1;
+            continue;
+        }
+        let start = col;
+        while col < width && column_lit(col) {
+            col += 1;
+        }
+        cells.push(start..col);
+    }
+    read_cells(&pixels, font, cells)
+}
+
+// Reads the letter in each range of columns, ignoring the empty ones.
+fn read_cells(
+    pixels: &[Vec<bool>],
+    font: &[(char, &str)],
+    cells: impl IntoIterator<Item = std::ops::Range<usize>>,
+) -> Result<String, OcrError> {
+    let column_lit = |c: &usize| pixels.iter().any(|row| row[*c]);
+    let mut text = String::new();
+    for cell in cells {
+        let Some(start) = cell.clone().find(column_lit) else {
+            continue;
+        };
+        let end = cell.rev().find(column_lit).unwrap() + 1;
+        let glyph = render(pixels, start..end);
+        let letter = font
+            .iter()
+            .find(|(_, g)| *g == glyph)
+            .map(|(letter, _)| *letter)
+            .ok_or(OcrError::UnknownGlyph {
+                index: text.len(),
+                glyph,
+            })?;
+        text.push(letter);
+    }
+    Ok(text)
+}
+
+/// Recognizes text from the positions (x, y) of the lit pixels, which can be negative.
+pub fn recognize_points<I>(points: I) -> Result<String, OcrError>
+where
+    I: IntoIterator<Item = (i64, i64)>,
+{
+    let points: Vec<(i64, i64)> = points.into_iter().collect();
+    let Some(min_x) = points.iter().map(|p| p.0).min() else {
+        return Err(OcrError::Empty);
+    };
+    let min_y = points.iter().map(|p| p.1).min().unwrap();
+    let max_x = points.iter().map(|p| p.0).max().unwrap();
+    let max_y = points.iter().map(|p| p.1).max().unwrap();
+    let width = usize::try_from(max_x - min_x + 1).unwrap();
+    let height = usize::try_from(max_y - min_y + 1).unwrap();
+    let mut pixels = vec![vec![false; width]; height];
+    for (x, y) in points {
+        pixels[usize::try_from(y - min_y).unwrap()][usize::try_from(x - min_x).unwrap()] = true;
+    }
+    recognize(&pixels)
+}
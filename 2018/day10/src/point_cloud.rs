@@ -0,0 +1,89 @@
+//! Points moving in straight lines at constant speed, which at some point form a message.
+//! Copied from utils, without the wrapping around a grid.
+//!
+//! The message appears when the points are the most grouped. Without wrapping, the width and height
+//! of the bounding box are convex functions of time, so their sum has a single minimum that
+//! a ternary search finds.
+
+use crate::ocr::{self, OcrError};
+
+/// Position and velocity, each as (x, y).
+pub type Point = ((i64, i64), (i64, i64));
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointCloud {
+    positions: Vec<(i64, i64)>,
+    velocities: Vec<(i64, i64)>,
+}
+
+impl PointCloud {
+    pub fn new(points: &[Point]) -> Self {
+        Self {
+            positions: points.iter().map(|p| p.0).collect(),
+            velocities: points.iter().map(|p| p.1).collect(),
+        }
+    }
+
+    pub fn positions_at(&self, time: i64) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.positions
+            .iter()
+            .zip(&self.velocities)
+            .map(move |(p, v)| (p.0 + v.0 * time, p.1 + v.1 * time))
+    }
+
+    /// Bounding box as ((min x, min y), (max x, max y)).
+    pub fn bounding_box_at(&self, time: i64) -> ((i64, i64), (i64, i64)) {
+        self.positions_at(time).fold(
+            ((i64::MAX, i64::MAX), (i64::MIN, i64::MIN)),
+            |(min, max), (x, y)| ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y))),
+        )
+    }
+
+    // Half perimeter of the bounding box, convex over time.
+    fn spread_at(&self, time: i64) -> i64 {
+        let (min, max) = self.bounding_box_at(time);
+        (max.0 - min.0) + (max.1 - min.1)
+    }
+
+    /// Time (not negative) at which the points are the closest together.
+    pub fn most_grouped_time(&self) -> i64 {
+        // First finding an upper bound, where the spread increases again.
+        let mut high = 1;
+        while self.spread_at(high + 1) < self.spread_at(high) {
+            high *= 2;
+        }
+        let mut low = 0;
+        high += 1;
+        while high - low > 2 {
+            let m1 = low + (high - low) / 3;
+            let m2 = high - (high - low) / 3;
+            if self.spread_at(m1) <= self.spread_at(m2) {
+                high = m2;
+            } else {
+                low = m1;
+            }
+        }
+        (low..=high).min_by_key(|t| self.spread_at(*t)).unwrap()
+    }
+
+    /// Renders the points with `#`, in their bounding box.
+    pub fn render_at(&self, time: i64) -> String {
+        let (min, max) = self.bounding_box_at(time);
+        let width = usize::try_from(max.0 - min.0 + 1).unwrap();
+        let height = usize::try_from(max.1 - min.1 + 1).unwrap();
+        let mut rows = vec![vec!['.'; width]; height];
+        for (x, y) in self.positions_at(time) {
+            rows[usize::try_from(y - min.1).unwrap()][usize::try_from(x - min.0).unwrap()] = '#';
+        }
+        rows.iter()
+            .map(|r| r.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Finds the time when the points are grouped, and reads the message they form then.
+    pub fn read_message(&self) -> (i64, Result<String, OcrError>) {
+        let time = self.most_grouped_time();
+        (time, ocr::recognize_points(self.positions_at(time)))
+    }
+}
@@ -0,0 +1,61 @@
+//! Cycle detection, to know the state of a simulation after a huge number of steps.
+//!
+//! Copied from utils, only with `metric_at`.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The states starting at step `start` repeat every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cycle {
+    start: usize,
+    length: usize,
+}
+
+// Runs the simulation until a key repeats, without going past step `max_steps`.
+// `visit` is called on each state, including the initial one.
+// When a cycle is found, the state is left at step `start + length`, otherwise at `max_steps`.
+fn detect<T, K: Eq + Hash>(
+    state: &mut T,
+    step: &mut impl FnMut(&mut T),
+    key: impl Fn(&T) -> K,
+    max_steps: usize,
+    mut visit: impl FnMut(&T),
+) -> Option<Cycle> {
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    for i in 0..=max_steps {
+        visit(state);
+        if let Some(start) = seen.insert(key(state), i) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+        if i < max_steps {
+            step(state);
+        }
+    }
+    None
+}
+
+/// A metric of the state after `n` steps.
+///
+/// The metric must grow by the same amount at each cycle, which allows the states
+/// to be only equivalent through their key, for example a pattern that moves.
+pub fn metric_at<T, K: Eq + Hash>(
+    mut state: T,
+    mut step: impl FnMut(&mut T),
+    key: impl Fn(&T) -> K,
+    metric: impl Fn(&T) -> i64,
+    n: usize,
+) -> i64 {
+    let mut metrics = Vec::new();
+    let Some(cycle) = detect(&mut state, &mut step, key, n, |s| metrics.push(metric(s))) else {
+        return metrics[n];
+    };
+    let current = cycle.start + cycle.length;
+    let delta = metrics[current] - metrics[cycle.start];
+    let cycles_left = (n - current) / cycle.length;
+    let remaining = (n - current) % cycle.length;
+    metrics[cycle.start + remaining] + i64::try_from(cycles_left + 1).unwrap() * delta
+}
@@ -1,5 +1,7 @@
 use std::io::{self, Read};
 
+mod cycle;
+
 #[allow(clippy::cast_possible_truncation)]
 fn pattern_to_index(s: &str) -> usize {
     s.chars().rev().enumerate().fold(0, |acc, (i, b)| {
@@ -47,7 +49,6 @@ fn state_to_string(state: &[bool]) -> String {
     state.iter().map(|v| if *v { '#' } else { '.' }).collect()
 }
 
-#[allow(dead_code)]
 fn state_to_string_trimed(state: &[bool]) -> String {
     state_to_string(state)
         .trim_start_matches('.')
@@ -64,9 +65,29 @@ fn get_sum_of_plant_pots(state: &[bool], index_of_zero: i64) -> i64 {
         .sum()
 }
 
-fn plant_pots_sum(initial_state: &[bool], instructions: &[bool], nb_of_generations: i64) -> i64 {
+// Index of pot 0 in the state.
+const INDEX_OF_ZERO: i64 = 10;
+
+fn next_generation(state: &mut Vec<bool>, instructions: &[bool]) {
+    let mut next_state: Vec<bool> = Vec::new();
+    next_state.push(false);
+    next_state.push(false);
+
+    for i in 2..state.len() - 2 {
+        let idx = slice_to_index(&state[i - 2..=i + 2]);
+        next_state.push(instructions[idx]);
+    }
+
+    next_state.push(false);
+    next_state.push(false);
+    next_state.push(false);
+    next_state.push(false);
+
+    *state = next_state;
+}
+
+fn plant_pots_sum(initial_state: &[bool], instructions: &[bool], nb_of_generations: usize) -> i64 {
     const TEN_FALSE: [bool; 10] = [false; 10];
-    const INDEX_OF_ZERO: i64 = 10;
 
     let mut state = Vec::new();
     // Hard-coded padding, ugly but does the job.
@@ -74,28 +95,15 @@ fn plant_pots_sum(initial_state: &[bool], instructions: &[bool], nb_of_generatio
     state.extend(initial_state);
     state.extend(TEN_FALSE);
 
-    // println!("0: {}", state_to_string(&state));
-    for _g in 1..=nb_of_generations {
-        let mut next_state: Vec<bool> = Vec::new();
-        next_state.push(false);
-        next_state.push(false);
-
-        for i in 2..state.len() - 2 {
-            let idx = slice_to_index(&state[i - 2..=i + 2]);
-            next_state.push(instructions[idx]);
-        }
-
-        next_state.push(false);
-        next_state.push(false);
-        next_state.push(false);
-        next_state.push(false);
-
-        std::mem::swap(&mut state, &mut next_state);
-        // println!("{}: {}", _g, state_to_string_trimed(&state));
-        // println!("{}: {}", _g, get_sum_of_plant_pots(&state, INDEX_OF_ZERO));
-    }
-
-    get_sum_of_plant_pots(&state, INDEX_OF_ZERO)
+    // The state stabilizes at some point, meaning it's the same pattern that just shifts.
+    // So we detect when the trimmed pattern repeats, and extrapolate the sum from there.
+    cycle::metric_at(
+        state,
+        |s| next_generation(s, instructions),
+        |s| state_to_string_trimed(s),
+        |s| get_sum_of_plant_pots(s, INDEX_OF_ZERO),
+        nb_of_generations,
+    )
 }
 
 fn plant_pots_sum_small(initial_state: &[bool], instructions: &[bool]) -> i64 {
@@ -103,18 +111,8 @@ fn plant_pots_sum_small(initial_state: &[bool], instructions: &[bool]) -> i64 {
 }
 
 fn plant_pots_sum_huge(initial_state: &[bool], instructions: &[bool]) -> i64 {
-    const NB_OF_GENERATIONS: i64 = 50_000_000_000;
-
-    // By printing the state trimmed, we see it stabilizes from generation 124,
-    // meaning it's the same pattern that just shifts.
-    const STABILIZATION_GENERATION: i64 = 125;
-
-    // So we find the starting value and the shift.
-    let sum = plant_pots_sum(initial_state, instructions, STABILIZATION_GENERATION);
-    let sum2 = plant_pots_sum(initial_state, instructions, STABILIZATION_GENERATION + 1);
-    let diff = sum2 - sum;
-
-    sum + diff * (NB_OF_GENERATIONS - STABILIZATION_GENERATION)
+    const NB_OF_GENERATIONS: usize = 50_000_000_000;
+    plant_pots_sum(initial_state, instructions, NB_OF_GENERATIONS)
 }
 
 fn main() {
@@ -151,4 +149,13 @@ mod tests {
         let (initial_state, instructions) = build(INPUT_TEST);
         assert_eq!(plant_pots_sum_small(&initial_state, &instructions), 325);
     }
+
+    #[test]
+    fn test_part2() {
+        let (initial_state, instructions) = build(INPUT_TEST);
+        assert_eq!(
+            plant_pots_sum_huge(&initial_state, &instructions),
+            999_999_999_374
+        );
+    }
 }
@@ -83,7 +83,7 @@ impl Grid {
             North => pos < self.cols,
             East => pos % self.cols == self.cols - 1,
             South => pos / self.cols == self.rows - 1,
-            West => pos % self.cols == 0,
+            West => pos.is_multiple_of(self.cols),
         }
     }
 
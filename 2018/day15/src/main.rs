@@ -334,6 +334,10 @@ fn main() {
         visualization::fancy(&map).unwrap();
         return;
     }
+    if param == "record" {
+        visualization::record(&map).unwrap();
+        return;
+    }
 
     println!("Part 1: {}", outcome(&map));
     println!("Part 2: {}", outcome_no_dead_elves(&map));
@@ -1,86 +1,71 @@
-use std::{io::stdout, thread, time::Duration};
+use std::{io, time::Duration};
 
-use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
-use ratatui::{
-    backend::CrosstermBackend,
-    layout::Rect,
-    style::{Color, Style, Stylize},
-    text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
-    Terminal,
-};
+use visu::{Color, FrameSource, Palette, Player};
 
-use crate::{build_units_list, do_action, grid::Grid, is_full_unit_dead};
+use crate::{build_units_list, do_action, grid::Grid, is_full_unit_dead, Unit};
 
-type Err = Box<dyn std::error::Error>;
-type Result<T> = std::result::Result<T, Err>;
+// The battle, one round per frame.
+struct Battle {
+    map: Grid,
+    units: Vec<Unit>,
+    combat_rounds: usize,
+    over: bool,
+}
 
-pub fn fancy(map: &Grid) -> Result<()> {
-    crossterm::execute!(stdout(), EnterAlternateScreen)?;
-    // We don't need raw mode, as we don't capture input. Not having raw mode allows ctrl-c to work for example.
-    // enable_raw_mode()?;
+impl FrameSource for Battle {
+    fn size(&self) -> (usize, usize) {
+        (self.map.rows, self.map.cols)
+    }
 
-    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
-    terminal.clear()?;
+    fn symbol(&self, row: usize, col: usize) -> char {
+        self.map.values[self.map.pos(row, col)]
+    }
 
-    let mut map = map.clone();
-    let mut units = build_units_list(&map);
+    fn title(&self) -> String {
+        format!("Round {}", self.combat_rounds)
+    }
 
-    let mut combat_rounds = 0;
-    'outer: loop {
-        units.sort_by_key(|u| u.position);
-        for i in 0..units.len() {
-            if is_full_unit_dead(&units) {
-                break 'outer;
-            }
-            do_action(&mut map, &mut units, i);
+    fn advance(&mut self) -> bool {
+        if self.over {
+            return false;
         }
-        combat_rounds += 1;
-        units.retain(|u| !u.is_dead());
-
-        let mut text = Vec::new();
-        for row in 0..map.rows {
-            let mut spans_vec = vec![];
-            for p in row * map.cols..(row + 1) * map.cols {
-                let c = map.values[p];
-                let mut span = Span::styled(format!("{c}"), Style::default().fg(Color::Black));
-                if c == '.' {
-                    span.style = Style::default().fg(Color::Gray);
-                }
-                if c == 'G' {
-                    span.style = Style::default().fg(Color::Red);
-                }
-                if c == 'E' {
-                    span.style = Style::default().fg(Color::Green);
-                }
-                spans_vec.push(span);
+        self.units.sort_by_key(|u| u.position);
+        for i in 0..self.units.len() {
+            if is_full_unit_dead(&self.units) {
+                self.over = true;
+                return true;
             }
-            text.push(Line::from(spans_vec));
+            do_action(&mut self.map, &mut self.units, i);
         }
-        let p = Paragraph::new(text)
-            .block(
-                Block::default()
-                    .title(format!("Round {combat_rounds}"))
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().black()),
-            )
-            .style(Style::default().fg(Color::White))
-            .wrap(Wrap { trim: true });
+        self.combat_rounds += 1;
+        self.units.retain(|u| !u.is_dead());
+        true
+    }
+}
 
-        terminal.draw(|f| {
-            let size = Rect::new(
-                0,
-                0,
-                u16::try_from(map.cols).unwrap() + 2,
-                u16::try_from(map.rows).unwrap() + 2,
-            );
-            f.render_widget(p, size);
-        })?;
+fn player() -> Player {
+    let palette = Palette::new()
+        .with('.', Color::Gray)
+        .with('G', Color::Red)
+        .with('E', Color::Green)
+        .with_default(Color::Black);
+    Player::new(palette).delay(Duration::from_millis(100))
+}
 
-        thread::sleep(Duration::from_millis(100));
+fn battle(map: &Grid) -> Battle {
+    Battle {
+        map: map.clone(),
+        units: build_units_list(map),
+        combat_rounds: 0,
+        over: false,
     }
+}
+
+pub fn fancy(map: &Grid) -> io::Result<()> {
+    player().play(&mut battle(map))
+}
 
-    crossterm::execute!(std::io::stdout(), LeaveAlternateScreen)?;
-    // disable_raw_mode()?;
-    Ok(())
+// Writes the battle as an asciicast file to stdout.
+pub fn record(map: &Grid) -> io::Result<()> {
+    player().record(&mut battle(map), io::stdout().lock())
 }
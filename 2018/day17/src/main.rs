@@ -4,6 +4,9 @@ use std::{
 };
 
 mod parsing;
+mod render;
+
+use render::{FrameSequence, Image, Palette};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Material {
@@ -110,7 +113,7 @@ impl Grid {
         !match direction {
             Up => pos < self.cols,
             Down => pos / self.cols == self.rows - 1,
-            Left => pos % self.cols == 0,
+            Left => pos.is_multiple_of(self.cols),
             Right => pos % self.cols == self.cols - 1,
         }
     }
@@ -140,6 +143,22 @@ impl Grid {
             println!();
         }
     }
+
+    // Image of the grid, each tile being a square of `scale` pixels.
+    fn image(&self, scale: usize) -> Image {
+        let palette = Palette::new([240, 220, 170])
+            .with(Clay, [120, 80, 40])
+            .with(Spring, [255, 0, 0])
+            .with(WaterAtRest, [30, 60, 200])
+            .with(WaterFlow, [100, 180, 255]);
+        Image::from_grid(
+            self.rows,
+            self.cols,
+            |row, col| self.values[self.pos(col, row)],
+            &palette,
+            scale,
+        )
+    }
 }
 
 fn find_flows(grid: &Grid) -> Vec<usize> {
@@ -157,7 +176,6 @@ fn find_flows(grid: &Grid) -> Vec<usize> {
 }
 
 // Moving the flows to left or right, saving the positions of the flows we added,
-#[allow(clippy::match_on_vec_items)]
 fn move_flow_to_side(
     grid: &mut Grid,
     pos: usize,
@@ -228,11 +246,16 @@ fn fill_space_with_water(grid: &mut Grid, flows: &[usize]) -> bool {
     something_happened
 }
 
-#[allow(clippy::match_on_vec_items)]
 fn fill_water(grid: &mut Grid) {
+    fill_water_with(grid, |_| {});
+}
+
+// Calls `on_step` before each iteration of the big loop.
+fn fill_water_with(grid: &mut Grid, mut on_step: impl FnMut(&Grid)) {
     // Tracks if water moved in an iteration of the big loop.
     let mut something_happened = true;
     while something_happened {
+        on_step(grid);
         something_happened = false;
 
         // Find all flows and see if we can go down
@@ -301,12 +324,30 @@ fn main() {
     io::stdin().read_to_string(&mut input).unwrap();
 
     let mut grid = Grid::build(&input);
-    fill_water(&mut grid);
 
     let param = std::env::args().nth(1).unwrap_or_default();
+    if param == "frames" {
+        // One image every 100 iterations, in the frames directory.
+        const FRAME_INTERVAL: usize = 100;
+        let mut frames = FrameSequence::new("frames", "water").unwrap();
+        let mut iteration: usize = 0;
+        fill_water_with(&mut grid, |g| {
+            if iteration.is_multiple_of(FRAME_INTERVAL) {
+                frames.add(&g.image(1)).unwrap();
+            }
+            iteration += 1;
+        });
+        frames.add(&grid.image(1)).unwrap();
+    } else {
+        fill_water(&mut grid);
+    }
+
     if param == "visu" {
         grid.print();
     }
+    if param == "png" {
+        grid.image(2).save("water.png").unwrap();
+    }
 
     let water_at_rest_count = count_tiles(&grid, WaterAtRest);
     let water_flow_count = count_tiles(&grid, WaterFlow);
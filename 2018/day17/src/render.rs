@@ -0,0 +1,194 @@
+//! Export of grids as [PNG](https://www.w3.org/TR/png/) images, to look at large simulations outside of the terminal.
+//! To avoid any dependency, the PNG data isn't compressed.
+//!
+//! Copied from utils, with only the PNG export.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub type Rgb = [u8; 3];
+
+/// Colors of the cells.
+#[derive(Debug, Clone)]
+pub struct Palette<T> {
+    colors: Vec<(T, Rgb)>,
+    default: Rgb,
+}
+
+impl<T: PartialEq> Palette<T> {
+    /// Palette where all the cells have the default color.
+    pub fn new(default: Rgb) -> Self {
+        Self {
+            colors: Vec::new(),
+            default,
+        }
+    }
+
+    pub fn with(mut self, cell: T, color: Rgb) -> Self {
+        self.colors.retain(|(c, _)| *c != cell);
+        self.colors.push((cell, color));
+        self
+    }
+
+    pub fn color(&self, cell: &T) -> Rgb {
+        self.colors
+            .iter()
+            .find(|(c, _)| c == cell)
+            .map_or(self.default, |(_, color)| *color)
+    }
+}
+
+/// An RGB image.
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, color: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![color; width * height],
+        }
+    }
+
+    /// Draws a grid, each cell being a square of `scale` x `scale` pixels.
+    pub fn from_grid<T: PartialEq>(
+        rows: usize,
+        cols: usize,
+        cell: impl Fn(usize, usize) -> T,
+        palette: &Palette<T>,
+        scale: usize,
+    ) -> Self {
+        let mut image = Self::new(cols * scale, rows * scale, palette.default);
+        for row in 0..rows {
+            for col in 0..cols {
+                let color = palette.color(&cell(row, col));
+                for y in row * scale..(row + 1) * scale {
+                    image.pixels
+                        [y * image.width + col * scale..y * image.width + (col + 1) * scale]
+                        .fill(color);
+                }
+            }
+        }
+        image
+    }
+
+    /// PNG, with uncompressed data.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend(u32::try_from(self.width).unwrap().to_be_bytes());
+        header.extend(u32::try_from(self.height).unwrap().to_be_bytes());
+        // Bit depth 8, color type RGB, default compression, filter and no interlace.
+        header.extend([8, 2, 0, 0, 0]);
+
+        // Each line starts with the filter type, 0 for none.
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for line in self.pixels.chunks(self.width.max(1)) {
+            raw.push(0);
+            raw.extend(line.iter().flatten());
+        }
+
+        let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut data, *b"IHDR", &header);
+        png_chunk(&mut data, *b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut data, *b"IEND", &[]);
+        data
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_png())
+    }
+}
+
+/// Writes numbered frames in a directory: `prefix_0000.png`, `prefix_0001.png`, etc.
+///
+/// They can be turned into an animation with `ffmpeg -i prefix_%04d.png anim.gif` for example.
+pub struct FrameSequence {
+    dir: PathBuf,
+    prefix: String,
+    count: usize,
+}
+
+impl FrameSequence {
+    /// Creates the directory if needed.
+    pub fn new(dir: impl AsRef<Path>, prefix: &str) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+            prefix: prefix.to_string(),
+            count: 0,
+        })
+    }
+
+    pub fn path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("{}_{index:04}.png", self.prefix))
+    }
+
+    /// Writes the next frame, returning its path.
+    pub fn add(&mut self, image: &Image) -> io::Result<PathBuf> {
+        let path = self.path(self.count);
+        image.save(&path)?;
+        self.count += 1;
+        Ok(path)
+    }
+}
+
+fn png_chunk(data: &mut Vec<u8>, kind: [u8; 4], content: &[u8]) {
+    data.extend(u32::try_from(content.len()).unwrap().to_be_bytes());
+    let start = data.len();
+    data.extend(kind);
+    data.extend(content);
+    let crc = crc32(&data[start..]);
+    data.extend(crc.to_be_bytes());
+}
+
+// zlib stream made of deflate blocks without compression.
+fn zlib_stored(raw: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65535;
+    let mut data = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if raw.is_empty() {
+        vec![&[]]
+    } else {
+        raw.chunks(MAX_BLOCK).collect()
+    };
+    for (i, block) in blocks.iter().enumerate() {
+        // Only the final block has its first bit set, and type 0 is no compression.
+        data.push(u8::from(i == blocks.len() - 1));
+        let len = u16::try_from(block.len()).unwrap();
+        data.extend(len.to_le_bytes());
+        data.extend((!len).to_le_bytes());
+        data.extend(*block);
+    }
+    data.extend(adler32(raw).to_be_bytes());
+    data
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1, 0);
+    for byte in data {
+        a = (a + u32::from(*byte)) % MOD;
+        b = (b + a) % MOD;
+    }
+    (b << 16) | a
+}
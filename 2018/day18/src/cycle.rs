@@ -0,0 +1,62 @@
+//! Cycle detection, to know the state of a simulation after a huge number of steps.
+//!
+//! Copied from utils, only with `state_at`.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The states starting at step `start` repeat every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cycle {
+    start: usize,
+    length: usize,
+}
+
+// Runs the simulation until a key repeats, without going past step `max_steps`.
+// `visit` is called on each state, including the initial one.
+// When a cycle is found, the state is left at step `start + length`, otherwise at `max_steps`.
+fn detect<T, K: Eq + Hash>(
+    state: &mut T,
+    step: &mut impl FnMut(&mut T),
+    key: impl Fn(&T) -> K,
+    max_steps: usize,
+    mut visit: impl FnMut(&T),
+) -> Option<Cycle> {
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    for i in 0..=max_steps {
+        visit(state);
+        if let Some(start) = seen.insert(key(state), i) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+        if i < max_steps {
+            step(state);
+        }
+    }
+    None
+}
+
+/// The state after `n` steps.
+pub fn state_at<T: Clone + Eq + Hash>(state: T, step: impl FnMut(&mut T), n: usize) -> T {
+    state_at_by(state, step, T::clone, n)
+}
+
+/// The state after `n` steps, detecting the cycle on a key of the state.
+/// Two states with the same key must evolve the same way.
+pub fn state_at_by<T, K: Eq + Hash>(
+    mut state: T,
+    mut step: impl FnMut(&mut T),
+    key: impl Fn(&T) -> K,
+    n: usize,
+) -> T {
+    if let Some(cycle) = detect(&mut state, &mut step, key, n, |_| {}) {
+        // We are at step start + length, which is like step start.
+        let current = cycle.start + cycle.length;
+        for _ in 0..(n - current) % cycle.length {
+            step(&mut state);
+        }
+    }
+    state
+}
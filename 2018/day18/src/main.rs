@@ -3,9 +3,10 @@ use std::{
     io::{self, Read},
 };
 
+mod cycle;
 mod visualization;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Area {
     OpenGround,
     Tree,
@@ -55,7 +56,7 @@ const ALL_DIRECTIONS: [Direction; 8] = [
     North, East, South, West, NorthEast, NorthWest, SouthEast, SouthWest,
 ];
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Grid {
     values: Vec<Area>,
     rows: usize,
@@ -109,7 +110,7 @@ impl Grid {
     }
 
     fn west_forbidden(&self, pos: usize) -> bool {
-        pos % self.cols == 0
+        pos.is_multiple_of(self.cols)
     }
 
     fn direction_forbidden(&self, pos: usize, direction: Direction) -> bool {
@@ -164,9 +165,7 @@ fn transform(grid: &Grid, pos: usize) -> Area {
             }
         }
         Lumberyard => {
-            if neighbors_area.iter().any(|&a| a == Lumberyard)
-                && neighbors_area.iter().any(|&a| a == Tree)
-            {
+            if neighbors_area.contains(&Lumberyard) && neighbors_area.contains(&Tree) {
                 a = Lumberyard;
             } else {
                 a = OpenGround;
@@ -197,32 +196,10 @@ fn resource_after_10_min(lumber_collection: &Grid) -> usize {
 }
 
 fn resource_after_1000_years(lumber_collection: &Grid) -> usize {
-    // The pattern becomes periodic.
     const TIME: usize = 1_000_000_000;
-    const START_OFFSET: usize = 1010;
-
-    let mut grid = lumber_collection.clone();
-
-    // Period doesn't start immediately, moving ahead.
-    for _ in 0..START_OFFSET {
-        advance_one_minute(&mut grid);
-    }
-
-    let period_start_grid = grid.clone();
-    let mut period = 0;
-    loop {
-        period += 1;
-        advance_one_minute(&mut grid);
-        if grid.values == period_start_grid.values {
-            break;
-        }
-    }
-
-    let jump_ahead_start = START_OFFSET + ((TIME - START_OFFSET) / period) * period;
-    for _ in jump_ahead_start..TIME {
-        advance_one_minute(&mut grid);
-    }
 
+    // The pattern becomes periodic, so we can jump ahead.
+    let grid = cycle::state_at(lumber_collection.clone(), advance_one_minute, TIME);
     total_resource_value(&grid)
 }
 
@@ -237,6 +214,10 @@ fn main() {
         visualization::fancy(&lumber_collection).unwrap();
         return;
     }
+    if param == "record" {
+        visualization::record(&lumber_collection).unwrap();
+        return;
+    }
 
     println!("Part 1: {}", resource_after_10_min(&lumber_collection));
     println!("Part 2: {}", resource_after_1000_years(&lumber_collection));
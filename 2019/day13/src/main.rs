@@ -1,7 +1,6 @@
 use std::{
     cmp::Ordering,
     io::{self, Read},
-    thread,
     time::Duration,
 };
 
@@ -16,8 +15,7 @@ use ratatui::{
 
 use fxhash::FxHashMap;
 use intcode::IntcodeComputer;
-
-mod terminal;
+use visu::{terminal, FrameSource, Palette, Player};
 
 const WINNING_INPUT: &str = "resources/winning_computer_input";
 
@@ -45,6 +43,18 @@ impl TileType {
             _ => panic!("Invalid tile type {v}"),
         }
     }
+
+    const EMPTY_SYMBOL: char = '\u{2B1C}';
+
+    fn symbol(self) -> char {
+        match self {
+            TileType::Empty => Self::EMPTY_SYMBOL,
+            TileType::Wall => '\u{1F7EB}',   // brown square
+            TileType::Block => '\u{1F7E6}',  // blue square
+            TileType::Paddle => '\u{1F7E8}', // yellow square
+            TileType::Ball => '\u{1F534}',   // red circle
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -286,48 +296,6 @@ impl App {
         Ok(())
     }
 
-    /// Plays the game automatically using the moves saved in the winning input file.
-    fn auto_run_from_file(&mut self, terminal: &mut terminal::Tui) -> io::Result<()> {
-        self.load_input(WINNING_INPUT);
-
-        // First screen
-        self.display.update(&mut self.computer);
-        terminal.draw(|frame| self.render_frame(frame))?;
-
-        for i in &self.saved_inputs {
-            thread::sleep(Duration::from_millis(10));
-
-            self.computer.io.add_input(*i);
-            self.display.update(&mut self.computer);
-
-            terminal.draw(|frame| self.render_frame(frame))?;
-        }
-
-        thread::sleep(Duration::from_millis(1000));
-        Ok(())
-    }
-
-    /// Plays the game automatically using the moves saved in the winning input file.
-    fn auto_run(&mut self, terminal: &mut terminal::Tui) -> io::Result<()> {
-        // First screen
-        self.display.update(&mut self.computer);
-        terminal.draw(|frame| self.render_frame(frame))?;
-
-        while !self.computer.is_halted() {
-            // Making it quite fast
-            thread::sleep(Duration::from_millis(2));
-
-            let i = self.display.calc_next_input();
-            self.computer.io.add_input(i);
-            self.display.update(&mut self.computer);
-
-            terminal.draw(|frame| self.render_frame(frame))?;
-        }
-
-        thread::sleep(Duration::from_millis(3000));
-        Ok(())
-    }
-
     fn render_frame(&self, frame: &mut Frame) {
         frame.render_widget(self, frame.size());
     }
@@ -340,7 +308,7 @@ impl App {
                 self.handle_key_event(key_event);
             }
             _ => {}
-        };
+        }
         Ok(())
     }
 
@@ -384,18 +352,11 @@ impl Widget for &App {
         for y in 0..=self.display.max_dims.unwrap().y {
             let mut spans_vec = vec![];
             for x in 0..=self.display.max_dims.unwrap().x {
-                const EMPTY: &str = "\u{2B1C}";
-                let c = if let Some(tile) = self.display.tiles.get(&Pos::new(x, y)) {
-                    match tile {
-                        TileType::Empty => EMPTY,
-                        TileType::Wall => "\u{1F7EB}",  // brown square
-                        TileType::Block => "\u{1F7E6}", // blue square
-                        TileType::Paddle => "\u{1F7E8}", // yellow square
-                        TileType::Ball => "\u{1F534}",  // red circle
-                    }
-                } else {
-                    EMPTY
-                };
+                let c = self
+                    .display
+                    .tiles
+                    .get(&Pos::new(x, y))
+                    .map_or(TileType::EMPTY_SYMBOL, |tile| tile.symbol());
                 let span = Span::styled(c.to_string(), Style::default().fg(Color::Black));
                 spans_vec.push(span);
             }
@@ -447,6 +408,75 @@ impl Widget for &App {
     }
 }
 
+/// The game playing on its own, one move per frame.
+struct AutoGame {
+    computer: IntcodeComputer,
+    display: Display,
+    // The moves saved in the winning input file, if we use them.
+    saved_inputs: Option<std::vec::IntoIter<i64>>,
+}
+
+impl AutoGame {
+    fn new(computer: &IntcodeComputer, from_file: bool) -> Self {
+        let mut computer = computer.clone();
+        // Enable game mode
+        computer.write_mem(0, 2);
+
+        let mut display = Display::empty();
+        display.update(&mut computer);
+
+        let saved_inputs = from_file.then(|| {
+            let input = std::fs::read_to_string(WINNING_INPUT).expect("Missing saved file");
+            parse_computer_input(&input).into_iter()
+        });
+        Self {
+            computer,
+            display,
+            saved_inputs,
+        }
+    }
+}
+
+impl FrameSource for AutoGame {
+    fn size(&self) -> (usize, usize) {
+        let max_dims = self.display.max_dims.unwrap();
+        (max_dims.y + 1, max_dims.x + 1)
+    }
+
+    fn symbol(&self, row: usize, col: usize) -> char {
+        self.display
+            .tiles
+            .get(&Pos::new(col, row))
+            .map_or(TileType::EMPTY_SYMBOL, |tile| tile.symbol())
+    }
+
+    fn title(&self) -> String {
+        if self.computer.is_halted() {
+            format!("Game Over! Score: {}", self.display.score)
+        } else {
+            format!("Score: {}", self.display.score)
+        }
+    }
+
+    fn advance(&mut self) -> bool {
+        if self.computer.is_halted() {
+            return false;
+        }
+        let i = match &mut self.saved_inputs {
+            Some(inputs) => {
+                let Some(i) = inputs.next() else {
+                    return false;
+                };
+                i
+            }
+            None => self.display.calc_next_input(),
+        };
+        self.computer.io.add_input(i);
+        self.display.update(&mut self.computer);
+        true
+    }
+}
+
 fn main() -> io::Result<()> {
     let param = std::env::args().nth(1).unwrap_or_default();
     if !param.is_empty() {
@@ -454,16 +484,16 @@ fn main() -> io::Result<()> {
         let input = std::fs::read_to_string("resources/input").expect("Unable to read input file");
         let computer = IntcodeComputer::build(&input);
 
-        if param.starts_with("auto") {
-            let mut terminal = terminal::init(false)?;
-            let mut app = App::new(&computer, false);
-            let app_result = if param == "auto_file" {
-                app.auto_run_from_file(&mut terminal)
-            } else {
-                app.auto_run(&mut terminal)
-            };
-            terminal::restore(false)?;
-            return app_result;
+        if param.starts_with("auto") || param == "record" {
+            let from_file = param == "auto_file";
+            let mut game = AutoGame::new(&computer, from_file);
+            // Making it quite fast
+            let delay = Duration::from_millis(if from_file { 10 } else { 2 });
+            let player = Player::new(Palette::new()).delay(delay);
+            if param == "record" {
+                return player.record(&mut game, io::stdout().lock());
+            }
+            return player.play(&mut game);
         }
 
         let mut terminal = terminal::init(true)?;
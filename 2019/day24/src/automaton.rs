@@ -0,0 +1,258 @@
+//! Cellular automata, on grids of any dimension or on hexagonal grids.
+//!
+//! Copied from utils, only with the von Neumann neighborhood, the neighborhoods given per cell
+//! and the totalistic rules, with which the background stays dead.
+
+use std::{fmt, rc::Rc};
+
+use fxhash::FxHashSet;
+
+/// Coordinates of a cell.
+pub type Point<const N: usize> = [i32; N];
+
+fn add<const N: usize>(p: &Point<N>, offset: &Point<N>) -> Point<N> {
+    std::array::from_fn(|d| p[d] + offset[d])
+}
+
+fn sub<const N: usize>(p: &Point<N>, offset: &Point<N>) -> Point<N> {
+    std::array::from_fn(|d| p[d] - offset[d])
+}
+
+/// Gives the neighbors of a cell.
+pub type NeighborsFn<const N: usize> = dyn Fn(&Point<N>) -> Vec<Point<N>>;
+
+/// The cells whose states give the next state of a cell.
+#[derive(Clone)]
+pub enum Neighborhood<const N: usize> {
+    /// The same offsets from every cell.
+    Offsets(Vec<Point<N>>),
+    /// The neighbors of each cell.
+    /// The relation must be symmetric, as the sparse storage looks for the cells that may change
+    /// among the neighbors of the alive ones.
+    Fn(Rc<NeighborsFn<N>>),
+}
+
+impl<const N: usize> Neighborhood<N> {
+    /// A neighborhood given for each cell.
+    pub fn from_fn(neighbors: impl Fn(&Point<N>) -> Vec<Point<N>> + 'static) -> Self {
+        Self::Fn(Rc::new(neighbors))
+    }
+
+    /// The 2 * N cells sharing a side with the cell.
+    pub fn von_neumann() -> Self {
+        let offsets = (0..N)
+            .flat_map(|d| {
+                [-1, 1].map(|v| {
+                    let mut offset = [0; N];
+                    offset[d] = v;
+                    offset
+                })
+            })
+            .collect();
+        Self::Offsets(offsets)
+    }
+}
+
+impl<const N: usize> fmt::Debug for Neighborhood<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Offsets(offsets) => f.debug_tuple("Offsets").field(offsets).finish(),
+            Self::Fn(_) => f.write_str("Fn"),
+        }
+    }
+}
+
+/// How the next state of a cell is computed from its state and the ones of its neighbors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// Depends only on the number of alive neighbors: A dead cell becomes alive if this number is in `birth`,
+    /// an alive one stays alive if it's in `survive`.
+    Totalistic {
+        birth: Vec<usize>,
+        survive: Vec<usize>,
+    },
+}
+
+impl Rule {
+    /// A totalistic rule in the B/S notation, like "B3/S23" for the Game of Life.
+    pub fn parse(notation: &str) -> Self {
+        let counts = |part: &str, prefix: char| -> Vec<usize> {
+            part.strip_prefix(prefix)
+                .expect("Invalid rule notation")
+                .chars()
+                .map(|c| c.to_digit(10).expect("Invalid count in rule") as usize)
+                .collect()
+        };
+        let (birth, survive) = notation.split_once('/').expect("Invalid rule notation");
+        Self::Totalistic {
+            birth: counts(birth, 'B'),
+            survive: counts(survive, 'S'),
+        }
+    }
+
+    fn next(&self, alive: bool, neighbors: impl Iterator<Item = bool>) -> bool {
+        match self {
+            Self::Totalistic { birth, survive } => {
+                let count = neighbors.filter(|n| *n).count();
+                if alive {
+                    survive.contains(&count)
+                } else {
+                    birth.contains(&count)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Cells<const N: usize> {
+    // All the cells inside the bounds, the ones outside being dead.
+    Dense { size: [usize; N], states: Vec<bool> },
+    // Only the alive cells.
+    Sparse(FxHashSet<Point<N>>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton<const N: usize> {
+    neighborhood: Neighborhood<N>,
+    rule: Rule,
+    cells: Cells<N>,
+}
+
+impl<const N: usize> Automaton<N> {
+    /// An automaton on the cells with coordinates from 0 to `size` excluded, all dead.
+    pub fn dense(size: [usize; N], neighborhood: Neighborhood<N>, rule: Rule) -> Self {
+        let states = vec![false; size.iter().product()];
+        Self {
+            neighborhood,
+            rule,
+            cells: Cells::Dense { size, states },
+        }
+    }
+
+    /// An automaton on an infinite grid, all dead.
+    pub fn sparse(neighborhood: Neighborhood<N>, rule: Rule) -> Self {
+        Self {
+            neighborhood,
+            rule,
+            cells: Cells::Sparse(FxHashSet::default()),
+        }
+    }
+
+    /// Sets the cells from a text, each line being a row.
+    /// The points are `[row, col, 0, ...]`, so N must be at least 2.
+    pub fn set_text(&mut self, input: &str, alive: char) {
+        for (row, line) in input.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                let mut p = [0; N];
+                p[0] = i32::try_from(row).unwrap();
+                p[1] = i32::try_from(col).unwrap();
+                self.set(p, c == alive);
+            }
+        }
+    }
+
+    fn index(size: &[usize; N], p: &Point<N>) -> Option<usize> {
+        let mut index = 0;
+        for d in 0..N {
+            let v = usize::try_from(p[d]).ok().filter(|v| *v < size[d])?;
+            index = index * size[d] + v;
+        }
+        Some(index)
+    }
+
+    fn point(size: &[usize; N], mut index: usize) -> Point<N> {
+        let mut p = [0; N];
+        for d in (0..N).rev() {
+            p[d] = i32::try_from(index % size[d]).unwrap();
+            index /= size[d];
+        }
+        p
+    }
+
+    pub fn get(&self, p: &Point<N>) -> bool {
+        match &self.cells {
+            Cells::Dense { size, states } => {
+                Self::index(size, p).is_some_and(|index| states[index])
+            }
+            Cells::Sparse(cells) => cells.contains(p),
+        }
+    }
+
+    /// Sets the state of a cell. On a dense automaton, the cell must be inside the bounds.
+    pub fn set(&mut self, p: Point<N>, alive: bool) {
+        match &mut self.cells {
+            Cells::Dense { size, states } => {
+                let index = Self::index(size, &p).expect("Cell outside of the automaton");
+                states[index] = alive;
+            }
+            Cells::Sparse(cells) => {
+                if alive {
+                    cells.insert(p);
+                } else {
+                    cells.remove(&p);
+                }
+            }
+        }
+    }
+
+    pub fn alive_count(&self) -> usize {
+        match &self.cells {
+            Cells::Dense { states, .. } => states.iter().filter(|s| **s).count(),
+            Cells::Sparse(cells) => cells.len(),
+        }
+    }
+
+    fn next_state(&self, p: &Point<N>) -> bool {
+        let alive = self.get(p);
+        match &self.neighborhood {
+            Neighborhood::Offsets(offsets) => self.rule.next(
+                alive,
+                offsets.iter().map(|offset| self.get(&add(p, offset))),
+            ),
+            Neighborhood::Fn(neighbors) => self
+                .rule
+                .next(alive, neighbors(p).iter().map(|n| self.get(n))),
+        }
+    }
+
+    /// Computes the next generation.
+    pub fn step(&mut self) {
+        let cells = match &self.cells {
+            Cells::Dense { size, states } => Cells::Dense {
+                size: *size,
+                states: (0..states.len())
+                    .map(|index| self.next_state(&Self::point(size, index)))
+                    .collect(),
+            },
+            Cells::Sparse(cells) => {
+                // Only alive cells and their neighbors can be alive next.
+                let mut candidates = FxHashSet::default();
+                for p in cells {
+                    candidates.insert(*p);
+                    match &self.neighborhood {
+                        Neighborhood::Offsets(offsets) => {
+                            candidates.extend(offsets.iter().map(|offset| sub(p, offset)));
+                        }
+                        // The relation being symmetric, the cells having p as neighbor are its neighbors.
+                        Neighborhood::Fn(neighbors) => candidates.extend(neighbors(p)),
+                    }
+                }
+                Cells::Sparse(
+                    candidates
+                        .into_iter()
+                        .filter(|p| self.next_state(p))
+                        .collect(),
+                )
+            }
+        };
+        self.cells = cells;
+    }
+
+    /// Computes that many generations.
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+}
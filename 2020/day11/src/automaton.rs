@@ -0,0 +1,146 @@
+//! Cellular automata, on grids of any dimension or on hexagonal grids.
+//!
+//! Copied from utils, only with the dense storage, the neighborhoods given per cell
+//! and the totalistic rules, with which the background stays dead.
+
+use std::{fmt, rc::Rc};
+
+/// Coordinates of a cell.
+pub type Point<const N: usize> = [i32; N];
+
+/// Gives the neighbors of a cell.
+pub type NeighborsFn<const N: usize> = dyn Fn(&Point<N>) -> Vec<Point<N>>;
+
+/// The cells whose states give the next state of a cell.
+#[derive(Clone)]
+pub enum Neighborhood<const N: usize> {
+    /// The neighbors of each cell.
+    Fn(Rc<NeighborsFn<N>>),
+}
+
+impl<const N: usize> Neighborhood<N> {
+    /// A neighborhood given for each cell.
+    pub fn from_fn(neighbors: impl Fn(&Point<N>) -> Vec<Point<N>> + 'static) -> Self {
+        Self::Fn(Rc::new(neighbors))
+    }
+}
+
+impl<const N: usize> fmt::Debug for Neighborhood<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Fn(_) => f.write_str("Fn"),
+        }
+    }
+}
+
+/// How the next state of a cell is computed from its state and the ones of its neighbors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// Depends only on the number of alive neighbors: A dead cell becomes alive if this number is in `birth`,
+    /// an alive one stays alive if it's in `survive`.
+    Totalistic {
+        birth: Vec<usize>,
+        survive: Vec<usize>,
+    },
+}
+
+impl Rule {
+    fn next(&self, alive: bool, neighbors: impl Iterator<Item = bool>) -> bool {
+        match self {
+            Self::Totalistic { birth, survive } => {
+                let count = neighbors.filter(|n| *n).count();
+                if alive {
+                    survive.contains(&count)
+                } else {
+                    birth.contains(&count)
+                }
+            }
+        }
+    }
+}
+
+/// What happened during one generation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    /// Number of cells that became alive or died.
+    pub born: usize,
+    pub died: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton<const N: usize> {
+    neighborhood: Neighborhood<N>,
+    rule: Rule,
+    // All the cells inside the bounds, the ones outside being dead.
+    size: [usize; N],
+    states: Vec<bool>,
+}
+
+impl<const N: usize> Automaton<N> {
+    /// An automaton on the cells with coordinates from 0 to `size` excluded, all dead.
+    pub fn dense(size: [usize; N], neighborhood: Neighborhood<N>, rule: Rule) -> Self {
+        Self {
+            neighborhood,
+            rule,
+            size,
+            states: vec![false; size.iter().product()],
+        }
+    }
+
+    fn index(size: &[usize; N], p: &Point<N>) -> Option<usize> {
+        let mut index = 0;
+        for d in 0..N {
+            let v = usize::try_from(p[d]).ok().filter(|v| *v < size[d])?;
+            index = index * size[d] + v;
+        }
+        Some(index)
+    }
+
+    fn point(size: &[usize; N], mut index: usize) -> Point<N> {
+        let mut p = [0; N];
+        for d in (0..N).rev() {
+            p[d] = i32::try_from(index % size[d]).unwrap();
+            index /= size[d];
+        }
+        p
+    }
+
+    pub fn get(&self, p: &Point<N>) -> bool {
+        Self::index(&self.size, p).is_some_and(|index| self.states[index])
+    }
+
+    pub fn alive_count(&self) -> usize {
+        self.states.iter().filter(|s| **s).count()
+    }
+
+    fn next_state(&self, p: &Point<N>) -> bool {
+        let alive = self.get(p);
+        match &self.neighborhood {
+            Neighborhood::Fn(neighbors) => self
+                .rule
+                .next(alive, neighbors(p).iter().map(|n| self.get(n))),
+        }
+    }
+
+    /// Computes the next generation.
+    pub fn step(&mut self) -> Stats {
+        let mut born = 0;
+        let mut died = 0;
+        let next_states = (0..self.states.len())
+            .map(|index| {
+                let old = self.states[index];
+                let new = self.next_state(&Self::point(&self.size, index));
+                if old != new {
+                    if new {
+                        born += 1;
+                    } else {
+                        died += 1;
+                    }
+                }
+                new
+            })
+            .collect();
+        self.states = next_states;
+        Stats { born, died }
+    }
+}
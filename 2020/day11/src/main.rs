@@ -1,224 +1,109 @@
-use std::{
-    fmt,
-    io::{self, Read},
-};
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum SeatState {
-    Floor,
-    Empty,
-    Occupied,
-}
-
-impl SeatState {
-    fn new(c: char) -> Self {
-        match c {
-            '.' => Self::Floor,
-            'L' => Self::Empty,
-            '#' => Self::Occupied,
-            _ => panic!("Invalid state"),
-        }
-    }
-}
-
-impl fmt::Display for SeatState {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match *self {
-                Self::Floor => '.',
-                Self::Empty => 'L',
-                Self::Occupied => '#',
-            }
-        )
-    }
-}
-
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-    NorthEast,
-    NorthWest,
-    SouthEast,
-    SouthWest,
-}
-use Direction::{East, North, NorthEast, NorthWest, South, SouthEast, SouthWest, West};
-
-const ALL_DIRECTIONS: [Direction; 8] = [
-    North, East, South, West, NorthEast, NorthWest, SouthEast, SouthWest,
+use std::io::{self, Read};
+
+mod automaton;
+use automaton::{Automaton, Neighborhood, Point, Rule};
+
+const DIRECTIONS: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
 ];
 
-#[derive(Debug, Clone, PartialEq)]
-struct Grid {
-    values: Vec<SeatState>,
-    rows: usize,
-    cols: usize,
+// The floor never changes, so only the seats are cells of the automaton, numbered in reading order.
+struct Area {
+    // The number of the seat at each position, None for the floor.
+    seats: Vec<Vec<Option<i32>>>,
+    seats_count: usize,
 }
 
-impl Grid {
+impl Area {
     fn build(input: &str) -> Self {
-        let mut rows = 0;
-        let values: Vec<_> = input
+        let mut seats_count = 0;
+        let seats = input
             .lines()
-            .flat_map(|l| {
-                rows += 1;
-                l.chars().map(SeatState::new).collect::<Vec<_>>()
+            .map(|line| {
+                line.chars()
+                    .map(|c| match c {
+                        '.' => None,
+                        'L' => {
+                            seats_count += 1;
+                            Some(i32::try_from(seats_count - 1).unwrap())
+                        }
+                        _ => panic!("Invalid state"),
+                    })
+                    .collect()
             })
             .collect();
-        assert_eq!(values.len() % rows, 0);
-        let cols = values.len() / rows;
-        Self { values, rows, cols }
-    }
-
-    #[allow(dead_code)]
-    fn print(&self) {
-        for row in 0..self.rows {
-            for p in row * self.cols..(row + 1) * self.cols {
-                print!("{}", self.values[p]);
-            }
-            println!();
-        }
-    }
-
-    fn north_forbidden(&self, pos: usize) -> bool {
-        pos < self.cols
-    }
-
-    fn east_forbidden(&self, pos: usize) -> bool {
-        pos % self.cols == self.cols - 1
-    }
-
-    fn south_forbidden(&self, pos: usize) -> bool {
-        pos / self.cols == self.rows - 1
-    }
-
-    fn west_forbidden(&self, pos: usize) -> bool {
-        pos % self.cols == 0
-    }
-
-    fn direction_forbidden(&self, pos: usize, direction: &Direction) -> bool {
-        match direction {
-            North => self.north_forbidden(pos),
-            East => self.east_forbidden(pos),
-            South => self.south_forbidden(pos),
-            West => self.west_forbidden(pos),
-            NorthEast => self.north_forbidden(pos) || self.east_forbidden(pos),
-            NorthWest => self.north_forbidden(pos) || self.west_forbidden(pos),
-            SouthEast => self.south_forbidden(pos) || self.east_forbidden(pos),
-            SouthWest => self.south_forbidden(pos) || self.west_forbidden(pos),
-        }
-    }
-
-    fn position_in(&self, pos: usize, direction: &Direction) -> usize {
-        match direction {
-            North => pos - self.cols,
-            East => pos + 1,
-            South => pos + self.cols,
-            West => pos - 1,
-            NorthEast => pos - self.cols + 1,
-            NorthWest => pos - self.cols - 1,
-            SouthEast => pos + self.cols + 1,
-            SouthWest => pos + self.cols - 1,
-        }
-    }
-
-    fn count_adjacents_occupied(&self, pos: usize) -> usize {
-        ALL_DIRECTIONS
-            .iter()
-            .filter(|d| !self.direction_forbidden(pos, d))
-            .map(|d| self.position_in(pos, d))
-            .filter(|&p| self.values[p] == SeatState::Occupied)
-            .count()
-    }
-
-    fn count_adjacents_occupied_all_direction(&self, pos: usize) -> usize {
-        ALL_DIRECTIONS
-            .iter()
-            .filter(|d| {
-                let mut p = pos;
-                loop {
-                    if self.direction_forbidden(p, d) {
-                        return false;
-                    }
-                    p = self.position_in(p, d);
-                    if self.values[p] == SeatState::Occupied {
-                        return true;
-                    }
-                    if self.values[p] == SeatState::Empty {
-                        return false;
-                    }
-                }
-            })
-            .count()
-    }
-
-    fn apply_rule(&self, pos: usize, occ_count: usize, occupied_limit: usize) -> SeatState {
-        match self.values.get(pos) {
-            Some(SeatState::Empty) => {
-                if occ_count == 0 {
-                    return SeatState::Occupied;
+        Self { seats, seats_count }
+    }
+
+    // The seats visible from each seat, the first one in each direction at most `max_distance` positions away.
+    fn visible_seats(&self, max_distance: i32) -> Vec<Vec<Point<1>>> {
+        let position = |row: i32, col: i32| {
+            let row = usize::try_from(row).ok()?;
+            let col = usize::try_from(col).ok()?;
+            self.seats.get(row)?.get(col)
+        };
+        let mut visible = Vec::with_capacity(self.seats_count);
+        for (row, line) in (0..).zip(&self.seats) {
+            for (col, seat) in (0..).zip(line) {
+                if seat.is_none() {
+                    continue;
                 }
+                visible.push(
+                    DIRECTIONS
+                        .iter()
+                        .filter_map(|(dr, dc)| {
+                            (1..=max_distance)
+                                .map_while(|d| position(row + d * dr, col + d * dc))
+                                .find_map(|seat| seat.map(|s| [s]))
+                        })
+                        .collect(),
+                );
             }
-            Some(SeatState::Occupied) => {
-                if occ_count >= occupied_limit {
-                    return SeatState::Empty;
-                }
-            }
-            _ => {}
         }
-        self.values[pos]
-    }
-
-    fn count_occupied_seats(&self) -> usize {
-        self.values
-            .iter()
-            .filter(|&&v| v == SeatState::Occupied)
-            .count()
+        visible
     }
 }
 
-fn occupied_count_end(
-    grid: &Grid,
-    count_adjacents_occupied_fn: fn(&Grid, usize) -> usize,
-    occupied_limit: usize,
-) -> usize {
-    let mut grid = grid.clone();
-    let mut next_values: Vec<SeatState> = Vec::new();
+// Empty seats get occupied when no visible seat is, occupied ones are left when `tolerance` visible seats are too.
+fn occupied_count_end(area: &Area, max_distance: i32, tolerance: usize) -> usize {
+    let visible = area.visible_seats(max_distance);
+    let neighborhood =
+        Neighborhood::from_fn(move |&[seat]| visible[usize::try_from(seat).unwrap()].clone());
+    let rule = Rule::Totalistic {
+        birth: vec![0],
+        survive: (0..tolerance).collect(),
+    };
+    let mut automaton = Automaton::dense([area.seats_count], neighborhood, rule);
     loop {
-        for pos in 0..grid.values.len() {
-            let occ_count = count_adjacents_occupied_fn(&grid, pos);
-            next_values.push(grid.apply_rule(pos, occ_count, occupied_limit));
-        }
-
-        if grid.values == next_values {
-            break;
+        let stats = automaton.step();
+        if stats.born == 0 && stats.died == 0 {
+            return automaton.alive_count();
         }
-
-        std::mem::swap(&mut grid.values, &mut next_values);
-        next_values.clear();
     }
-
-    grid.count_occupied_seats()
 }
 
-fn occupied_count_end_rule1(grid: &Grid) -> usize {
-    occupied_count_end(grid, Grid::count_adjacents_occupied, 4)
+fn occupied_count_end_rule1(area: &Area) -> usize {
+    occupied_count_end(area, 1, 4)
 }
 
-fn occupied_count_end_rule2(grid: &Grid) -> usize {
-    occupied_count_end(grid, Grid::count_adjacents_occupied_all_direction, 5)
+fn occupied_count_end_rule2(area: &Area) -> usize {
+    occupied_count_end(area, i32::MAX, 5)
 }
 
 fn main() {
     let mut input = String::new();
     io::stdin().read_to_string(&mut input).unwrap();
-    let grid = Grid::build(&input);
+    let area = Area::build(&input);
 
-    println!("Part 1: {}", occupied_count_end_rule1(&grid));
-    println!("Part 2: {}", occupied_count_end_rule2(&grid));
+    println!("Part 1: {}", occupied_count_end_rule1(&area));
+    println!("Part 2: {}", occupied_count_end_rule2(&area));
 }
 
 #[cfg(test)]
@@ -229,11 +114,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(occupied_count_end_rule1(&Grid::build(INPUT_TEST)), 37);
+        assert_eq!(occupied_count_end_rule1(&Area::build(INPUT_TEST)), 37);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(occupied_count_end_rule2(&Grid::build(INPUT_TEST)), 26);
+        assert_eq!(occupied_count_end_rule2(&Area::build(INPUT_TEST)), 26);
     }
 }
@@ -0,0 +1,406 @@
+//! Assembly of square tiles into an image, by matching their borders.
+//! Copied from utils.
+//!
+//! Tiles can be rotated and flipped. The image can be any rectangle of tiles, and when
+//! several tiles could fit at a place, backtracking finds the one that allows completing
+//! the image.
+//!
+//! The assembled image can then be searched for a pattern in all orientations:
+//!
+//! ```text
+//! let tiles = jigsaw::parse_tiles(&input)?;
+//! let image = jigsaw::assemble(&tiles).unwrap().merge(true);
+//! let monsters = image.find_pattern(&Image::parse(SEA_MONSTER));
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// A rectangular image of pixels that can be rotated and flipped.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Image {
+    pub pixels: Vec<bool>,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Image {
+    pub fn new(pixels: Vec<bool>, rows: usize, cols: usize) -> Self {
+        assert_eq!(pixels.len(), rows * cols);
+        Self { pixels, rows, cols }
+    }
+
+    /// Parses `#` as lit pixels, any other character as not lit.
+    /// Lines don't need to have the same length, as in patterns with trailing spaces.
+    pub fn parse(text: &str) -> Self {
+        let lines: Vec<&str> = text.lines().collect();
+        let cols = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let pixels = lines
+            .iter()
+            .flat_map(|l| {
+                l.chars()
+                    .map(|c| c == '#')
+                    .chain(std::iter::repeat(false))
+                    .take(cols)
+            })
+            .collect();
+        Self::new(pixels, lines.len(), cols)
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.pixels[row * self.cols + col]
+    }
+
+    fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> bool) -> Self {
+        let pixels = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(|(row, col)| f(row, col))
+            .collect();
+        Self::new(pixels, rows, cols)
+    }
+
+    /// Rotates by 90 degrees clockwise.
+    pub fn rotate(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |row, col| {
+            self.get(self.rows - 1 - col, row)
+        })
+    }
+
+    /// Flips horizontally, left becoming right.
+    pub fn flip(&self) -> Self {
+        Self::from_fn(self.rows, self.cols, |row, col| {
+            self.get(row, self.cols - 1 - col)
+        })
+    }
+
+    /// The 8 orientations: 4 rotations, flipped or not. The first one is the image itself.
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut result = Vec::with_capacity(8);
+        let mut image = self.clone();
+        for _ in 0..4 {
+            let rotated = image.rotate();
+            result.push(image.flip());
+            result.push(image);
+            image = rotated;
+        }
+        result.swap(0, 1);
+        result
+    }
+
+    /// Borders from left to right, or top to bottom.
+    pub fn top(&self) -> Vec<bool> {
+        (0..self.cols).map(|col| self.get(0, col)).collect()
+    }
+
+    pub fn bottom(&self) -> Vec<bool> {
+        (0..self.cols)
+            .map(|col| self.get(self.rows - 1, col))
+            .collect()
+    }
+
+    pub fn left(&self) -> Vec<bool> {
+        (0..self.rows).map(|row| self.get(row, 0)).collect()
+    }
+
+    pub fn right(&self) -> Vec<bool> {
+        (0..self.rows)
+            .map(|row| self.get(row, self.cols - 1))
+            .collect()
+    }
+
+    /// Removes the first and last rows and columns.
+    pub fn strip_border(&self) -> Self {
+        Self::from_fn(self.rows - 2, self.cols - 2, |row, col| {
+            self.get(row + 1, col + 1)
+        })
+    }
+
+    pub fn lit_count(&self) -> usize {
+        self.pixels.iter().filter(|p| **p).count()
+    }
+
+    fn matches_at(&self, pattern: &Image, row: usize, col: usize) -> bool {
+        (0..pattern.rows)
+            .all(|r| (0..pattern.cols).all(|c| !pattern.get(r, c) || self.get(row + r, col + c)))
+    }
+
+    /// Finds the pattern in all its orientations. The lit pixels of the pattern must be lit
+    /// in the image, the others can be anything.
+    pub fn find_pattern(&self, pattern: &Image) -> Vec<Match> {
+        let mut orientations = pattern.orientations();
+        // Symmetric patterns have the same orientations several times.
+        let mut seen = HashSet::new();
+        orientations.retain(|o| seen.insert(o.clone()));
+
+        let mut matches = Vec::new();
+        for oriented in orientations {
+            if oriented.rows > self.rows || oriented.cols > self.cols {
+                continue;
+            }
+            for row in 0..=self.rows - oriented.rows {
+                for col in 0..=self.cols - oriented.cols {
+                    if self.matches_at(&oriented, row, col) {
+                        matches.push(Match {
+                            row,
+                            col,
+                            pattern: oriented.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+}
+
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                write!(f, "{}", if self.get(row, col) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A pattern found in an image, at the position of its top left corner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    pub row: usize,
+    pub col: usize,
+    /// The pattern, in the orientation it was found.
+    pub pattern: Image,
+}
+
+impl Match {
+    /// Positions (row, col) in the image of the lit pixels of the pattern.
+    pub fn pixels(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.pattern.rows)
+            .flat_map(|r| (0..self.pattern.cols).map(move |c| (r, c)))
+            .filter(|(r, c)| self.pattern.get(*r, *c))
+            .map(|(r, c)| (self.row + r, self.col + c))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tile {
+    pub id: u64,
+    pub image: Image,
+}
+
+/// Parses tiles like:
+///
+/// ```text
+/// Tile 2311:
+/// ..##.#..#.
+/// ##..#.....
+/// ```
+///
+/// separated by empty lines.
+pub fn parse_tiles(input: &str) -> Result<Vec<Tile>, String> {
+    input
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| {
+            let (header, image) = block
+                .trim()
+                .split_once('\n')
+                .ok_or_else(|| format!("Tile without image: {block}"))?;
+            let id = header
+                .strip_prefix("Tile ")
+                .and_then(|h| h.strip_suffix(':'))
+                .and_then(|id| id.parse().ok())
+                .ok_or_else(|| format!("Invalid tile header: {header}"))?;
+            let image = Image::parse(image);
+            if image.rows != image.cols {
+                return Err(format!("Tile {id} isn't square"));
+            }
+            Ok(Tile { id, image })
+        })
+        .collect()
+}
+
+/// Tiles placed in a grid, with their orientation applied.
+#[derive(Debug, Clone)]
+pub struct Assembly {
+    pub rows: usize,
+    pub cols: usize,
+    /// Tile IDs and images, row by row.
+    pub tiles: Vec<(u64, Image)>,
+}
+
+impl Assembly {
+    pub fn id_at(&self, row: usize, col: usize) -> u64 {
+        self.tiles[row * self.cols + col].0
+    }
+
+    /// IDs of the corners: top left, top right, bottom left, bottom right.
+    pub fn corner_ids(&self) -> [u64; 4] {
+        [
+            self.id_at(0, 0),
+            self.id_at(0, self.cols - 1),
+            self.id_at(self.rows - 1, 0),
+            self.id_at(self.rows - 1, self.cols - 1),
+        ]
+    }
+
+    /// Merges the tiles into one image, optionally removing the border of each tile.
+    pub fn merge(&self, strip_borders: bool) -> Image {
+        let tiles: Vec<Image> = self
+            .tiles
+            .iter()
+            .map(|(_, image)| {
+                if strip_borders {
+                    image.strip_border()
+                } else {
+                    image.clone()
+                }
+            })
+            .collect();
+        let size = tiles[0].rows;
+        Image::from_fn(self.rows * size, self.cols * size, |row, col| {
+            tiles[row / size * self.cols + col / size].get(row % size, col % size)
+        })
+    }
+}
+
+// A tile in one of its orientations, with its borders.
+struct Oriented {
+    tile: usize,
+    image: Image,
+    top: Vec<bool>,
+    left: Vec<bool>,
+    right: Vec<bool>,
+    bottom: Vec<bool>,
+}
+
+struct Solver<'a> {
+    cols: usize,
+    candidates: &'a [Oriented],
+    // Indexes of the candidates by their left and top borders.
+    by_left: &'a HashMap<&'a [bool], Vec<usize>>,
+    by_top: &'a HashMap<&'a [bool], Vec<usize>>,
+    all: &'a [usize],
+    used: Vec<bool>,
+    placed: Vec<usize>,
+}
+
+impl Solver<'_> {
+    fn solve(&mut self, count: usize) -> bool {
+        let pos = self.placed.len();
+        if pos == count {
+            return true;
+        }
+        let left = (!pos.is_multiple_of(self.cols)).then(|| &self.candidates[self.placed[pos - 1]]);
+        let above = (pos >= self.cols).then(|| &self.candidates[self.placed[pos - self.cols]]);
+        let possible = match (left, above) {
+            (Some(l), _) => self.by_left.get(l.right.as_slice()).map(Vec::as_slice),
+            (None, Some(a)) => self.by_top.get(a.bottom.as_slice()).map(Vec::as_slice),
+            (None, None) => Some(self.all),
+        };
+        let Some(possible) = possible else {
+            return false;
+        };
+        for &i in possible {
+            let candidate = &self.candidates[i];
+            if self.used[candidate.tile]
+                || left.is_some_and(|l| l.right != candidate.left)
+                || above.is_some_and(|a| a.bottom != candidate.top)
+            {
+                continue;
+            }
+            self.used[candidate.tile] = true;
+            self.placed.push(i);
+            if self.solve(count) {
+                return true;
+            }
+            self.placed.pop();
+            self.used[candidate.tile] = false;
+        }
+        false
+    }
+}
+
+/// Assembles all the tiles into a rectangle, trying first the shapes closest to a square.
+/// Returns None if the tiles don't fit together.
+pub fn assemble(tiles: &[Tile]) -> Option<Assembly> {
+    if tiles.is_empty() {
+        return None;
+    }
+    let count = tiles.len();
+
+    // Tiles with the fewest borders matching other tiles are likely corners, trying them first.
+    let borders = |image: &Image| [image.top(), image.right(), image.bottom(), image.left()];
+    let all_borders: Vec<Vec<bool>> = tiles
+        .iter()
+        .flat_map(|t| borders(&t.image))
+        .flat_map(|b| {
+            let reversed = b.iter().rev().copied().collect();
+            [b, reversed]
+        })
+        .collect();
+    let matching_borders = |tile: &Tile| {
+        borders(&tile.image)
+            .iter()
+            .filter(|b| all_borders.iter().filter(|o| o == b).count() > 1)
+            .count()
+    };
+    let mut order: Vec<usize> = (0..count).collect();
+    order.sort_by_key(|i| matching_borders(&tiles[*i]));
+
+    let candidates: Vec<Oriented> = order
+        .iter()
+        .flat_map(|&tile| {
+            tiles[tile]
+                .image
+                .orientations()
+                .into_iter()
+                .map(move |image| Oriented {
+                    tile,
+                    top: image.top(),
+                    left: image.left(),
+                    right: image.right(),
+                    bottom: image.bottom(),
+                    image,
+                })
+        })
+        .collect();
+
+    let mut by_left: HashMap<&[bool], Vec<usize>> = HashMap::new();
+    let mut by_top: HashMap<&[bool], Vec<usize>> = HashMap::new();
+    for (i, candidate) in candidates.iter().enumerate() {
+        by_left.entry(&candidate.left).or_default().push(i);
+        by_top.entry(&candidate.top).or_default().push(i);
+    }
+
+    let all: Vec<usize> = (0..candidates.len()).collect();
+
+    let mut shapes: Vec<(usize, usize)> = (1..=count)
+        .filter(|rows| count.is_multiple_of(*rows))
+        .map(|rows| (rows, count / rows))
+        .collect();
+    shapes.sort_by_key(|(rows, cols)| rows.abs_diff(*cols));
+
+    for (rows, cols) in shapes {
+        let mut solver = Solver {
+            cols,
+            candidates: &candidates,
+            by_left: &by_left,
+            by_top: &by_top,
+            all: &all,
+            used: vec![false; count],
+            placed: Vec::with_capacity(count),
+        };
+        if solver.solve(count) {
+            let tiles = solver
+                .placed
+                .iter()
+                .map(|&i| (tiles[candidates[i].tile].id, candidates[i].image.clone()))
+                .collect();
+            return Some(Assembly { rows, cols, tiles });
+        }
+    }
+    None
+}
@@ -0,0 +1,143 @@
+//! Cellular automata, on grids of any dimension or on hexagonal grids.
+//!
+//! Copied from utils, only with the sparse storage and the totalistic rules,
+//! with which the background stays dead.
+
+use fxhash::FxHashSet;
+
+/// Coordinates of a cell.
+pub type Point<const N: usize> = [i32; N];
+
+fn add<const N: usize>(p: &Point<N>, offset: &Point<N>) -> Point<N> {
+    std::array::from_fn(|d| p[d] + offset[d])
+}
+
+fn sub<const N: usize>(p: &Point<N>, offset: &Point<N>) -> Point<N> {
+    std::array::from_fn(|d| p[d] - offset[d])
+}
+
+/// The cells whose states give the next state of a cell, as offsets from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Neighborhood<const N: usize> {
+    offsets: Vec<Point<N>>,
+}
+
+impl Neighborhood<2> {
+    /// The 6 cells around a hexagon, with axial coordinates `[q, r]`:
+    /// East is `[1, 0]`, south-east `[0, 1]`, south-west `[-1, 1]`, west `[-1, 0]`,
+    /// north-west `[0, -1]` and north-east `[1, -1]`.
+    pub fn hex() -> Self {
+        Self {
+            offsets: vec![[1, 0], [0, 1], [-1, 1], [-1, 0], [0, -1], [1, -1]],
+        }
+    }
+}
+
+/// How the next state of a cell is computed from its state and the ones of its neighbors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// Depends only on the number of alive neighbors: A dead cell becomes alive if this number is in `birth`,
+    /// an alive one stays alive if it's in `survive`.
+    Totalistic {
+        birth: Vec<usize>,
+        survive: Vec<usize>,
+    },
+}
+
+impl Rule {
+    /// A totalistic rule in the B/S notation, like "B3/S23" for the Game of Life.
+    pub fn parse(notation: &str) -> Self {
+        let counts = |part: &str, prefix: char| -> Vec<usize> {
+            part.strip_prefix(prefix)
+                .expect("Invalid rule notation")
+                .chars()
+                .map(|c| c.to_digit(10).expect("Invalid count in rule") as usize)
+                .collect()
+        };
+        let (birth, survive) = notation.split_once('/').expect("Invalid rule notation");
+        Self::Totalistic {
+            birth: counts(birth, 'B'),
+            survive: counts(survive, 'S'),
+        }
+    }
+
+    fn next(&self, alive: bool, neighbors: impl Iterator<Item = bool>) -> bool {
+        match self {
+            Self::Totalistic { birth, survive } => {
+                let count = neighbors.filter(|n| *n).count();
+                if alive {
+                    survive.contains(&count)
+                } else {
+                    birth.contains(&count)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton<const N: usize> {
+    neighborhood: Neighborhood<N>,
+    rule: Rule,
+    // Only the alive cells.
+    cells: FxHashSet<Point<N>>,
+}
+
+impl<const N: usize> Automaton<N> {
+    /// An automaton on an infinite grid, all dead.
+    pub fn sparse(neighborhood: Neighborhood<N>, rule: Rule) -> Self {
+        Self {
+            neighborhood,
+            rule,
+            cells: FxHashSet::default(),
+        }
+    }
+
+    pub fn get(&self, p: &Point<N>) -> bool {
+        self.cells.contains(p)
+    }
+
+    pub fn set(&mut self, p: Point<N>, alive: bool) {
+        if alive {
+            self.cells.insert(p);
+        } else {
+            self.cells.remove(&p);
+        }
+    }
+
+    pub fn alive_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn next_state(&self, p: &Point<N>) -> bool {
+        let neighbors = self
+            .neighborhood
+            .offsets
+            .iter()
+            .map(|offset| self.get(&add(p, offset)));
+        self.rule.next(self.get(p), neighbors)
+    }
+
+    /// Computes the next generation.
+    pub fn step(&mut self) {
+        // Only cells with an alive neighbor can be alive.
+        let mut candidates = FxHashSet::default();
+        for p in &self.cells {
+            candidates.insert(*p);
+            for offset in &self.neighborhood.offsets {
+                candidates.insert(sub(p, offset));
+            }
+        }
+        self.cells = candidates
+            .into_iter()
+            .filter(|p| self.next_state(p))
+            .collect();
+    }
+
+    /// Computes that many generations.
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+}
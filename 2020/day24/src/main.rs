@@ -1,28 +1,11 @@
 use std::io::{self, Read};
 
-use fxhash::FxHashSet;
+mod automaton;
+use automaton::{Automaton, Neighborhood, Point, Rule};
 
-// Cube coordinates
+// Axial coordinates [q, r], the s of the cube coordinates being -q - r.
 // See https://www.redblobgames.com/grids/hexagons/
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct CubeCoords {
-    q: i32,
-    r: i32,
-    s: i32,
-}
-
-impl CubeCoords {
-    fn new(q: i32, r: i32, s: i32) -> Self {
-        CubeCoords { q, r, s }
-    }
-
-    fn adjacents(&self) -> Vec<CubeCoords> {
-        [East, NorthEast, NorthWest, SouthEast, SouthWest, West]
-            .iter()
-            .map(|dir| dir.next_pos(self))
-            .collect()
-    }
-}
+type AxialCoords = Point<2>;
 
 #[derive(Debug)]
 enum Dir {
@@ -36,14 +19,14 @@ enum Dir {
 use Dir::{East, NorthEast, NorthWest, SouthEast, SouthWest, West};
 
 impl Dir {
-    fn next_pos(&self, pos: &CubeCoords) -> CubeCoords {
+    fn next_pos(&self, [q, r]: AxialCoords) -> AxialCoords {
         match self {
-            East => CubeCoords::new(pos.q + 1, pos.r, pos.s - 1),
-            West => CubeCoords::new(pos.q - 1, pos.r, pos.s + 1),
-            NorthWest => CubeCoords::new(pos.q, pos.r - 1, pos.s + 1),
-            NorthEast => CubeCoords::new(pos.q + 1, pos.r - 1, pos.s),
-            SouthEast => CubeCoords::new(pos.q, pos.r + 1, pos.s - 1),
-            SouthWest => CubeCoords::new(pos.q - 1, pos.r + 1, pos.s),
+            East => [q + 1, r],
+            West => [q - 1, r],
+            NorthWest => [q, r - 1],
+            NorthEast => [q + 1, r - 1],
+            SouthEast => [q, r + 1],
+            SouthWest => [q - 1, r + 1],
         }
     }
 }
@@ -80,62 +63,24 @@ fn build(input: &str) -> Vec<Vec<Dir>> {
         .collect()
 }
 
-fn get_floor(tiles_list: &[Vec<Dir>]) -> FxHashSet<CubeCoords> {
-    let mut floor: FxHashSet<CubeCoords> = FxHashSet::default();
+// The black tiles are the alive cells.
+// Any black tile with zero or more than 2 black tiles immediately adjacent to it is flipped to white,
+// and any white tile with exactly 2 black tiles immediately adjacent to it is flipped to black.
+fn get_floor(tiles_list: &[Vec<Dir>]) -> Automaton<2> {
+    let mut floor = Automaton::sparse(Neighborhood::hex(), Rule::parse("B2/S12"));
     for tiles in tiles_list {
-        let mut pos = CubeCoords::new(0, 0, 0);
-        for t in tiles {
-            pos = t.next_pos(&pos);
-        }
-
-        if floor.contains(&pos) {
-            floor.remove(&pos);
-        } else {
-            floor.insert(pos);
-        }
+        let pos = tiles.iter().fold([0, 0], |pos, t| t.next_pos(pos));
+        floor.set(pos, !floor.get(&pos));
     }
     floor
 }
 
-fn black_tiles_after_days(floor: &FxHashSet<CubeCoords>) -> usize {
+fn black_tiles_after_days(floor: &Automaton<2>) -> usize {
     const DAYS_COUNT: usize = 100;
 
     let mut floor = floor.clone();
-    for _ in 0..DAYS_COUNT {
-        let mut new_floor = floor.clone();
-
-        // We collect all tiles adjacent to black, as tiles that may be white and are worth checking.
-        let mut all_adjacents: Vec<CubeCoords> = Vec::new();
-
-        // Any black tile with zero or more than 2 black tiles immediately adjacent to it
-        // is flipped to white.
-        for t in &floor {
-            let adjs = t.adjacents();
-            let black_count = adjs.iter().filter(|c| floor.contains(c)).count();
-            if black_count == 0 || black_count > 2 {
-                new_floor.remove(t);
-            }
-
-            all_adjacents.extend(adjs);
-        }
-
-        // Any white tile with exactly 2 black tiles immediately adjacent to it is flipped to black.
-        for t in all_adjacents {
-            // We should only look at white tiles, so we skip black ones.
-            if floor.contains(&t) {
-                continue;
-            }
-            let adjs = t.adjacents();
-            let black_count = adjs.iter().filter(|c| floor.contains(c)).count();
-            if black_count == 2 {
-                new_floor.insert(t);
-            }
-        }
-
-        std::mem::swap(&mut floor, &mut new_floor);
-    }
-
-    floor.len()
+    floor.run(DAYS_COUNT);
+    floor.alive_count()
 }
 
 fn main() {
@@ -145,7 +90,7 @@ fn main() {
 
     let floor = get_floor(&tiles_list);
 
-    println!("Part 1: {}", floor.len());
+    println!("Part 1: {}", floor.alive_count());
     println!("Part 2: {}", black_tiles_after_days(&floor));
 }
 
@@ -158,7 +103,7 @@ mod tests {
     #[test]
     fn test_part1() {
         let floor = get_floor(&build(INPUT_TEST));
-        assert_eq!(floor.len(), 10);
+        assert_eq!(floor.alive_count(), 10);
     }
 
     #[test]
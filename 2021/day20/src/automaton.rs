@@ -0,0 +1,179 @@
+//! Cellular automata, on grids of any dimension or on hexagonal grids.
+//!
+//! Copied from utils, only with the sparse storage and the table rules.
+
+use fxhash::FxHashSet;
+
+/// Coordinates of a cell.
+pub type Point<const N: usize> = [i32; N];
+
+fn add<const N: usize>(p: &Point<N>, offset: &Point<N>) -> Point<N> {
+    std::array::from_fn(|d| p[d] + offset[d])
+}
+
+fn sub<const N: usize>(p: &Point<N>, offset: &Point<N>) -> Point<N> {
+    std::array::from_fn(|d| p[d] - offset[d])
+}
+
+/// The cells whose states give the next state of a cell, as offsets from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Neighborhood<const N: usize> {
+    offsets: Vec<Point<N>>,
+}
+
+impl<const N: usize> Neighborhood<N> {
+    /// All the cells in the block of size 3 around the cell, including the cell itself,
+    /// in lexicographic order. With `[row, col]` points, it's the reading order.
+    pub fn block() -> Self {
+        let mut offsets = vec![[0; N]];
+        for d in 0..N {
+            offsets = offsets
+                .into_iter()
+                .flat_map(|offset| {
+                    (-1..=1).map(move |v| {
+                        let mut offset = offset;
+                        offset[d] = v;
+                        offset
+                    })
+                })
+                .collect();
+        }
+        Self { offsets }
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+}
+
+/// How the next state of a cell is computed from its state and the ones of its neighbors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// The next state is `table[index]`, the index having one bit per neighbor,
+    /// 1 if alive, the first neighbor being the most significant bit.
+    /// The state of the cell is only used if the neighborhood contains it.
+    Table(Vec<bool>),
+}
+
+impl Rule {
+    /// A table rule from a string of '#' for alive and '.' for dead.
+    pub fn table(table: &str) -> Self {
+        Self::Table(
+            table
+                .chars()
+                .map(|c| match c {
+                    '#' => true,
+                    '.' => false,
+                    _ => panic!("Invalid char in rule table"),
+                })
+                .collect(),
+        )
+    }
+
+    fn next(&self, neighbors: impl Iterator<Item = bool>) -> bool {
+        match self {
+            Self::Table(table) => {
+                table[neighbors.fold(0, |index, n| (index << 1) | usize::from(n))]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton<const N: usize> {
+    neighborhood: Neighborhood<N>,
+    rule: Rule,
+    // Only the cells that differ from the background.
+    cells: FxHashSet<Point<N>>,
+    background: bool,
+}
+
+impl<const N: usize> Automaton<N> {
+    /// An automaton on an infinite grid, all dead.
+    pub fn sparse(neighborhood: Neighborhood<N>, rule: Rule) -> Self {
+        let Rule::Table(table) = &rule;
+        assert_eq!(
+            table.len(),
+            1 << neighborhood.len(),
+            "Rule table doesn't match the neighborhood"
+        );
+        Self {
+            neighborhood,
+            rule,
+            cells: FxHashSet::default(),
+            background: false,
+        }
+    }
+
+    /// Sets the cells from a text, each line being a row.
+    /// The points are `[row, col, 0, ...]`, so N must be at least 2.
+    pub fn set_text(&mut self, input: &str, alive: char) {
+        for (row, line) in input.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                let mut p = [0; N];
+                p[0] = i32::try_from(row).unwrap();
+                p[1] = i32::try_from(col).unwrap();
+                self.set(p, c == alive);
+            }
+        }
+    }
+
+    pub fn get(&self, p: &Point<N>) -> bool {
+        self.cells.contains(p) != self.background
+    }
+
+    pub fn set(&mut self, p: Point<N>, alive: bool) {
+        if alive == self.background {
+            self.cells.remove(&p);
+        } else {
+            self.cells.insert(p);
+        }
+    }
+
+    /// Number of alive cells, None if infinite.
+    pub fn alive_count(&self) -> Option<usize> {
+        if self.background {
+            None
+        } else {
+            Some(self.cells.len())
+        }
+    }
+
+    fn next_state(&self, p: &Point<N>) -> bool {
+        let neighbors = self
+            .neighborhood
+            .offsets
+            .iter()
+            .map(|offset| self.get(&add(p, offset)));
+        self.rule.next(neighbors)
+    }
+
+    /// Computes the next generation.
+    pub fn step(&mut self) {
+        let next_background = self.rule.next(std::iter::repeat_n(
+            self.background,
+            self.neighborhood.len(),
+        ));
+
+        // Only cells with a neighbor different from the background can differ from it.
+        let mut candidates = FxHashSet::default();
+        for p in &self.cells {
+            candidates.insert(*p);
+            for offset in &self.neighborhood.offsets {
+                candidates.insert(sub(p, offset));
+            }
+        }
+        self.cells = candidates
+            .into_iter()
+            .filter(|p| self.next_state(p) != next_background)
+            .collect();
+        self.background = next_background;
+    }
+
+    /// Computes that many generations.
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+}
@@ -1,9 +1,6 @@
-use std::{
-    hash::{Hash, Hasher},
-    io::{self, Read},
-};
+use std::io::{self, Read};
 
-use fxhash::{FxHashMap, FxHasher};
+mod cycle;
 
 // The debug version
 #[cfg(feature = "my_debug")]
@@ -432,115 +429,112 @@ fn next_rock(i: usize) -> Box<dyn Rock> {
     }
 }
 
-// Calculates a hash for the set of lines.
-fn hash_lines(lines: &[u8]) -> u64 {
-    let mut hasher = FxHasher::default();
-    lines.hash(&mut hasher);
-    hasher.finish()
+// The chamber with the rocks fallen so far.
+struct Tower<'a> {
+    chamber: Chamber,
+    movements: &'a [Jet],
+    jet_index: usize,
+    rock_number: usize,
 }
 
-fn fall_rocks<const USE_PATTERN_DETECTION: bool>(movements: &[Jet], total_rocks: usize) -> usize {
-    let mut chamber = Chamber::new();
-
-    let mut rock_number = 0;
-    let mut rock: Box<dyn Rock> = next_rock(rock_number);
-    let mut pos = rock.get_initial_position(&chamber);
-
-    // Pattern detection: We try to find a top of the chamber that repeats at the same rock type.
-    // - Key: Hash of the top of the chamber.
-    // - Value: Rock number and chamber height.
-    let mut patterns: FxHashMap<u64, (usize, usize)> = FxHashMap::default();
-    let mut height_to_add: Option<usize> = None;
-
-    debug_print!("First rock begins falling");
-    chamber.debug_print_falling(&rock.units(&pos));
-
-    for m in movements.iter().cycle() {
-        // Pushing rock to the side.
-        match m {
-            Jet::Left => {
-                if let Some(next) = rock.move_left(&chamber, &pos) {
-                    debug_print!("Pushing left.");
-                    pos = next;
-                } else {
-                    debug_print!("Pushing left but nothing happens.");
-                }
-            }
-            Jet::Right => {
-                if let Some(next) = rock.move_right(&chamber, &pos) {
-                    debug_print!("Pushing right.");
-                    pos = next;
-                } else {
-                    debug_print!("Pushing right but nothing happens.");
-                }
-            }
+impl<'a> Tower<'a> {
+    fn new(movements: &'a [Jet]) -> Self {
+        Self {
+            chamber: Chamber::new(),
+            movements,
+            jet_index: 0,
+            rock_number: 0,
         }
-        chamber.debug_print_falling(&rock.units(&pos));
+    }
 
-        // Rock falling down.
-        if let Some(next) = rock.move_down(&chamber, &pos) {
-            debug_print!("Falling one unit");
-            pos = next;
-            chamber.debug_print_falling(&rock.units(&pos));
-        } else {
-            chamber.set(&rock.units(&pos));
-            debug_print!("Resting (height {})", chamber.height());
-            chamber.debug_print();
-
-            // Pattern detection
-            if USE_PATTERN_DETECTION
-                && height_to_add.is_none()
-                && chamber.height() > 20
-                && rock_number % 5 == 0
-            {
-                let hash = hash_lines(&chamber.units[chamber.height() - 20..=chamber.height()]);
-                if let Some((prevous_rock_number, previous_height)) =
-                    patterns.insert(hash, (rock_number, chamber.height()))
-                {
-                    // Found pattern
-                    let rock_diff = rock_number - prevous_rock_number;
-                    let height_diff = chamber.height() - previous_height;
-                    // println!("Found pattern, rock diff={}, height diff={}", rock_diff, height_diff);
-
-                    let period_count = (total_rocks - rock_number) / rock_diff;
-                    let rocks_to_jump_ahead = period_count * rock_diff;
-                    height_to_add = Some(period_count * height_diff);
-
-                    rock_number += rocks_to_jump_ahead;
+    // Drops the next rock, until it comes to rest.
+    fn drop_rock(&mut self) {
+        debug_print!("Getting new rock (number {})", self.rock_number);
+        let rock = next_rock(self.rock_number);
+        let mut pos = rock.get_initial_position(&self.chamber);
+        self.chamber.debug_print_falling(&rock.units(&pos));
+
+        loop {
+            // Pushing rock to the side.
+            match self.movements[self.jet_index] {
+                Jet::Left => {
+                    if let Some(next) = rock.move_left(&self.chamber, &pos) {
+                        debug_print!("Pushing left.");
+                        pos = next;
+                    } else {
+                        debug_print!("Pushing left but nothing happens.");
+                    }
+                }
+                Jet::Right => {
+                    if let Some(next) = rock.move_right(&self.chamber, &pos) {
+                        debug_print!("Pushing right.");
+                        pos = next;
+                    } else {
+                        debug_print!("Pushing right but nothing happens.");
+                    }
                 }
             }
-
-            rock_number += 1;
-            if rock_number >= total_rocks {
+            self.jet_index = (self.jet_index + 1) % self.movements.len();
+            self.chamber.debug_print_falling(&rock.units(&pos));
+
+            // Rock falling down.
+            if let Some(next) = rock.move_down(&self.chamber, &pos) {
+                debug_print!("Falling one unit");
+                pos = next;
+                self.chamber.debug_print_falling(&rock.units(&pos));
+            } else {
+                self.chamber.set(&rock.units(&pos));
+                debug_print!("Resting (height {})", self.chamber.height());
+                self.chamber.debug_print();
                 break;
             }
-
-            debug_print!("Getting new rock (number {})", rock_number);
-            rock = next_rock(rock_number);
-            pos = rock.get_initial_position(&chamber);
-            chamber.debug_print_falling(&rock.units(&pos));
         }
+        self.rock_number += 1;
     }
 
-    if !USE_PATTERN_DETECTION {
-        // chamber.print_falling(&[]);
-        assert_eq!(
-            chamber.height(),
-            chamber.units.iter().filter(|line| **line != 0).count()
-        );
+    // For pattern detection: The next rock type and jet, with the top of the chamber.
+    // If they are the same, the next rocks will fall the same way.
+    fn key(&self) -> (usize, usize, Vec<u8>) {
+        const TOP_LINES: usize = 20;
+        let height = self.chamber.height();
+        (
+            self.rock_number % 5,
+            self.jet_index,
+            self.chamber.units[height.saturating_sub(TOP_LINES)..height].to_vec(),
+        )
     }
-
-    chamber.height() + height_to_add.unwrap_or_default()
 }
 
 fn column_height_after_2022(movements: &[Jet]) -> usize {
     const TOTAL_ROCKS: usize = 2022;
-    fall_rocks::<false>(movements, TOTAL_ROCKS)
+    let mut tower = Tower::new(movements);
+    for _ in 0..TOTAL_ROCKS {
+        tower.drop_rock();
+    }
+    // tower.chamber.print_falling(&[]);
+    assert_eq!(
+        tower.chamber.height(),
+        tower
+            .chamber
+            .units
+            .iter()
+            .filter(|line| **line != 0)
+            .count()
+    );
+    tower.chamber.height()
 }
 
 fn column_height_after_trillion(movements: &[Jet]) -> usize {
     const TOTAL_ROCKS: usize = 1_000_000_000_000;
-    fall_rocks::<true>(movements, TOTAL_ROCKS)
+    // The top of the tower ends up repeating itself, growing by the same height each time.
+    let height = cycle::metric_at(
+        Tower::new(movements),
+        Tower::drop_rock,
+        Tower::key,
+        |tower| i64::try_from(tower.chamber.height()).unwrap(),
+        TOTAL_ROCKS,
+    );
+    usize::try_from(height).unwrap()
 }
 
 fn main() {
@@ -567,7 +561,7 @@ mod tests {
     fn test_part2() {
         assert_eq!(
             column_height_after_trillion(&build(INPUT_TEST)),
-            1514285714288
+            1_514_285_714_288
         );
     }
 }
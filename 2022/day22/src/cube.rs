@@ -0,0 +1,340 @@
+//! Folding of a cube net, to find where we arrive on the cube when walking off a face.
+//!
+//! The faces are folded one after the other from the first one, following the net. For each face,
+//! we track in 3D its outward normal and the directions of the net x and y axes. Two faces are
+//! adjacent on the cube if the normal of one is the direction to walk on the other to reach it.
+//! It works for any of the 11 cube nets, in any orientation, without hard-coding anything.
+
+use crate::{Facing, PathTurn};
+
+type Vec3 = [i32; 3];
+
+fn neg(v: Vec3) -> Vec3 {
+    v.map(|c| -c)
+}
+
+const FACINGS: [Facing; 4] = [Facing::Right, Facing::Down, Facing::Left, Facing::Up];
+
+fn reverse(facing: Facing) -> Facing {
+    facing.turn(PathTurn::Right).turn(PathTurn::Right)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Face {
+    // Position in the net, counted in faces.
+    col: usize,
+    row: usize,
+    // Orientation once folded: Outward normal, and directions of the net x and y axes.
+    normal: Vec3,
+    right: Vec3,
+    down: Vec3,
+}
+
+impl Face {
+    // Direction in 3D of a facing on this face.
+    fn direction(&self, facing: Facing) -> Vec3 {
+        match facing {
+            Facing::Right => self.right,
+            Facing::Down => self.down,
+            Facing::Left => neg(self.right),
+            Facing::Up => neg(self.down),
+        }
+    }
+
+    // The face next to this one in the net, folded along their common edge.
+    fn folded(&self, facing: Facing) -> Self {
+        let (col, row, normal, right, down) = match facing {
+            Facing::Right => (
+                self.col + 1,
+                self.row,
+                self.right,
+                neg(self.normal),
+                self.down,
+            ),
+            Facing::Left => (
+                self.col - 1,
+                self.row,
+                neg(self.right),
+                self.normal,
+                self.down,
+            ),
+            Facing::Down => (
+                self.col,
+                self.row + 1,
+                self.down,
+                self.right,
+                neg(self.normal),
+            ),
+            Facing::Up => (
+                self.col,
+                self.row - 1,
+                neg(self.down),
+                self.right,
+                self.normal,
+            ),
+        };
+        Self {
+            col,
+            row,
+            normal,
+            right,
+            down,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CubeNet {
+    size: usize,
+    faces: Vec<Face>,
+}
+
+impl CubeNet {
+    // Builds the cube from its net: true for the tiles of the net.
+    // Rows don't need to have the same length.
+    pub fn new(net: &[Vec<bool>]) -> Result<Self, String> {
+        let count = net.iter().flatten().filter(|t| **t).count();
+        let size = (1..=count)
+            .take_while(|s| 6 * s * s <= count)
+            .last()
+            .ok_or("Empty net")?;
+        if 6 * size * size != count {
+            return Err(format!("{count} tiles can't make 6 square faces"));
+        }
+        let is_face = |col: usize, row: usize| {
+            net.get(row * size)
+                .and_then(|r| r.get(col * size))
+                .is_some_and(|t| *t)
+        };
+        let width = net.iter().map(Vec::len).max().unwrap_or(0);
+        let first = (0..net.len().div_ceil(size))
+            .flat_map(|row| (0..width.div_ceil(size)).map(move |col| (col, row)))
+            .find(|(col, row)| is_face(*col, *row))
+            .ok_or("Empty net")?;
+
+        let mut faces = vec![Face {
+            col: first.0,
+            row: first.1,
+            normal: [0, 0, 1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        }];
+        let mut i = 0;
+        while i < faces.len() {
+            let face = faces[i];
+            for facing in FACINGS {
+                if (facing == Facing::Left && face.col == 0)
+                    || (facing == Facing::Up && face.row == 0)
+                {
+                    continue;
+                }
+                let folded = face.folded(facing);
+                if is_face(folded.col, folded.row)
+                    && !faces
+                        .iter()
+                        .any(|f| (f.col, f.row) == (folded.col, folded.row))
+                {
+                    faces.push(folded);
+                }
+            }
+            i += 1;
+        }
+        if faces.len() != 6 {
+            return Err("The net has more than 6 faces, or isn't connected".to_string());
+        }
+        if faces
+            .iter()
+            .enumerate()
+            .any(|(i, f)| faces[i + 1..].iter().any(|g| g.normal == f.normal))
+        {
+            return Err("Faces overlap once folded, not a cube net".to_string());
+        }
+        Ok(Self { size, faces })
+    }
+
+    fn face_at(&self, pos: (usize, usize)) -> &Face {
+        self.faces
+            .iter()
+            .find(|f| (f.col, f.row) == (pos.0 / self.size, pos.1 / self.size))
+            .expect("Position not on the net")
+    }
+
+    // Position and facing after one step forward on the cube.
+    // The position is (x, y) in the net, starting at 0.
+    pub fn wrap(&self, pos: (usize, usize), facing: Facing) -> ((usize, usize), Facing) {
+        let s = self.size;
+        let face = self.face_at(pos);
+        let (x, y) = (pos.0 % s, pos.1 % s);
+        match facing {
+            Facing::Right if x + 1 < s => return ((pos.0 + 1, pos.1), facing),
+            Facing::Down if y + 1 < s => return ((pos.0, pos.1 + 1), facing),
+            Facing::Left if x > 0 => return ((pos.0 - 1, pos.1), facing),
+            Facing::Up if y > 0 => return ((pos.0, pos.1 - 1), facing),
+            _ => {}
+        }
+
+        // Going over the edge: The next face is in the direction we are walking,
+        // and we arrive from the edge in the direction of the face we leave.
+        let next = self
+            .faces
+            .iter()
+            .find(|f| f.normal == face.direction(facing))
+            .unwrap();
+        let edge = FACINGS
+            .into_iter()
+            .find(|f| next.direction(*f) == face.normal)
+            .unwrap();
+        // Offset along the edge, reversed if the edge axes are in opposite directions.
+        let along_edge = |face: &Face, facing: Facing| match facing {
+            Facing::Right | Facing::Left => face.down,
+            Facing::Down | Facing::Up => face.right,
+        };
+        let offset = match facing {
+            Facing::Right | Facing::Left => y,
+            Facing::Down | Facing::Up => x,
+        };
+        let offset = if along_edge(face, facing) == along_edge(next, edge) {
+            offset
+        } else {
+            s - 1 - offset
+        };
+        let (x, y) = match edge {
+            Facing::Right => (s - 1, offset),
+            Facing::Down => (offset, s - 1),
+            Facing::Left => (0, offset),
+            Facing::Up => (offset, 0),
+        };
+        ((next.col * s + x, next.row * s + y), reverse(edge))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The 11 cube nets, one character per face.
+    const NETS: [&str; 11] = [
+        "#...\n####\n#...",
+        "#...\n####\n.#..",
+        "#...\n####\n..#.",
+        "#...\n####\n...#",
+        ".#..\n####\n.#..",
+        ".#..\n####\n..#.",
+        "##..\n.###\n.#..",
+        "##..\n.###\n..#.",
+        "##..\n.###\n...#",
+        "##..\n.##.\n..##",
+        "###..\n..###",
+    ];
+
+    fn parse(net: &str) -> Vec<Vec<bool>> {
+        net.lines()
+            .map(|l| l.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    // The 8 rotations and reflections of a net.
+    fn orientations(net: &[Vec<bool>]) -> Vec<Vec<Vec<bool>>> {
+        let transpose = |n: &[Vec<bool>]| -> Vec<Vec<bool>> {
+            (0..n[0].len())
+                .map(|x| n.iter().map(|row| row[x]).collect())
+                .collect()
+        };
+        let flip = |n: &[Vec<bool>]| -> Vec<Vec<bool>> {
+            n.iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect()
+        };
+        let mut result = vec![net.to_vec()];
+        for i in 0..7 {
+            let last = &result[result.len() - 1];
+            let oriented = if i % 2 == 0 {
+                transpose(last)
+            } else {
+                flip(last)
+            };
+            result.push(oriented);
+        }
+        result
+    }
+
+    fn scale(net: &[Vec<bool>], size: usize) -> Vec<Vec<bool>> {
+        net.iter()
+            .flat_map(|row| {
+                let row: Vec<bool> = row
+                    .iter()
+                    .flat_map(|t| std::iter::repeat_n(*t, size))
+                    .collect();
+                std::iter::repeat_n(row, size)
+            })
+            .collect()
+    }
+
+    fn step(net: &[Vec<bool>], pos: (usize, usize), facing: Facing) -> Option<(usize, usize)> {
+        let (x, y) = match facing {
+            Facing::Right => (pos.0 + 1, pos.1),
+            Facing::Down => (pos.0, pos.1 + 1),
+            Facing::Left => (pos.0.checked_sub(1)?, pos.1),
+            Facing::Up => (pos.0, pos.1.checked_sub(1)?),
+        };
+        net.get(y)?.get(x)?.then_some((x, y))
+    }
+
+    fn check(net: &[Vec<bool>], size: usize) {
+        let cube = CubeNet::new(net).unwrap();
+        assert_eq!(cube.size, size);
+        for (y, row) in net.iter().enumerate() {
+            for x in (0..row.len()).filter(|x| row[*x]) {
+                for facing in FACINGS {
+                    let (arrival, arrival_facing) = cube.wrap((x, y), facing);
+                    assert!(net[arrival.1][arrival.0]);
+                    // Walking in the net when possible.
+                    if let Some(n) = step(net, (x, y), facing) {
+                        assert_eq!((arrival, arrival_facing), (n, facing));
+                    }
+                    // Coming back.
+                    assert_eq!(
+                        cube.wrap(arrival, reverse(arrival_facing)),
+                        ((x, y), reverse(facing))
+                    );
+                    // Going around the cube.
+                    let mut p = ((x, y), facing);
+                    for _ in 0..4 * size {
+                        p = cube.wrap(p.0, p.1);
+                    }
+                    assert_eq!(p, ((x, y), facing));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_nets() {
+        for net in NETS {
+            for oriented in orientations(&parse(net)) {
+                for size in [1, 2, 5] {
+                    check(&scale(&oriented, size), size);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_example() {
+        let net = parse("..#.\n###.\n..##");
+        let cube = CubeNet::new(&scale(&net, 4)).unwrap();
+        // A and B in the puzzle description.
+        assert_eq!(cube.wrap((11, 5), Facing::Right), ((14, 8), Facing::Down));
+        // C and D.
+        assert_eq!(cube.wrap((10, 11), Facing::Down), ((1, 7), Facing::Up));
+    }
+
+    #[test]
+    fn test_invalid_nets() {
+        assert!(CubeNet::new(&parse("######")).is_err());
+        assert!(CubeNet::new(&parse("##\n##\n##")).is_err());
+        assert!(CubeNet::new(&parse("#.#\n###\n.#.")).is_err());
+        assert!(CubeNet::new(&parse("###\n#..")).is_err());
+        assert!(CubeNet::new(&[]).is_err());
+    }
+}
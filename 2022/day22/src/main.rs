@@ -5,6 +5,10 @@ use std::{
 
 use itertools::Itertools;
 
+use cube::CubeNet;
+
+mod cube;
+
 #[derive(Debug, Clone, Copy)]
 enum PathTurn {
     Left,
@@ -246,7 +250,6 @@ impl Map {
             .unwrap()
     }
 
-    #[allow(clippy::match_on_vec_items)]
     fn move_to(&self, pos_dir: &PosDir, steps: usize, wrapping: &impl Wrapping) -> PosDir {
         let mut p = *pos_dir;
         for _ in 0..steps {
@@ -350,275 +353,40 @@ impl Wrapping for FlatModel {
     }
 }
 
-struct Cube {
-    len: usize,
-}
-
-impl Cube {
-    fn new(len: usize) -> Self {
-        Self { len }
-    }
-
-    fn calc_offset(&self, v: usize) -> usize {
-        (v - 1) % self.len
-    }
-
-    fn cube_number(&self, v: usize) -> usize {
-        (v - 1) / self.len
-    }
-
-    fn horiz(&self, offset: usize, x: usize, y: usize, facing: Facing) -> PosDir {
-        assert!([Facing::Up, Facing::Down].contains(&facing));
-        let x = 1 + x * self.len + offset;
-        let y = if facing == Facing::Up {
-            y * self.len
-        } else {
-            1 + y * self.len
-        };
-        PosDir::new(x, y, facing)
-    }
-
-    fn horiz_rev(&self, offset: usize, x: usize, y: usize, facing: Facing) -> PosDir {
-        assert!([Facing::Up, Facing::Down].contains(&facing));
-        let x = 1 + (x + 1) * self.len - 1 - offset;
-        let y = if facing == Facing::Up {
-            y * self.len
-        } else {
-            1 + y * self.len
-        };
-        PosDir::new(x, y, facing)
-    }
-
-    fn vert(&self, offset: usize, x: usize, y: usize, facing: Facing) -> PosDir {
-        assert!([Facing::Left, Facing::Right].contains(&facing));
-        let x = if facing == Facing::Left {
-            x * self.len
-        } else {
-            1 + x * self.len
-        };
-        let y = 1 + y * self.len + offset;
-        PosDir::new(x, y, facing)
-    }
-
-    fn vert_rev(&self, offset: usize, x: usize, y: usize, facing: Facing) -> PosDir {
-        assert!([Facing::Left, Facing::Right].contains(&facing));
-        let x = if facing == Facing::Left {
-            x * self.len
-        } else {
-            1 + x * self.len
-        };
-        let y = 1 + (y + 1) * self.len - 1 - offset;
-        PosDir::new(x, y, facing)
-    }
-}
-
-// Cube numbers:
-//   1
-// 234
-//   56
-struct TestCubeModel(Cube);
-
-impl TestCubeModel {
-    const LEN: usize = 4;
-
-    #[allow(dead_code)]
-    fn new() -> Self {
-        Self(Cube::new(Self::LEN))
-    }
-}
-
-impl Wrapping for TestCubeModel {
-    fn left(&self, _map: &Map, pos_dir: &PosDir) -> PosDir {
-        assert_eq!(pos_dir.facing, Facing::Left);
-        let offset = self.0.calc_offset(pos_dir.y);
-        match self.0.cube_number(pos_dir.y) {
-            0 => {
-                // Cube 1 => Cube 3 down
-                self.0.horiz(offset, 1, 1, Facing::Down)
-            }
-            1 => {
-                // Cube 2 => Cube 6 up
-                self.0.horiz_rev(offset, 3, 2, Facing::Up)
-            }
-            2 => {
-                // Cube 5 => Cube 3 up
-                self.0.horiz_rev(offset, 1, 2, Facing::Up)
-            }
-            _ => panic!("Wrapping error"),
-        }
-    }
-
-    fn right(&self, _map: &Map, pos_dir: &PosDir) -> PosDir {
-        assert_eq!(pos_dir.facing, Facing::Right);
-        let offset = self.0.calc_offset(pos_dir.y);
-        match self.0.cube_number(pos_dir.y) {
-            0 => {
-                // Cube 1 => Cube 6 left
-                self.0.vert_rev(offset, 4, 2, Facing::Left)
-            }
-            1 => {
-                // Cube 4 => Cube 6 down
-                self.0.horiz_rev(offset, 3, 2, Facing::Down)
-            }
-            2 => {
-                // Cube 6 => Cube 1 left
-                self.0.vert_rev(offset, 3, 0, Facing::Left)
-            }
-            _ => panic!("Wrapping error"),
-        }
-    }
-
-    fn up(&self, _map: &Map, pos_dir: &PosDir) -> PosDir {
-        assert_eq!(pos_dir.facing, Facing::Up);
-        let offset = self.0.calc_offset(pos_dir.x);
-        match self.0.cube_number(pos_dir.x) {
-            0 => {
-                // Cube 2 => Cube 1 down
-                self.0.horiz_rev(offset, 2, 0, Facing::Down)
-            }
-            1 => {
-                // Cube 3 => Cube 1 right
-                self.0.vert(offset, 2, 0, Facing::Right)
-            }
-            2 => {
-                // Cube 1 => Cube 2 down
-                self.0.horiz_rev(offset, 0, 1, Facing::Down)
-            }
-            3 => {
-                // Cube 6 => Cube 4 left
-                self.0.vert_rev(offset, 3, 1, Facing::Left)
-            }
-            _ => panic!("Wrapping error"),
-        }
-    }
+// Wrapping on the cube folded from the map, found automatically.
+struct CubeModel(CubeNet);
 
-    fn down(&self, _map: &Map, pos_dir: &PosDir) -> PosDir {
-        assert_eq!(pos_dir.facing, Facing::Down);
-        let offset = self.0.calc_offset(pos_dir.x);
-        match self.0.cube_number(pos_dir.x) {
-            0 => {
-                // Cube 2 => Cube 5 up
-                self.0.horiz_rev(offset, 2, 3, Facing::Up)
-            }
-            1 => {
-                // Cube 3 => Cube 5 right
-                self.0.vert_rev(offset, 2, 2, Facing::Right)
-            }
-            2 => {
-                // Cube 5 => Cube 2 up
-                self.0.horiz_rev(offset, 0, 2, Facing::Up)
-            }
-            3 => {
-                // Cube 6 => Cube 2 right
-                self.0.vert_rev(offset, 0, 1, Facing::Right)
-            }
-            _ => panic!("Wrapping error"),
-        }
+impl CubeModel {
+    fn new(map: &Map) -> Self {
+        // Without the void border.
+        let net: Vec<Vec<bool>> = map.tiles[1..map.tiles.len() - 1]
+            .iter()
+            .map(|row| row[1..].iter().map(|t| *t != Tile::Void).collect())
+            .collect();
+        Self(CubeNet::new(&net).unwrap())
     }
-}
-
-// Cube numbers:
-//  12
-//  3
-// 45
-// 6
-struct RealCubeModel(Cube);
-
-impl RealCubeModel {
-    const LEN: usize = 50;
 
-    fn new() -> Self {
-        Self(Cube::new(Self::LEN))
+    fn wrap(&self, pos_dir: &PosDir) -> PosDir {
+        let ((x, y), facing) = self.0.wrap((pos_dir.x - 1, pos_dir.y - 1), pos_dir.facing);
+        PosDir::new(x + 1, y + 1, facing)
     }
 }
 
-impl Wrapping for RealCubeModel {
+impl Wrapping for CubeModel {
     fn left(&self, _map: &Map, pos_dir: &PosDir) -> PosDir {
-        assert_eq!(pos_dir.facing, Facing::Left);
-        let offset = self.0.calc_offset(pos_dir.y);
-        match self.0.cube_number(pos_dir.y) {
-            0 => {
-                // Cube 1 => Cube 4 right
-                self.0.vert_rev(offset, 0, 2, Facing::Right)
-            }
-            1 => {
-                // Cube 3 => Cube 4 down
-                self.0.horiz(offset, 0, 2, Facing::Down)
-            }
-            2 => {
-                // Cube 4 => Cube 1 right
-                self.0.vert_rev(offset, 1, 0, Facing::Right)
-            }
-            3 => {
-                // Cube 6 => Cube 1 down
-                self.0.horiz(offset, 1, 0, Facing::Down)
-            }
-            _ => panic!("Wrapping error"),
-        }
+        self.wrap(pos_dir)
     }
 
     fn right(&self, _map: &Map, pos_dir: &PosDir) -> PosDir {
-        assert_eq!(pos_dir.facing, Facing::Right);
-        let offset = self.0.calc_offset(pos_dir.y);
-        match self.0.cube_number(pos_dir.y) {
-            0 => {
-                // Cube 2 => Cube 5 left
-                self.0.vert_rev(offset, 2, 2, Facing::Left)
-            }
-            1 => {
-                // Cube 3 => Cube 2 up
-                self.0.horiz(offset, 2, 1, Facing::Up)
-            }
-            2 => {
-                // Cube 5 => Cube 2 left
-                self.0.vert_rev(offset, 3, 0, Facing::Left)
-            }
-            3 => {
-                // Cube 6 => Cube 5 up
-                self.0.horiz(offset, 1, 3, Facing::Up)
-            }
-            _ => panic!("Wrapping error"),
-        }
+        self.wrap(pos_dir)
     }
 
     fn up(&self, _map: &Map, pos_dir: &PosDir) -> PosDir {
-        assert_eq!(pos_dir.facing, Facing::Up);
-        let offset = self.0.calc_offset(pos_dir.x);
-        match self.0.cube_number(pos_dir.x) {
-            0 => {
-                // Cube 4 => Cube 3 right
-                self.0.vert(offset, 1, 1, Facing::Right)
-            }
-            1 => {
-                // Cube 1 => Cube 6 right
-                self.0.vert(offset, 0, 3, Facing::Right)
-            }
-            2 => {
-                // Cube 2 => Cube 6 up
-                self.0.horiz(offset, 0, 4, Facing::Up)
-            }
-            _ => panic!("Wrapping error"),
-        }
+        self.wrap(pos_dir)
     }
 
     fn down(&self, _map: &Map, pos_dir: &PosDir) -> PosDir {
-        assert_eq!(pos_dir.facing, Facing::Down);
-        let offset = self.0.calc_offset(pos_dir.x);
-        match self.0.cube_number(pos_dir.x) {
-            0 => {
-                // Cube 6 => Cube 2 down
-                self.0.horiz(offset, 2, 0, Facing::Down)
-            }
-            1 => {
-                // Cube 5 => Cube 6 left
-                self.0.vert(offset, 1, 3, Facing::Left)
-            }
-            2 => {
-                // Cube 2 => Cube 3 left
-                self.0.vert(offset, 2, 1, Facing::Left)
-            }
-            _ => panic!("Wrapping error"),
-        }
+        self.wrap(pos_dir)
     }
 }
 
@@ -633,7 +401,7 @@ fn final_password(map: &Map, path: &[PathItem]) -> usize {
 }
 
 fn final_password_on_cube(map: &Map, path: &[PathItem]) -> usize {
-    let pos_dir = map.follow_path(path, &RealCubeModel::new());
+    let pos_dir = map.follow_path(path, &CubeModel::new(map));
     pos_dir.calc_password()
 }
 
@@ -658,14 +426,9 @@ mod tests {
         assert_eq!(final_password(&map, &path), 6032);
     }
 
-    fn final_password_on_cube_test(map: &Map, path: &[PathItem]) -> usize {
-        let pos_dir = map.follow_path(path, &TestCubeModel::new());
-        pos_dir.calc_password()
-    }
-
     #[test]
     fn test_part2() {
         let (map, path) = build(INPUT_TEST);
-        assert_eq!(final_password_on_cube_test(&map, &path), 5031);
+        assert_eq!(final_password_on_cube(&map, &path), 5031);
     }
 }
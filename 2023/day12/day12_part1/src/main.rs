@@ -2,9 +2,9 @@
 
 use itertools::Itertools;
 use std::{
+    fmt,
     fs::File,
     io::{self, BufRead, BufReader},
-    usize,
 };
 
 const OPERATIONAL: char = '.';
@@ -18,6 +18,7 @@ struct Record {
 }
 
 impl Record {
+    #[allow(dead_code)]
     fn new(states: String, groups: Vec<usize>) -> Self {
         Self {
             states,
@@ -39,20 +40,9 @@ impl Record {
         }
     }
 
-    fn to_string(&self) -> String {
-        format!(
-            "{} {}",
-            &self.states,
-            self.damaged_cont_group_sizes
-                .iter()
-                .map(usize::to_string)
-                .join(",")
-        )
-    }
-
     // Calculates the size of the damaged continuous groups.
     // This function needs to be fairly fast, it will be called a lot.
-    fn calc_state_group_sizes(states: &String) -> Vec<usize> {
+    fn calc_state_group_sizes(states: &str) -> Vec<usize> {
         // Counting repeated characters with coalesce
         states
             .chars()
@@ -69,10 +59,12 @@ impl Record {
     }
 
     // Validates that the set of states is correct for the corresponding group sizes
+    #[allow(dead_code)]
     fn validate(&self) -> bool {
         Self::calc_state_group_sizes(&self.states) == self.damaged_cont_group_sizes
     }
 
+    #[allow(dead_code)]
     fn unknown_positions(&self) -> Vec<usize> {
         self.states
             .chars()
@@ -96,6 +88,7 @@ impl Record {
         self.spring_count() - self.damaged_count()
     }
 
+    #[allow(dead_code)]
     fn adjust_states(states: &str, rep: &[&char]) -> String {
         let mut i = 0;
         states
@@ -113,6 +106,7 @@ impl Record {
 
     // This version works in theory, but uses way to much memory as soon as there a few more UNKNOWN.
     // It cannot be used for even part 1.
+    #[allow(dead_code)]
     fn arrangements_count(&self) -> usize {
         let known_operation_count = self.states.chars().filter(|c| *c == OPERATIONAL).count();
         let known_damaged_count = self.states.chars().filter(|c| *c == DAMAGED).count();
@@ -123,18 +117,18 @@ impl Record {
 
         let mut v = vec![OPERATIONAL; unknown_operation_count];
         v.extend(vec![DAMAGED; unknown_damaged_count]);
-        println!("{:?}", v);
+        println!("{v:?}");
 
         let t = v
             .iter()
             .permutations(unknown_operation_count + unknown_damaged_count)
             .unique()
             .filter(|rep| {
-                let adj_s = Self::adjust_states(&self.states, &rep);
+                let adj_s = Self::adjust_states(&self.states, rep);
                 Self::calc_state_group_sizes(&adj_s) == self.damaged_cont_group_sizes
             })
             .count();
-        println!("Sum for {}: {}", self.to_string(), t);
+        println!("Sum for {self}: {t}");
         t
     }
 
@@ -179,6 +173,20 @@ impl Record {
     }
 }
 
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            &self.states,
+            self.damaged_cont_group_sizes
+                .iter()
+                .map(usize::to_string)
+                .join(",")
+        )
+    }
+}
+
 #[test]
 fn test_counts() {
     let r = Record::build("???.### 1,1,3");
@@ -202,7 +210,7 @@ fn test_validate() {
 #[test]
 fn test_adjust_states() {
     assert_eq!(
-        Record::adjust_states("..?.?#.??#?", &vec![&'.', &'#', &'#', &'.', &'#']),
+        Record::adjust_states("..?.?#.??#?", &[&'.', &'#', &'#', &'.', &'#']),
         "....##.#.##"
     );
 }
@@ -230,7 +238,7 @@ fn test_compare_string_against_state() {
     assert!(!r.compare_string_against_state(".##.....#######"));
 }
 
-fn sum_of_arrangements(records: &Vec<Record>) -> usize {
+fn sum_of_arrangements(records: &[Record]) -> usize {
     records.iter().map(Record::arrangements_count_2).sum()
 }
 
@@ -255,6 +263,7 @@ fn main() {
     println!("Part 1: {}", sum_of_arrangements(&records));
 }
 
+#[allow(dead_code)]
 fn part1(filename: &str) -> usize {
     let file = File::open(filename).unwrap();
     let mut reader = BufReader::new(file);
@@ -263,6 +272,7 @@ fn part1(filename: &str) -> usize {
 }
 
 // #[test]
+#[allow(dead_code)]
 fn test_part1() {
     assert_eq!(part1("resources/input_test1"), 6);
     assert_eq!(part1("resources/input_test2"), 21);
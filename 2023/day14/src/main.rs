@@ -1,6 +1,8 @@
 use std::io;
 use std::{fmt, io::Read};
 
+mod cycle;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Table<T>
 where
@@ -215,45 +217,10 @@ fn collapse_down(s: &[char]) -> Vec<char> {
     res
 }
 
-fn cycle_nth(platform: Table<char>, count: usize) -> Table<char> {
-    let mut p = platform;
-    for _ in 0..count {
-        p = cycle(&p);
-    }
-    p
-}
-
-// The cycle repeats at some point, finding when.
-// Returns the value and its period.
-fn find_period(platform: Table<char>, warmup: usize) -> (usize, Table<char>) {
-    let initial_pattern = cycle_nth(platform, warmup);
-    // println!("initial_pattern: {:?}", initial_pattern);
-    let mut p = initial_pattern.clone();
-    let mut i = 0;
-    loop {
-        i += 1;
-        p = cycle(&p);
-        // println!("{} cycles: {}", i, total_load_north(&p));
-        if p == initial_pattern {
-            break;
-        }
-    }
-    // println!("Period is {} cycles: {:?}", i, p);
-    (i, p)
-}
-
 fn total_load_north_after_n_cycles(platform: Table<char>, cycles: usize) -> usize {
-    const WARMUP: usize = 100;
-
-    let (period, mut p) = find_period(platform, WARMUP);
-    // Future jump!
-    // p corresponds to WARMUP, WARMUP + P, WARMUP + 2*P, etc
-    let c = WARMUP + ((cycles - WARMUP) / period) * period;
-    // println!("period={}, c={}", period, c);
-    for _ in c..cycles {
-        p = cycle(&p);
-    }
-    total_load_north(&p)
+    // The platform ends up repeating itself, allowing to jump ahead.
+    let platform = cycle::state_at(platform, |p| *p = cycle(p), cycles);
+    total_load_north(&platform)
 }
 
 fn main() {
@@ -0,0 +1,168 @@
+//! Runs all days of 2024 in one process, with timing.
+//!
+//! Each day is a library, run through its `solve` function.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+type Solve = fn(&str) -> Vec<String>;
+
+// Day N is at index N - 1.
+const DAYS: [Solve; 25] = [
+    day01::solve,
+    day02::solve,
+    day03::solve,
+    day04::solve,
+    day05::solve,
+    day06::solve,
+    day07::solve,
+    day08::solve,
+    day09::solve,
+    day10::solve,
+    day11::solve,
+    day12::solve,
+    day13::solve,
+    day14::solve,
+    day15::solve,
+    day16::solve,
+    day17::solve,
+    day18::solve,
+    day19::solve,
+    day20::solve,
+    day21::solve,
+    day22::solve,
+    day23::solve,
+    day24::solve,
+    day25::solve,
+];
+
+struct Args {
+    days: Vec<u8>,
+    parallel: bool,
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: all [--parallel] [DAY]...");
+    eprintln!("Runs the specified days, all of them by default.");
+    process::exit(1);
+}
+
+fn parse_args() -> Args {
+    let mut days = Vec::new();
+    let mut parallel = false;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--parallel" | "-p" => parallel = true,
+            "--help" | "-h" => usage(),
+            _ => match arg.parse::<u8>() {
+                Ok(day) if (1..=25).contains(&day) => days.push(day),
+                _ => {
+                    eprintln!("Invalid day: {arg}");
+                    usage();
+                }
+            },
+        }
+    }
+    if days.is_empty() {
+        days = (1..=25).collect();
+    }
+    days.sort_unstable();
+    days.dedup();
+    Args { days, parallel }
+}
+
+fn input_path(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join(format!("day{day:02}/resources/input"))
+}
+
+enum Outcome {
+    Solved {
+        answers: Vec<String>,
+        duration: Duration,
+    },
+    NoInput,
+}
+
+fn run_day(day: u8) -> Outcome {
+    let Ok(input) = fs::read_to_string(input_path(day)) else {
+        return Outcome::NoInput;
+    };
+    let start = Instant::now();
+    let answers = DAYS[usize::from(day - 1)](&input);
+    Outcome::Solved {
+        answers,
+        duration: start.elapsed(),
+    }
+}
+
+fn print_outcome(day: u8, outcome: &Outcome) {
+    match outcome {
+        Outcome::Solved { answers, duration } => {
+            println!(
+                "Day {day:2}: {:>10.3} ms  {}",
+                duration.as_secs_f64() * 1000.0,
+                answers.join("  ")
+            );
+        }
+        Outcome::NoInput => println!("Day {day:2}: no input in {}", input_path(day).display()),
+    }
+}
+
+fn main() {
+    let args = parse_args();
+
+    let start = Instant::now();
+    let outcomes: Vec<Outcome> = if args.parallel {
+        thread::scope(|s| {
+            let handles: Vec<_> = args
+                .days
+                .iter()
+                .map(|&day| s.spawn(move || run_day(day)))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        })
+    } else {
+        // Printing as we go, to see progress.
+        args.days
+            .iter()
+            .map(|&day| {
+                let outcome = run_day(day);
+                print_outcome(day, &outcome);
+                outcome
+            })
+            .collect()
+    };
+    let total = start.elapsed();
+
+    if args.parallel {
+        for (&day, outcome) in args.days.iter().zip(&outcomes) {
+            print_outcome(day, outcome);
+        }
+    }
+
+    let solved = outcomes
+        .iter()
+        .filter(|o| matches!(o, Outcome::Solved { .. }))
+        .count();
+    let sum: Duration = outcomes
+        .iter()
+        .filter_map(|o| match o {
+            Outcome::Solved { duration, .. } => Some(*duration),
+            Outcome::NoInput => None,
+        })
+        .sum();
+    println!(
+        "Total: {:>10.3} ms for {solved} days",
+        total.as_secs_f64() * 1000.0
+    );
+    if args.parallel {
+        println!("Sum of the days: {:>10.3} ms", sum.as_secs_f64() * 1000.0);
+    }
+}
@@ -11,7 +11,7 @@ enum Block {
 impl fmt::Display for Block {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
-            Self::Free => write!(f, ".",),
+            Self::Free => write!(f, "."),
             Self::File(id) => {
                 if id < 10 {
                     write!(f, "{id}")
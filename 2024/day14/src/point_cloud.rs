@@ -0,0 +1,69 @@
+//! Points moving in straight lines at constant speed, which at some point form a picture.
+//! Copied from utils, only with the wrapping around a grid.
+//!
+//! When the points wrap around a grid, each axis is periodic, with the grid width and height
+//! as periods: we look in each period for the time with the smallest variance, and combine both
+//! with the Chinese remainder theorem.
+
+/// Position and velocity, each as (x, y).
+pub type Point = ((i64, i64), (i64, i64));
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointCloud {
+    positions: Vec<(i64, i64)>,
+    velocities: Vec<(i64, i64)>,
+}
+
+impl PointCloud {
+    pub fn new(points: &[Point]) -> Self {
+        Self {
+            positions: points.iter().map(|p| p.0).collect(),
+            velocities: points.iter().map(|p| p.1).collect(),
+        }
+    }
+
+    pub fn positions_at(&self, time: i64) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.positions
+            .iter()
+            .zip(&self.velocities)
+            .map(move |(p, v)| (p.0 + v.0 * time, p.1 + v.1 * time))
+    }
+
+    /// Positions when wrapping around a grid of this size.
+    pub fn wrapped_positions_at(
+        &self,
+        time: i64,
+        width: i64,
+        height: i64,
+    ) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.positions_at(time)
+            .map(move |(x, y)| (x.rem_euclid(width), y.rem_euclid(height)))
+    }
+
+    // Sum of the squared distances to the mean, times the number of points, to stay with integers.
+    fn variance(values: impl Iterator<Item = i64>) -> i64 {
+        let values: Vec<i64> = values.collect();
+        let n = i64::try_from(values.len()).unwrap();
+        let sum: i64 = values.iter().sum();
+        values.iter().map(|v| (v * n - sum).pow(2)).sum::<i64>() / n
+    }
+
+    /// Time at which the points are the most grouped when wrapping around a grid of this size.
+    /// The width and height need to be coprime, which they are in the puzzles.
+    pub fn most_grouped_wrapped_time(&self, width: i64, height: i64) -> i64 {
+        let best = |period: i64, axis: fn((i64, i64)) -> i64| {
+            (0..period)
+                .min_by_key(|t| {
+                    Self::variance(self.wrapped_positions_at(*t, width, height).map(axis))
+                })
+                .unwrap()
+        };
+        let tx = best(width, |p| p.0);
+        let ty = best(height, |p| p.1);
+        // Chinese remainder theorem: t = tx mod width and t = ty mod height.
+        (0..height)
+            .map(|k| tx + k * width)
+            .find(|t| t % height == ty)
+            .expect("Width and height must be coprime")
+    }
+}
@@ -0,0 +1,63 @@
+use circuit::{Circuit, adder};
+
+fn build(input: &str) -> Circuit {
+    Circuit::parse(input).unwrap().with_bits(1)
+}
+
+fn z_output_number(circuit: &Circuit) -> u64 {
+    circuit::number(&circuit.evaluate().unwrap(), 'z')
+}
+
+// Compares the circuit to a generated adder, to find the swapped wires.
+fn swapped_wires(circuit: &Circuit) -> String {
+    let bits = circuit
+        .initial
+        .keys()
+        .filter(|w| w.starts_with('x'))
+        .count();
+    let swaps = adder::find_swapped_outputs(circuit, bits).unwrap();
+    let mut wires: Vec<&str> = swaps
+        .iter()
+        .flat_map(|(w1, w2)| [w1.as_str(), w2.as_str()])
+        .collect();
+    wires.sort_unstable();
+    wires.join(",")
+}
+
+#[must_use]
+pub fn solve(input: &str) -> Vec<String> {
+    let circuit = build(input);
+    // View with dot -Tpdf -Kdot input.gv > input.pdf
+    // println!("{}", circuit.graphviz());
+
+    vec![
+        z_output_number(&circuit).to_string(),
+        swapped_wires(&circuit),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT_TEST_1: &str = include_str!("../resources/input_test_1");
+    const INPUT_TEST_2: &str = include_str!("../resources/input_test_2");
+    // A 6 bits adder with the internal wires renamed as in the puzzle input,
+    // z02 being swapped with the AND of x02 and y02, and the XOR of x04 and y04 with their AND.
+    const INPUT_TEST_3: &str = include_str!("../resources/input_test_3");
+
+    #[test]
+    fn test_part1_1() {
+        assert_eq!(z_output_number(&build(INPUT_TEST_1)), 4);
+    }
+
+    #[test]
+    fn test_part1_2() {
+        assert_eq!(z_output_number(&build(INPUT_TEST_2)), 2024);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(swapped_wires(&build(INPUT_TEST_3)), "gkk,lvb,uda,z02");
+    }
+}
@@ -0,0 +1,236 @@
+//! Ripple-carry adders, and how to find the wires swapped in one (2024 day 24).
+//!
+//! Bit `i` of the adder adds `xNN`, `yNN` and the carry of the previous bit:
+//!
+//! ```text
+//! xNN XOR yNN -> xorNN
+//! xNN AND yNN -> andNN
+//! carryMM XOR xorNN -> zNN
+//! carryMM AND xorNN -> cxorNN
+//! cxorNN OR andNN -> carryNN
+//! ```
+//!
+//! Bit 0 has no carry in, and the last carry is the highest bit of the result.
+
+use std::collections::HashMap;
+
+use crate::{Circuit, Gate, Op};
+
+fn wire(name: &str, i: usize) -> String {
+    format!("{name}{i:02}")
+}
+
+/// A working adder of two numbers of `bits` bits.
+#[must_use]
+pub fn ripple_carry_adder(bits: usize) -> Circuit {
+    let gate = |op, in1: &str, in2: &str, out: &str| Gate::new(op, &[in1, in2], out);
+    let mut gates = vec![
+        gate(Op::Xor, "x00", "y00", "z00"),
+        gate(Op::And, "x00", "y00", "carry00"),
+    ];
+    for i in 1..bits {
+        let (x, y, carry) = (wire("x", i), wire("y", i), wire("carry", i - 1));
+        let (xor, and, cxor) = (wire("xor", i), wire("and", i), wire("cxor", i));
+        let carry_out = if i == bits - 1 {
+            wire("z", bits)
+        } else {
+            wire("carry", i)
+        };
+        gates.extend([
+            gate(Op::Xor, &x, &y, &xor),
+            gate(Op::And, &x, &y, &and),
+            gate(Op::Xor, &carry, &xor, &wire("z", i)),
+            gate(Op::And, &carry, &xor, &cxor),
+            gate(Op::Or, &cxor, &and, &carry_out),
+        ]);
+    }
+    let mut circuit = Circuit::new(gates).with_bits(1);
+    for i in 0..bits {
+        circuit.set(&wire("x", i), 0);
+        circuit.set(&wire("y", i), 0);
+    }
+    circuit
+}
+
+fn is_output(wire: &str) -> bool {
+    wire.starts_with('z')
+}
+
+fn find_gate<'a>(circuit: &'a Circuit, op: Op, inputs: &[&str]) -> Option<&'a Gate> {
+    circuit.gates.iter().find(|g| {
+        g.op == op && {
+            let mut actual: Vec<&str> = g.input_wires().collect();
+            let mut expected = inputs.to_vec();
+            actual.sort_unstable();
+            expected.sort_unstable();
+            actual == expected
+        }
+    })
+}
+
+/// Compares the circuit to a working adder of `bits` bits, returning the pairs of wires
+/// whose gates have their outputs swapped.
+///
+/// Gates are matched to the working adder by their operation and inputs, starting from the
+/// `x` and `y` wires. When no gate matches, the gate with only one of the inputs gives
+/// the wire swapped with the expected one. Output wires are checked as well.
+///
+/// # Errors
+///
+/// If the circuit differs from an adder in other ways than swapped outputs.
+pub fn find_swapped_outputs(
+    circuit: &Circuit,
+    bits: usize,
+) -> Result<Vec<(String, String)>, String> {
+    let reference = ripple_carry_adder(bits);
+    let order = reference.topological_order().map_err(|e| e.to_string())?;
+    let mut circuit = circuit.clone();
+    let mut swaps: Vec<(String, String)> = Vec::new();
+
+    'restart: loop {
+        if swaps.len() > circuit.gates.len() {
+            return Err("Too many swaps, the circuit isn't an adder".to_string());
+        }
+        // Reference wire name to the wire in the circuit.
+        let mut mapping: HashMap<&str, String> = reference
+            .initial
+            .keys()
+            .map(|w| (w.as_str(), w.clone()))
+            .collect();
+        for &i in &order {
+            let expected = &reference.gates[i];
+            let inputs: Vec<&str> = expected
+                .input_wires()
+                .map(|w| mapping[w].as_str())
+                .collect();
+            if let Some(gate) = find_gate(&circuit, expected.op, &inputs) {
+                let output = gate.output.clone();
+                if is_output(&expected.output) && output != expected.output {
+                    circuit.swap_outputs(&output, &expected.output);
+                    swaps.push((output, expected.output.clone()));
+                    continue 'restart;
+                }
+                mapping.insert(&expected.output, output);
+                continue;
+            }
+            // One of the inputs is wrong, the gate having the other one tells which.
+            let candidates: Vec<&Gate> = circuit
+                .gates
+                .iter()
+                .filter(|g| g.op == expected.op && g.input_wires().any(|w| inputs.contains(&w)))
+                .collect();
+            let [gate] = candidates[..] else {
+                return Err(format!("Can't find the gate for: {expected}"));
+            };
+            let actual: Vec<&str> = gate.input_wires().collect();
+            let wrong = actual.iter().find(|w| !inputs.contains(w));
+            let missing = inputs.iter().find(|w| !actual.contains(w));
+            let (Some(wrong), Some(missing)) = (wrong, missing) else {
+                return Err(format!("Can't find the gate for: {expected}"));
+            };
+            let pair = ((*wrong).to_string(), (*missing).to_string());
+            circuit.swap_outputs(&pair.0, &pair.1);
+            swaps.push(pair);
+            continue 'restart;
+        }
+        return Ok(swaps);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number;
+
+    fn add(adder: &Circuit, bits: usize, a: u64, b: u64) -> u64 {
+        let mut adder = adder.clone();
+        adder.set_number('x', a, bits);
+        adder.set_number('y', b, bits);
+        number(&adder.evaluate().unwrap(), 'z')
+    }
+
+    #[test]
+    fn test_ripple_carry_adder() {
+        let adder = ripple_carry_adder(8);
+        for (a, b) in [(0, 0), (1, 1), (200, 100), (255, 255), (37, 218)] {
+            assert_eq!(add(&adder, 8, a, b), a + b);
+        }
+    }
+
+    // Renames the internal wires, as they are in the puzzle input.
+    fn scramble(adder: &Circuit) -> Circuit {
+        let mut renaming: HashMap<String, String> = HashMap::new();
+        for (i, gate) in adder.gates.iter().enumerate() {
+            if !is_output(&gate.output) {
+                renaming.insert(gate.output.clone(), format!("w{}", (i * 7) % 97));
+            }
+        }
+        let rename = |w: &str| renaming.get(w).cloned().unwrap_or(w.to_string());
+        let gates = adder
+            .gates
+            .iter()
+            .map(|g| {
+                let inputs: Vec<String> = g.input_wires().map(rename).collect();
+                let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+                Gate::new(g.op, &inputs, &rename(&g.output))
+            })
+            .collect();
+        let mut circuit = Circuit::new(gates).with_bits(1);
+        circuit.initial.clone_from(&adder.initial);
+        circuit
+    }
+
+    #[test]
+    fn test_find_swapped_outputs() {
+        let bits = 12;
+        let mut circuit = scramble(&ripple_carry_adder(bits));
+        assert_eq!(find_swapped_outputs(&circuit, bits), Ok(Vec::new()));
+
+        // XOR and AND of the inputs of the same bit.
+        let xor3 = find_gate(&circuit, Op::Xor, &["x03", "y03"])
+            .unwrap()
+            .output
+            .clone();
+        let and3 = find_gate(&circuit, Op::And, &["x03", "y03"])
+            .unwrap()
+            .output
+            .clone();
+        // An output and a carry.
+        let z7 = circuit.gates.iter().find(|g| g.output == "z07").unwrap();
+        let carry6 = z7
+            .input_wires()
+            .find(|w| {
+                circuit
+                    .gates
+                    .iter()
+                    .any(|g| g.output == *w && g.op == Op::Or)
+            })
+            .unwrap()
+            .to_string();
+        // An output and the AND of its inputs.
+        let and9 = find_gate(&circuit, Op::And, &["x09", "y09"])
+            .unwrap()
+            .output
+            .clone();
+        let mut expected = vec![
+            (xor3.clone(), and3.clone()),
+            ("z06".to_string(), carry6.clone()),
+            ("z09".to_string(), and9.clone()),
+        ];
+        for (w1, w2) in &expected {
+            circuit.swap_outputs(w1, w2);
+        }
+        assert_ne!(add(&circuit, bits, 1234, 567), 1234 + 567);
+
+        let mut swaps = find_swapped_outputs(&circuit, bits).unwrap();
+        for pairs in [&mut swaps, &mut expected] {
+            for pair in pairs.iter_mut() {
+                if pair.0 > pair.1 {
+                    *pair = (pair.1.clone(), pair.0.clone());
+                }
+            }
+            pairs.sort();
+        }
+        assert_eq!(swaps, expected);
+    }
+}
@@ -0,0 +1,193 @@
+//! The gates and their syntax, common to 2015 day 7 and 2024 day 24:
+//!
+//! ```text
+//! 123 -> x
+//! x AND y -> d
+//! x LSHIFT 2 -> f
+//! NOT x -> h
+//! x00 XOR y00 -> z00
+//! ```
+
+use std::fmt;
+
+/// An input of a gate, either a wire or a constant signal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Wire(String),
+    Value(u64),
+}
+
+impl Operand {
+    fn parse(s: &str) -> Self {
+        s.parse()
+            .map_or_else(|_| Operand::Wire(s.to_string()), Operand::Value)
+    }
+
+    #[must_use]
+    pub fn wire(&self) -> Option<&str> {
+        match self {
+            Operand::Wire(w) => Some(w),
+            Operand::Value(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Wire(w) => write!(f, "{w}"),
+            Operand::Value(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    /// Copies the input to the output, like `123 -> x` or `lx -> a`.
+    Forward,
+    Not,
+    And,
+    Or,
+    Xor,
+    LShift,
+    RShift,
+}
+
+impl Op {
+    fn name(self) -> &'static str {
+        match self {
+            Op::Forward => "",
+            Op::Not => "NOT",
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Xor => "XOR",
+            Op::LShift => "LSHIFT",
+            Op::RShift => "RSHIFT",
+        }
+    }
+
+    /// Binary operations whose inputs can be swapped.
+    #[must_use]
+    pub fn is_commutative(self) -> bool {
+        matches!(self, Op::And | Op::Or | Op::Xor)
+    }
+
+    /// Applies the operation, keeping only `mask` bits.
+    ///
+    /// # Panics
+    ///
+    /// If there are not enough inputs for the operation.
+    #[must_use]
+    pub fn apply(self, inputs: &[u64], mask: u64) -> u64 {
+        let result = match self {
+            Op::Forward => inputs[0],
+            Op::Not => !inputs[0],
+            Op::And => inputs[0] & inputs[1],
+            Op::Or => inputs[0] | inputs[1],
+            Op::Xor => inputs[0] ^ inputs[1],
+            Op::LShift => inputs[0]
+                .checked_shl(u32::try_from(inputs[1]).unwrap())
+                .unwrap_or(0),
+            Op::RShift => inputs[0]
+                .checked_shr(u32::try_from(inputs[1]).unwrap())
+                .unwrap_or(0),
+        };
+        result & mask
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Gate {
+    pub op: Op,
+    /// One input for the unary operations, two for the others.
+    pub inputs: Vec<Operand>,
+    pub output: String,
+}
+
+impl Gate {
+    #[must_use]
+    pub fn new(op: Op, inputs: &[&str], output: &str) -> Self {
+        Self {
+            op,
+            inputs: inputs.iter().map(|i| Operand::parse(i)).collect(),
+            output: output.to_string(),
+        }
+    }
+
+    /// Parses a gate like `x AND y -> d`.
+    ///
+    /// # Errors
+    ///
+    /// If the line isn't a valid gate.
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let (expr, output) = line
+            .split_once(" -> ")
+            .ok_or_else(|| format!("Invalid gate: {line}"))?;
+        let parts: Vec<&str> = expr.split_whitespace().collect();
+        let (op, inputs) = match parts[..] {
+            [input] => (Op::Forward, vec![input]),
+            ["NOT", input] => (Op::Not, vec![input]),
+            [in1, op, in2] => {
+                let op = match op {
+                    "AND" => Op::And,
+                    "OR" => Op::Or,
+                    "XOR" => Op::Xor,
+                    "LSHIFT" => Op::LShift,
+                    "RSHIFT" => Op::RShift,
+                    _ => return Err(format!("Unknown operation {op} in: {line}")),
+                };
+                (op, vec![in1, in2])
+            }
+            _ => return Err(format!("Invalid gate: {line}")),
+        };
+        Ok(Self::new(op, &inputs, output.trim()))
+    }
+
+    pub fn input_wires(&self) -> impl Iterator<Item = &str> {
+        self.inputs.iter().filter_map(Operand::wire)
+    }
+}
+
+impl fmt::Display for Gate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inputs.as_slice() {
+            [input] if self.op == Op::Forward => write!(f, "{input} -> {}", self.output),
+            [input] => write!(f, "{} {input} -> {}", self.op.name(), self.output),
+            [in1, in2] => write!(f, "{in1} {} {in2} -> {}", self.op.name(), self.output),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        for line in [
+            "123 -> x",
+            "lx -> a",
+            "NOT x -> h",
+            "x LSHIFT 2 -> f",
+            "1 AND cx -> cy",
+            "x00 XOR y00 -> z00",
+        ] {
+            assert_eq!(Gate::parse(line).unwrap().to_string(), line);
+        }
+        let gate = Gate::parse("x LSHIFT 2 -> f").unwrap();
+        assert_eq!(gate.op, Op::LShift);
+        assert_eq!(gate.inputs, [Operand::Wire("x".into()), Operand::Value(2)]);
+        assert!(Gate::parse("x NAND y -> z").is_err());
+        assert!(Gate::parse("x AND y").is_err());
+    }
+
+    #[test]
+    fn test_apply() {
+        let mask = 0xFFFF;
+        assert_eq!(Op::Not.apply(&[123], mask), 65412);
+        assert_eq!(Op::LShift.apply(&[123, 2], mask), 492);
+        assert_eq!(Op::RShift.apply(&[456, 2], mask), 114);
+        assert_eq!(Op::Xor.apply(&[1, 1], 1), 0);
+    }
+}
@@ -0,0 +1,311 @@
+//! Circuits of logic gates connected by wires, as in 2015 day 7 and 2024 day 24.
+//!
+//! A circuit is parsed from the gates, one per line, optionally preceded by the initial values
+//! of some wires (2024 syntax):
+//!
+//! ```
+//! let circuit = circuit::Circuit::parse("x00: 1\ny00: 1\n\nx00 AND y00 -> z00\n").unwrap();
+//! assert_eq!(circuit.evaluate().unwrap()["z00"], 1);
+//! ```
+//!
+//! Evaluation follows the topological order of the gates, so cycles are detected instead of
+//! looping forever. Signals have a configurable width, 16 bits in 2015 and 1 bit in 2024.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::{self, Write};
+
+pub mod adder;
+pub mod gate;
+
+pub use gate::{Gate, Op, Operand};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    Parse(String),
+    /// Wires part of a cycle, or depending on one.
+    Cycle(Vec<String>),
+    /// A wire used as input that no gate nor initial value sets.
+    Unconnected(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{e}"),
+            Error::Cycle(wires) => write!(f, "Cycle between wires {}", wires.join(", ")),
+            Error::Unconnected(wire) => write!(f, "Wire {wire} isn't connected to anything"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Values of the wires after evaluation, sorted by name.
+pub type Signals = BTreeMap<String, u64>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circuit {
+    pub gates: Vec<Gate>,
+    /// Wire values set directly, without a gate.
+    pub initial: Signals,
+    bits: u32,
+}
+
+impl Circuit {
+    #[must_use]
+    pub fn new(gates: Vec<Gate>) -> Self {
+        Self {
+            gates,
+            initial: Signals::new(),
+            bits: 64,
+        }
+    }
+
+    /// Parses the gates and the `wire: value` initial values.
+    /// Signals are 64 bits, see [`Circuit::with_bits`].
+    ///
+    /// # Errors
+    ///
+    /// On the first invalid line.
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut circuit = Self::new(Vec::new());
+        for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some((wire, value)) = line.split_once(": ") {
+                let value = value
+                    .parse()
+                    .map_err(|_| Error::Parse(format!("Invalid value: {line}")))?;
+                circuit.initial.insert(wire.to_string(), value);
+            } else {
+                circuit.gates.push(Gate::parse(line).map_err(Error::Parse)?);
+            }
+        }
+        Ok(circuit)
+    }
+
+    /// Sets the width of the signals.
+    ///
+    /// # Panics
+    ///
+    /// If the width isn't between 1 and 64 bits.
+    #[must_use]
+    pub fn with_bits(mut self, bits: u32) -> Self {
+        assert!((1..=64).contains(&bits));
+        self.bits = bits;
+        self
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.bits)
+    }
+
+    fn driver(&self, wire: &str) -> Option<usize> {
+        self.gates.iter().position(|g| g.output == wire)
+    }
+
+    /// Indexes of the gates, each one after the gates setting its inputs.
+    ///
+    /// # Errors
+    ///
+    /// If a wire isn't connected, or if there is a cycle.
+    pub fn topological_order(&self) -> Result<Vec<usize>, Error> {
+        let drivers: HashMap<&str, usize> = self
+            .gates
+            .iter()
+            .enumerate()
+            .map(|(i, g)| (g.output.as_str(), i))
+            .collect();
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); self.gates.len()];
+        let mut missing_inputs: Vec<usize> = vec![0; self.gates.len()];
+        for (i, gate) in self.gates.iter().enumerate() {
+            for wire in gate.input_wires() {
+                if let Some(&driver) = drivers.get(wire) {
+                    dependents[driver].push(i);
+                    missing_inputs[i] += 1;
+                } else if !self.initial.contains_key(wire) {
+                    return Err(Error::Unconnected(wire.to_string()));
+                }
+            }
+        }
+
+        let mut ready: VecDeque<usize> = (0..self.gates.len())
+            .filter(|i| missing_inputs[*i] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.gates.len());
+        while let Some(i) = ready.pop_front() {
+            order.push(i);
+            for &d in &dependents[i] {
+                missing_inputs[d] -= 1;
+                if missing_inputs[d] == 0 {
+                    ready.push_back(d);
+                }
+            }
+        }
+        if order.len() < self.gates.len() {
+            let mut wires: Vec<String> = (0..self.gates.len())
+                .filter(|i| missing_inputs[*i] > 0)
+                .map(|i| self.gates[i].output.clone())
+                .collect();
+            wires.sort();
+            return Err(Error::Cycle(wires));
+        }
+        Ok(order)
+    }
+
+    /// Computes the signals on all wires.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Circuit::topological_order`].
+    pub fn evaluate(&self) -> Result<Signals, Error> {
+        let mut signals = self.initial.clone();
+        let mask = self.mask();
+        for i in self.topological_order()? {
+            let gate = &self.gates[i];
+            let inputs: Vec<u64> = gate
+                .inputs
+                .iter()
+                .map(|input| match input {
+                    Operand::Wire(w) => signals[w],
+                    Operand::Value(v) => *v,
+                })
+                .collect();
+            signals.insert(gate.output.clone(), gate.op.apply(&inputs, mask));
+        }
+        Ok(signals)
+    }
+
+    /// Forces the value of a wire, replacing the gate that was setting it.
+    pub fn set(&mut self, wire: &str, value: u64) {
+        if let Some(i) = self.driver(wire) {
+            self.gates.remove(i);
+        }
+        self.initial.insert(wire.to_string(), value);
+    }
+
+    /// Sets the wires `<prefix>00`, `<prefix>01`, etc. to the bits of the number.
+    pub fn set_number(&mut self, prefix: char, number: u64, bits: usize) {
+        for i in 0..bits {
+            self.set(&format!("{prefix}{i:02}"), (number >> i) & 1);
+        }
+    }
+
+    /// Swaps the outputs of the gates setting these two wires.
+    ///
+    /// # Panics
+    ///
+    /// If no gate sets one of the wires.
+    pub fn swap_outputs(&mut self, wire1: &str, wire2: &str) {
+        let i1 = self.driver(wire1).expect("No gate for the wire");
+        let i2 = self.driver(wire2).expect("No gate for the wire");
+        self.gates[i1].output = wire2.to_string();
+        self.gates[i2].output = wire1.to_string();
+    }
+
+    /// Circuit in the Graphviz format. View with `dot -Tpdf -Kdot input.gv > input.pdf`.
+    #[must_use]
+    pub fn graphviz(&self) -> String {
+        let mut out = String::from("digraph {\n");
+        for (wire, value) in &self.initial {
+            writeln!(out, "    \"{wire}\" [shape=box, label=\"{wire}: {value}\"]").unwrap();
+        }
+        for (i, gate) in self.gates.iter().enumerate() {
+            let (shape, label) = match gate.op {
+                Op::Forward => ("circle", String::new()),
+                Op::Not => ("circle", "NOT".to_string()),
+                Op::LShift | Op::RShift => ("rectangle", format!("{:?}", gate.op).to_uppercase()),
+                Op::And | Op::Or | Op::Xor => ("triangle", format!("{:?}", gate.op).to_uppercase()),
+            };
+            writeln!(out, "    gate{i} [shape={shape}, label=\"{label}\"]").unwrap();
+            for input in &gate.inputs {
+                writeln!(out, "    \"{input}\" -> gate{i}").unwrap();
+            }
+            writeln!(out, "    gate{i} -> \"{}\"", gate.output).unwrap();
+        }
+        out += "}\n";
+        out
+    }
+}
+
+/// The number formed by the wires `<prefix>00` (lowest bit), `<prefix>01`, etc.
+#[must_use]
+pub fn number(signals: &Signals, prefix: char) -> u64 {
+    signals
+        .iter()
+        .filter_map(|(wire, value)| {
+            let index: u32 = wire.strip_prefix(prefix)?.parse().ok()?;
+            Some(value << index)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT_2015: &str = "123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i";
+
+    #[test]
+    fn test_evaluate_2015() {
+        let circuit = Circuit::parse(INPUT_2015).unwrap().with_bits(16);
+        let signals = circuit.evaluate().unwrap();
+        let expected = [
+            ("d", 72),
+            ("e", 507),
+            ("f", 492),
+            ("g", 114),
+            ("h", 65412),
+            ("i", 65079),
+            ("x", 123),
+            ("y", 456),
+        ];
+        assert_eq!(
+            signals,
+            expected.iter().map(|(w, v)| (w.to_string(), *v)).collect()
+        );
+    }
+
+    #[test]
+    fn test_evaluate_2024() {
+        let circuit = Circuit::parse(
+            "x00: 1\nx01: 1\nx02: 1\ny00: 0\ny01: 1\ny02: 0\n\nx00 AND y00 -> z00\nx01 XOR y01 -> z01\nx02 OR y02 -> z02",
+        )
+        .unwrap()
+        .with_bits(1);
+        assert_eq!(number(&circuit.evaluate().unwrap(), 'z'), 4);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut circuit = Circuit::parse(INPUT_2015).unwrap().with_bits(16);
+        circuit.set("x", 1);
+        assert_eq!(circuit.evaluate().unwrap()["d"], 0);
+        assert_eq!(circuit.gates.len(), 7);
+    }
+
+    #[test]
+    fn test_errors() {
+        let circuit = Circuit::parse("a AND b -> c\nc -> a\n1 -> b\nc -> d\nd OR e -> f").unwrap();
+        assert_eq!(circuit.evaluate(), Err(Error::Unconnected("e".into())));
+        let circuit = Circuit::parse("a AND b -> c\nc -> a\n1 -> b\nc -> d").unwrap();
+        assert_eq!(
+            circuit.evaluate(),
+            Err(Error::Cycle(vec!["a".into(), "c".into(), "d".into()]))
+        );
+        assert!(Circuit::parse("x00: one").is_err());
+    }
+
+    #[test]
+    fn test_graphviz() {
+        let gv = Circuit::parse(INPUT_2015).unwrap().graphviz();
+        assert!(gv.starts_with("digraph {\n"));
+        assert!(gv.contains("gate4 [shape=rectangle, label=\"LSHIFT\"]"));
+        assert!(gv.contains("\"x\" -> gate4\n    \"2\" -> gate4\n    gate4 -> \"f\""));
+    }
+}
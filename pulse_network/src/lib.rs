@@ -0,0 +1,440 @@
+//! Networks of modules sending pulses to each other, as in 2023 day 20.
+//!
+//! The network is parsed from the puzzle syntax, one module per line:
+//!
+//! ```
+//! let mut network = pulse_network::Network::parse("broadcaster -> a\n%a -> inv\n&inv -> out").unwrap();
+//! let pulses = network.press_button();
+//! assert_eq!(pulses.len(), 4);
+//! ```
+//!
+//! Finding when a module receives a low pulse usually takes too many button presses to simulate.
+//! In the puzzle inputs, the module is fed by a conjunction, whose inputs are counters sending
+//! a high pulse at regular intervals. [`Network::presses_for_low_to`] finds the periods of
+//! these counters and combines them.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+
+pub mod module;
+
+pub use module::{Broadcast, Conjunction, FlipFlop, Module, ModuleCommon};
+
+pub const LOW: bool = false;
+pub const HIGH: bool = true;
+
+/// Information about a pulse: Sender, receiver and value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pulse {
+    pub from: String,
+    pub value: bool,
+    pub to: String,
+}
+
+impl Pulse {
+    #[must_use]
+    pub fn new(from: &str, value: bool, to: &str) -> Self {
+        Self {
+            from: from.to_string(),
+            value,
+            to: to.to_string(),
+        }
+    }
+}
+
+/// Pulses sent by the modules, as (button press, value).
+/// A module sends the same value to all its next modules, it's recorded only once.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    /// Modules to record, all if none.
+    watched: Option<HashSet<String>>,
+    sent: HashMap<String, Vec<(u64, bool)>>,
+}
+
+impl History {
+    fn record(&mut self, module: &str, press: u64, value: bool) {
+        if self.watched.as_ref().is_none_or(|w| w.contains(module)) {
+            self.sent
+                .entry(module.to_string())
+                .or_default()
+                .push((press, value));
+        }
+    }
+
+    #[must_use]
+    pub fn sent_by(&self, module: &str) -> &[(u64, bool)] {
+        self.sent.get(module).map_or(&[], Vec::as_slice)
+    }
+
+    /// Button presses during which the module sent this value, without duplicates.
+    pub fn presses_sending(&self, module: &str, value: bool) -> impl Iterator<Item = u64> + '_ {
+        let mut last = None;
+        self.sent_by(module)
+            .iter()
+            .filter(move |(_, v)| *v == value)
+            .map(|(press, _)| *press)
+            .filter(move |press| last.replace(*press) != Some(*press))
+    }
+
+    fn clear(&mut self) {
+        self.sent.clear();
+    }
+}
+
+/// Above that, we consider the network has no cycle.
+const MAX_PRESSES: u64 = 1_000_000;
+
+#[derive(Debug)]
+pub struct Network {
+    modules: HashMap<String, Box<dyn Module>>,
+    /// Module names in the order of the input, for a stable output.
+    names: Vec<String>,
+    presses: u64,
+    history: Option<History>,
+}
+
+impl Network {
+    pub const BUTTON: &'static str = "button";
+
+    /// Parses the configuration, one module per line.
+    ///
+    /// # Errors
+    ///
+    /// On the first invalid line.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut modules: HashMap<String, Box<dyn Module>> = HashMap::new();
+        let mut names = Vec::new();
+        for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let m: Box<dyn Module> = if line.starts_with('%') {
+                Box::new(FlipFlop::build(line)?)
+            } else if line.starts_with('&') {
+                Box::new(Conjunction::build(line)?)
+            } else if line.starts_with(Broadcast::NAME) {
+                Box::new(Broadcast::build(line)?)
+            } else {
+                return Err(format!("Invalid line: {line}"));
+            };
+            names.push(m.get_name().to_string());
+            modules.insert(m.get_name().to_string(), m);
+        }
+        if !modules.contains_key(Broadcast::NAME) {
+            return Err("No broadcaster module".to_string());
+        }
+        let mut network = Self {
+            modules,
+            names,
+            presses: 0,
+            history: None,
+        };
+        // Conjunction setup needs to be finished once we know all the modules.
+        let all_inputs: Vec<(String, HashMap<String, bool>)> = network
+            .names
+            .iter()
+            .map(|name| {
+                let inputs = network.inputs_of(name);
+                let inputs = inputs.iter().map(|i| (i.to_string(), LOW)).collect();
+                (name.clone(), inputs)
+            })
+            .collect();
+        for (name, inputs) in all_inputs {
+            if let Some(module) = network.modules.get_mut(&name) {
+                module.update_previous_pulse(inputs);
+            }
+        }
+        Ok(network)
+    }
+
+    /// Names of the modules sending pulses to this one.
+    #[must_use]
+    pub fn inputs_of(&self, name: &str) -> Vec<&str> {
+        self.names
+            .iter()
+            .filter(|n| {
+                self.modules[n.as_str()]
+                    .get_next_modules()
+                    .iter()
+                    .any(|m| m == name)
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
+    #[must_use]
+    pub fn module(&self, name: &str) -> Option<&dyn Module> {
+        self.modules.get(name).map(AsRef::as_ref)
+    }
+
+    /// Number of button presses since the creation or the last reset.
+    #[must_use]
+    pub fn presses(&self) -> u64 {
+        self.presses
+    }
+
+    #[must_use]
+    pub fn is_initial_state(&self) -> bool {
+        self.modules.values().all(|m| m.is_initial_state())
+    }
+
+    /// Sets all the modules back to their initial state, and clears the history.
+    pub fn reset(&mut self) {
+        self.modules.values_mut().for_each(|m| m.reset());
+        self.presses = 0;
+        if let Some(history) = &mut self.history {
+            history.clear();
+        }
+    }
+
+    /// Starts recording the pulses sent by all modules.
+    pub fn record_history(&mut self) {
+        self.history = Some(History::default());
+    }
+
+    /// Starts recording the pulses sent by these modules only.
+    pub fn record_history_of(&mut self, modules: &[&str]) {
+        self.history = Some(History {
+            watched: Some(modules.iter().map(ToString::to_string).collect()),
+            sent: HashMap::new(),
+        });
+    }
+
+    #[must_use]
+    pub fn history(&self) -> Option<&History> {
+        self.history.as_ref()
+    }
+
+    /// Presses the button, which sends a low pulse to the broadcaster.
+    /// Returns all the pulses sent, in the order they were processed.
+    pub fn press_button(&mut self) -> Vec<Pulse> {
+        self.presses += 1;
+        let mut sent_pulses = Vec::new();
+        let mut pulses_to_exec = VecDeque::from([Pulse::new(Self::BUTTON, LOW, Broadcast::NAME)]);
+        while let Some(received_pulse) = pulses_to_exec.pop_front() {
+            // If dest module is not found, nothing to execute.
+            if let Some(module) = self.modules.get_mut(&received_pulse.to) {
+                let sent = module.exec(&received_pulse);
+                if let (Some(history), Some(first)) = (&mut self.history, sent.first()) {
+                    history.record(&received_pulse.to, self.presses, first.value);
+                }
+                pulses_to_exec.extend(sent);
+            }
+            sent_pulses.push(received_pulse);
+        }
+        sent_pulses
+    }
+
+    /// Periods of the counters feeding the conjunction that sends pulses to `target`:
+    /// for each of them, the number of presses between the high pulses it sends.
+    ///
+    /// The network is reset, and its history replaced by the one of the counters.
+    ///
+    /// # Errors
+    ///
+    /// If the network doesn't have this structure, or if the counters don't send a high pulse
+    /// at regular intervals from the start.
+    pub fn sub_counter_periods(&mut self, target: &str) -> Result<Vec<(String, u64)>, String> {
+        let feeders = self.inputs_of(target);
+        let [conjunction] = feeders[..] else {
+            return Err(format!(
+                "{target} must be fed by one module, not {feeders:?}"
+            ));
+        };
+        if self.modules[conjunction].get_type() != Conjunction::TYPE {
+            return Err(format!(
+                "{conjunction} feeding {target} isn't a conjunction"
+            ));
+        }
+        let counters: Vec<String> = self
+            .inputs_of(conjunction)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        self.record_history_of(&counters.iter().map(String::as_str).collect::<Vec<_>>());
+        self.reset();
+
+        // Waiting for two high pulses of each counter, to check the period.
+        let high_presses = |network: &Self, counter: &str| -> Vec<u64> {
+            network.history().map_or_else(Vec::new, |h| {
+                h.presses_sending(counter, HIGH).take(2).collect()
+            })
+        };
+        while counters.iter().any(|c| high_presses(self, c).len() < 2) {
+            if self.presses >= MAX_PRESSES {
+                return Err(format!("No period found in {MAX_PRESSES} presses"));
+            }
+            self.press_button();
+        }
+        counters
+            .iter()
+            .map(|counter| {
+                let presses = high_presses(self, counter);
+                let period = presses[1] - presses[0];
+                if presses[0] == period {
+                    Ok((counter.clone(), period))
+                } else {
+                    Err(format!(
+                        "{counter} doesn't have a cycle starting at 0, high at presses {} and {}",
+                        presses[0], presses[1]
+                    ))
+                }
+            })
+            .collect()
+    }
+
+    /// Fewest number of button presses required to deliver a low pulse to the target,
+    /// found with the periods of the counters feeding it, see [`Network::sub_counter_periods`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Network::sub_counter_periods`].
+    pub fn presses_for_low_to(&mut self, target: &str) -> Result<u64, String> {
+        Ok(self
+            .sub_counter_periods(target)?
+            .iter()
+            .fold(1, |n, (_, period)| lcm(n, *period)))
+    }
+
+    /// Network in the Graphviz format. View with `dot -Tpdf input.gv > input.pdf`.
+    #[must_use]
+    pub fn dot(&self) -> String {
+        let mut out = String::from("digraph modules {\n    layout=\"dot\"\n\n");
+        for name in &self.names {
+            let shape = match self.modules[name].get_type() {
+                Broadcast::TYPE => "diamond",
+                Conjunction::TYPE => "box",
+                _ => "ellipse",
+            };
+            writeln!(out, "    {name} [shape={shape}];").unwrap();
+        }
+        out.push('\n');
+        for name in &self.names {
+            let next = self.modules[name].get_next_modules().join(" ");
+            writeln!(out, "    {name} -> {{ {next} }}").unwrap();
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT_TEST_1: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
+
+    const INPUT_TEST_2: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    // Two counters of 2 and 3 bits, sending a high pulse to `dt` every 3 and 5 presses.
+    // A counter is made of flip-flops, with a conjunction resetting it when it reaches
+    // its period: it's fed by the bits set in the period and sends to the others.
+    const INPUT_COUNTERS: &str = "broadcaster -> a, c
+%a -> b, k
+%b -> k
+&k -> a, inv
+&inv -> dt
+%c -> d, m
+%d -> e
+%e -> m
+&m -> c, d, inv2
+&inv2 -> dt
+&dt -> rx";
+
+    fn pulse_count_product(network: &mut Network) -> usize {
+        let (high, low): (Vec<Pulse>, Vec<Pulse>) = (0..1000)
+            .flat_map(|_| network.press_button())
+            .partition(|p| p.value);
+        high.len() * low.len()
+    }
+
+    #[test]
+    fn test_press_button() {
+        let mut network = Network::parse(INPUT_TEST_1).unwrap();
+        let pulses = network.press_button();
+        assert_eq!(pulses.len(), 12);
+        assert_eq!(pulses[0], Pulse::new("button", LOW, "broadcaster"));
+        assert_eq!(pulses[11], Pulse::new("inv", HIGH, "a"));
+        assert!(network.is_initial_state());
+        assert_eq!(pulse_count_product(&mut network), 32_000_000);
+
+        let mut network = Network::parse(INPUT_TEST_2).unwrap();
+        assert_eq!(network.inputs_of("con"), ["a", "b"]);
+        assert_eq!(pulse_count_product(&mut network), 11_687_500);
+        network.reset();
+        assert_eq!(network.presses(), 0);
+        assert!(network.is_initial_state());
+        assert_eq!(pulse_count_product(&mut network), 11_687_500);
+    }
+
+    #[test]
+    fn test_history() {
+        let mut network = Network::parse(INPUT_TEST_2).unwrap();
+        network.record_history();
+        for _ in 0..4 {
+            network.press_button();
+        }
+        let history = network.history().unwrap();
+        assert_eq!(
+            history.sent_by("a"),
+            [(1, HIGH), (2, LOW), (3, HIGH), (4, LOW)]
+        );
+        assert_eq!(history.sent_by("b"), [(1, HIGH), (3, LOW)]);
+        assert_eq!(
+            history.presses_sending("inv", LOW).collect::<Vec<_>>(),
+            [1, 3]
+        );
+        assert!(history.sent_by("output").is_empty());
+    }
+
+    #[test]
+    fn test_periods() {
+        let mut network = Network::parse(INPUT_COUNTERS).unwrap();
+        assert_eq!(
+            network.sub_counter_periods("rx"),
+            Ok(vec![("inv".to_string(), 3), ("inv2".to_string(), 5)])
+        );
+        assert_eq!(network.presses_for_low_to("rx"), Ok(15));
+
+        // Checking with the simulation.
+        network.reset();
+        let mut presses = 0;
+        while !network
+            .press_button()
+            .iter()
+            .any(|p| p.to == "rx" && p.value == LOW)
+        {
+            presses += 1;
+        }
+        assert_eq!(presses + 1, 15);
+
+        let mut network = Network::parse(INPUT_TEST_1).unwrap();
+        assert!(network.presses_for_low_to("a").is_err());
+    }
+
+    #[test]
+    fn test_dot() {
+        let dot = Network::parse(INPUT_TEST_2).unwrap().dot();
+        assert!(dot.starts_with("digraph modules {\n"));
+        assert!(dot.contains("    broadcaster [shape=diamond];\n"));
+        assert!(dot.contains("    inv [shape=box];\n"));
+        assert!(dot.contains("    a -> { inv con }\n"));
+    }
+}
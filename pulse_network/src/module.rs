@@ -0,0 +1,295 @@
+//! The modules of the network, all implementing the [`Module`] trait.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use crate::{Pulse, HIGH, LOW};
+
+/// Interface shared by all the modules.
+pub trait Module: Debug {
+    fn get_type(&self) -> &str;
+
+    fn get_common(&self) -> &ModuleCommon;
+
+    fn get_name(&self) -> &str {
+        &self.get_common().name
+    }
+
+    fn get_next_modules(&self) -> &[String] {
+        &self.get_common().next_modules
+    }
+
+    /// Executes a pulse.
+    /// Returns the list of pulses sent.
+    fn exec(&mut self, pulse: &Pulse) -> Vec<Pulse>;
+
+    /// Checks if the module is in its initial state.
+    fn is_initial_state(&self) -> bool;
+
+    /// Only needed by Conjunction module.
+    fn update_previous_pulse(&mut self, _previous_pulse: HashMap<String, bool>) {}
+
+    /// Sets the module back to its initial state.
+    fn reset(&mut self);
+}
+
+/// Implementation shared by all modules.
+#[derive(Debug, Clone)]
+pub struct ModuleCommon {
+    name: String,
+    next_modules: Vec<String>,
+}
+
+impl ModuleCommon {
+    fn build(line: &str) -> Result<Self, String> {
+        // module -> a, b, c
+        let (name, next_modules) = line
+            .split_once(" -> ")
+            .ok_or_else(|| format!("Invalid module: {line}"))?;
+        Ok(Self {
+            name: name.to_string(),
+            next_modules: next_modules
+                .split(',')
+                .map(|n| n.trim().to_string())
+                .filter(|n| !n.is_empty())
+                .collect(),
+        })
+    }
+
+    #[must_use]
+    pub fn pulses_to_send(&self, pulse: bool) -> Vec<Pulse> {
+        self.next_modules
+            .iter()
+            .map(|n| Pulse::new(&self.name, pulse, n))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FlipFlop {
+    common: ModuleCommon,
+    state: bool,
+}
+
+impl FlipFlop {
+    pub const TYPE: &'static str = "FlipFlop";
+
+    /// Builds from a line like `%a -> b`.
+    ///
+    /// # Errors
+    ///
+    /// If the line is invalid.
+    pub fn build(line: &str) -> Result<Self, String> {
+        Ok(Self {
+            common: ModuleCommon::build(line.trim_start_matches('%'))?,
+            state: false,
+        })
+    }
+
+    fn flip(&mut self) {
+        self.state ^= true;
+    }
+}
+
+impl Module for FlipFlop {
+    fn get_type(&self) -> &str {
+        Self::TYPE
+    }
+
+    fn get_common(&self) -> &ModuleCommon {
+        &self.common
+    }
+
+    fn exec(&mut self, pulse: &Pulse) -> Vec<Pulse> {
+        if pulse.value {
+            // HIGH: ignore it
+            Vec::new()
+        } else {
+            // LOW: flip and sends pulse matching state
+            self.flip();
+            self.common.pulses_to_send(self.state)
+        }
+    }
+
+    fn is_initial_state(&self) -> bool {
+        !self.state
+    }
+
+    fn reset(&mut self) {
+        self.state = false;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Conjunction {
+    common: ModuleCommon,
+    previous_pulse: HashMap<String, bool>,
+}
+
+impl Conjunction {
+    pub const TYPE: &'static str = "Conjunction";
+
+    /// Builds from a line like `&inv -> b`.
+    ///
+    /// # Errors
+    ///
+    /// If the line is invalid.
+    pub fn build(line: &str) -> Result<Self, String> {
+        Ok(Self {
+            common: ModuleCommon::build(line.trim_start_matches('&'))?,
+            previous_pulse: HashMap::new(),
+        })
+    }
+}
+
+impl Module for Conjunction {
+    fn get_type(&self) -> &str {
+        Self::TYPE
+    }
+
+    fn get_common(&self) -> &ModuleCommon {
+        &self.common
+    }
+
+    fn exec(&mut self, pulse: &Pulse) -> Vec<Pulse> {
+        // Update that memory to this input
+        self.previous_pulse.insert(pulse.from.clone(), pulse.value);
+
+        if self.previous_pulse.values().all(|mem| *mem) {
+            // if all input are high, send low pulse
+            self.common.pulses_to_send(LOW)
+        } else {
+            // else send high pulse
+            self.common.pulses_to_send(HIGH)
+        }
+    }
+
+    fn is_initial_state(&self) -> bool {
+        self.previous_pulse.values().all(|mem| !*mem)
+    }
+
+    fn update_previous_pulse(&mut self, previous_pulse: HashMap<String, bool>) {
+        self.previous_pulse = previous_pulse;
+    }
+
+    fn reset(&mut self) {
+        self.previous_pulse
+            .values_mut()
+            .for_each(|val| *val = false);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Broadcast {
+    common: ModuleCommon,
+}
+
+impl Broadcast {
+    pub const TYPE: &'static str = "Broadcast";
+    pub const NAME: &'static str = "broadcaster";
+
+    /// Builds from a line like `broadcaster -> a, b, c`.
+    ///
+    /// # Errors
+    ///
+    /// If the line is invalid.
+    pub fn build(line: &str) -> Result<Self, String> {
+        Ok(Self {
+            common: ModuleCommon::build(line)?,
+        })
+    }
+}
+
+impl Module for Broadcast {
+    fn get_type(&self) -> &str {
+        Self::TYPE
+    }
+
+    fn get_common(&self) -> &ModuleCommon {
+        &self.common
+    }
+
+    fn exec(&mut self, pulse: &Pulse) -> Vec<Pulse> {
+        self.common.pulses_to_send(pulse.value)
+    }
+
+    fn is_initial_state(&self) -> bool {
+        true
+    }
+
+    fn reset(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flipflop() {
+        const IR: &str = "irrelevant";
+        let mut m = FlipFlop::build("%a -> b").unwrap();
+        assert_eq!(m.get_name(), "a");
+        assert_eq!(m.get_next_modules(), ["b"]);
+        assert_eq!(m.state, LOW);
+        assert_eq!(m.exec(&Pulse::new(IR, HIGH, "a")), Vec::new());
+        assert!(m.is_initial_state());
+        assert_eq!(
+            m.exec(&Pulse::new(IR, LOW, "a")),
+            [Pulse::new("a", HIGH, "b")]
+        );
+        assert_eq!(
+            m.exec(&Pulse::new(IR, LOW, "a")),
+            [Pulse::new("a", LOW, "b")]
+        );
+        assert!(m.is_initial_state());
+        assert_eq!(m.exec(&Pulse::new(IR, HIGH, "a")), Vec::new());
+        assert_eq!(
+            m.exec(&Pulse::new(IR, LOW, "a")),
+            [Pulse::new("a", HIGH, "b")]
+        );
+        m.reset();
+        assert!(m.is_initial_state());
+    }
+
+    #[test]
+    fn test_conjunction() {
+        let mut m = Conjunction::build("&inv -> b").unwrap();
+        assert_eq!(m.get_name(), "inv");
+        assert_eq!(m.get_next_modules(), ["b"]);
+        assert!(m.previous_pulse.is_empty());
+        assert!(m.is_initial_state());
+        assert_eq!(
+            m.exec(&Pulse::new("a", HIGH, "inv")),
+            [Pulse::new("inv", LOW, "b")]
+        );
+        assert_eq!(
+            m.exec(&Pulse::new("c", LOW, "inv")),
+            [Pulse::new("inv", HIGH, "b")]
+        );
+        assert!(!m.is_initial_state());
+
+        assert_eq!(
+            m.exec(&Pulse::new("a", LOW, "inv")),
+            [Pulse::new("inv", HIGH, "b")]
+        );
+        assert!(m.is_initial_state());
+    }
+
+    #[test]
+    fn test_broadcast() {
+        let mut m = Broadcast::build("broadcaster -> a, b, c").unwrap();
+        assert_eq!(m.get_name(), "broadcaster");
+        assert_eq!(m.get_next_modules(), ["a", "b", "c"]);
+        assert!(m.is_initial_state());
+        assert_eq!(
+            m.exec(&Pulse::new("irrelevant", HIGH, "broadcaster")),
+            [
+                Pulse::new("broadcaster", HIGH, "a"),
+                Pulse::new("broadcaster", HIGH, "b"),
+                Pulse::new("broadcaster", HIGH, "c")
+            ]
+        );
+        assert!(m.is_initial_state());
+        assert!(FlipFlop::build("%a").is_err());
+    }
+}
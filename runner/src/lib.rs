@@ -0,0 +1,249 @@
+//! Common code to run a day: reading the input, timing each phase, printing the answers.
+//!
+//! A day implements [`Solution`], and [`main!`] generates its `main` function:
+//!
+//! ```
+//! struct Day01;
+//!
+//! impl runner::Solution for Day01 {
+//!     type Input = Vec<i64>;
+//!     type Output1 = i64;
+//!     type Output2 = i64;
+//!
+//!     fn parse(input: &str) -> Self::Input {
+//!         input.lines().map(|l| l.parse().unwrap()).collect()
+//!     }
+//!
+//!     fn part1(input: &Self::Input) -> i64 {
+//!         input.iter().sum()
+//!     }
+//!
+//!     fn part2(input: &Self::Input) -> i64 {
+//!         input.iter().product()
+//!     }
+//! }
+//!
+//! runner::main!(Day01);
+//! ```
+//!
+//! The program then reads the input from the file given as argument, or from stdin,
+//! and `--part N` runs only one part.
+//! Answers are printed as `Part 1: ...` on stdout, timings on stderr.
+
+use std::fmt::{Debug, Display};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+
+pub trait Solution {
+    type Input;
+    type Output1: Display;
+    type Output2: Display;
+
+    fn parse(input: &str) -> Self::Input;
+    fn part1(input: &Self::Input) -> Self::Output1;
+    fn part2(input: &Self::Input) -> Self::Output2;
+}
+
+/// Generates the `main` function running the solution.
+#[macro_export]
+macro_rules! main {
+    ($solution:ty) => {
+        fn main() {
+            $crate::run::<$solution>();
+        }
+    };
+}
+
+/// Content of the `resources/input_test_N` file of the day, for tests.
+#[macro_export]
+macro_rules! test_input {
+    ($n:literal) => {
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/resources/input_test_",
+            stringify!($n)
+        ))
+    };
+}
+
+/// Runs the solution on the `resources/input_test_N` file of the day and compares the answers
+/// with the expected ones. A part with no expected answer isn't run.
+///
+/// # Panics
+///
+/// If the file can't be read or an answer is wrong.
+pub fn check<S: Solution>(n: u32, expected1: Option<S::Output1>, expected2: Option<S::Output2>)
+where
+    S::Output1: PartialEq + Debug,
+    S::Output2: PartialEq + Debug,
+{
+    // Tests are run from the day directory, which Cargo also gives in this variable.
+    let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let path = Path::new(&dir).join(format!("resources/input_test_{n}"));
+    let input = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {e}", path.display()));
+
+    let parsed = S::parse(&input);
+    if let Some(expected) = expected1 {
+        assert_eq!(S::part1(&parsed), expected, "Part 1 of input_test_{n}");
+    }
+    if let Some(expected) = expected2 {
+        assert_eq!(S::part2(&parsed), expected, "Part 2 of input_test_{n}");
+    }
+}
+
+const USAGE: &str = "Usage: [--part 1|2] [INPUT_FILE]
+Reads the input from stdin if no file is specified.";
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    part: Option<u8>,
+    file: Option<PathBuf>,
+}
+
+fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("Invalid part {s}")),
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--part" || arg == "-p" {
+            let part = args.next().ok_or("Missing part")?;
+            options.part = Some(parse_part(&part)?);
+        } else if let Some(part) = arg.strip_prefix("--part=") {
+            options.part = Some(parse_part(part)?);
+        } else if arg == "--help" || arg == "-h" {
+            return Err(USAGE.to_string());
+        } else if arg.starts_with('-') {
+            return Err(format!("Unknown option {arg}\n{USAGE}"));
+        } else if options.file.is_some() {
+            return Err(format!("Only one input file can be specified\n{USAGE}"));
+        } else {
+            options.file = Some(PathBuf::from(arg));
+        }
+    }
+    Ok(options)
+}
+
+fn read_input(options: &Options) -> io::Result<String> {
+    if let Some(file) = &options.file {
+        fs::read_to_string(file)
+    } else {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Runs the solution with the command line arguments.
+pub fn run<S: Solution>() {
+    let options = parse_args(std::env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(2);
+    });
+    let input = read_input(&options).unwrap_or_else(|e| {
+        eprintln!("Failed to read input: {e}");
+        process::exit(2);
+    });
+
+    let (parsed, duration) = timed(|| S::parse(&input));
+    eprintln!("Parsing time: {duration:.2?}");
+
+    if options.part.is_none_or(|p| p == 1) {
+        let (answer, duration) = timed(|| S::part1(&parsed));
+        println!("Part 1: {answer}");
+        eprintln!("Part 1 time: {duration:.2?}");
+    }
+    if options.part.is_none_or(|p| p == 2) {
+        let (answer, duration) = timed(|| S::part2(&parsed));
+        println!("Part 2: {answer}");
+        eprintln!("Part 2 time: {duration:.2?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sums;
+
+    impl Solution for Sums {
+        type Input = Vec<Vec<u32>>;
+        type Output1 = u32;
+        type Output2 = String;
+
+        fn parse(input: &str) -> Self::Input {
+            input
+                .lines()
+                .map(|l| l.split(' ').map(|n| n.parse().unwrap()).collect())
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> u32 {
+            input.iter().flatten().sum()
+        }
+
+        fn part2(input: &Self::Input) -> String {
+            input
+                .iter()
+                .map(|l| l.iter().sum::<u32>().to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+    }
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(args("")), Ok(Options::default()));
+        assert_eq!(
+            parse_args(args("--part 2 resources/input")),
+            Ok(Options {
+                part: Some(2),
+                file: Some(PathBuf::from("resources/input")),
+            })
+        );
+        assert_eq!(parse_args(args("--part=1")).unwrap().part, Some(1));
+        assert!(parse_args(args("--part 3")).is_err());
+        assert!(parse_args(args("--part")).is_err());
+        assert!(parse_args(args("a b")).is_err());
+        assert!(parse_args(args("--verbose")).is_err());
+    }
+
+    #[test]
+    fn test_test_input() {
+        let input = Sums::parse(test_input!(1));
+        assert_eq!(Sums::part1(&input), 21);
+        assert_eq!(Sums::part2(&input), "6,15");
+    }
+
+    #[test]
+    fn test_check() {
+        check::<Sums>(1, Some(21), Some("6,15".to_string()));
+        check::<Sums>(1, None, Some("6,15".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Part 1 of input_test_1")]
+    fn test_check_wrong_answer() {
+        check::<Sums>(1, Some(20), None);
+    }
+}
@@ -0,0 +1,72 @@
+use std::io::{self, Read};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Grid {
+    values: Vec<char>,
+    rows: usize,
+    cols: usize,
+}
+
+impl Grid {
+    fn build(input: &str) -> Self {
+        let mut rows = 0;
+        let values: Vec<_> = input
+            .lines()
+            .flat_map(|l| {
+                rows += 1;
+                l.chars().collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(values.len() % rows, 0);
+        let cols = values.len() / rows;
+        Self { values, rows, cols }
+    }
+
+    fn pos(&self, row: usize, col: usize) -> usize {
+        row * self.cols + col
+    }
+
+    fn print(&self) {
+        for row in 0..self.rows {
+            for p in row * self.cols..(row + 1) * self.cols {
+                print!("{}", self.values[p]);
+            }
+            println!();
+        }
+    }
+}
+
+fn part1(grid: &Grid) -> i64 {
+    0
+}
+
+fn part2(grid: &Grid) -> i64 {
+    0
+}
+
+fn main() {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input).unwrap();
+    let grid = Grid::build(&input);
+    grid.print();
+
+    println!("Part 1: {}", part1(&grid));
+    println!("Part 2: {}", part2(&grid));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT_TEST: &str = include_str!("../resources/input_test_1");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&Grid::build(INPUT_TEST)), 0);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&Grid::build(INPUT_TEST)), 0);
+    }
+}
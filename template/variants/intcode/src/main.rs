@@ -0,0 +1,22 @@
+use std::io::{self, Read};
+
+use intcode::IntcodeComputer;
+
+fn part1(computer: &IntcodeComputer) -> i64 {
+    let mut computer = computer.clone();
+    computer.exec();
+    0
+}
+
+fn part2(computer: &IntcodeComputer) -> i64 {
+    0
+}
+
+fn main() {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input).unwrap();
+    let computer = IntcodeComputer::build(&input);
+
+    println!("Part 1: {}", part1(&computer));
+    println!("Part 2: {}", part2(&computer));
+}
@@ -0,0 +1,38 @@
+use runner::Solution;
+
+struct Day;
+
+impl Solution for Day {
+    type Input = String;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Self::Input {
+        input.to_string()
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        0
+    }
+
+    fn part2(input: &Self::Input) -> i64 {
+        0
+    }
+}
+
+runner::main!(Day);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        runner::check::<Day>(1, Some(0), None);
+    }
+
+    #[test]
+    fn test_part2() {
+        runner::check::<Day>(1, None, Some(0));
+    }
+}
@@ -0,0 +1,44 @@
+use std::io::{self, Read};
+
+use virtual_cpu::instruction::{execute_all, Instruction};
+use virtual_cpu::registers::Registers;
+
+fn build(input: &str) -> Vec<Instruction> {
+    Instruction::build_list(input)
+}
+
+fn part1(instructions: &[Instruction]) -> i64 {
+    let mut regs = Registers::new();
+    execute_all(instructions, &mut regs);
+    regs.get('a')
+}
+
+fn part2(instructions: &[Instruction]) -> i64 {
+    0
+}
+
+fn main() {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input).unwrap();
+    let instructions = build(&input);
+
+    println!("Part 1: {}", part1(&instructions));
+    println!("Part 2: {}", part2(&instructions));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT_TEST: &str = include_str!("../resources/input_test_1");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&build(INPUT_TEST)), 0);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&build(INPUT_TEST)), 0);
+    }
+}
@@ -0,0 +1,188 @@
+//! Runs all days of all workspaces on their input, and compares the answers with the registry.
+
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::time::Duration;
+
+use clap::Parser;
+
+use answers::output::parse_output;
+use answers::puzzle::Event;
+use answers::registry::{self, Change, Registry};
+use answers::run::{run_with_timeout, RunResult};
+use answers::workspace::{self, Day};
+
+/// Checks that all days still give the recorded answers
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Registry file, defaults to answers.tsv at the top of the repository
+    #[arg(long)]
+    registry: Option<PathBuf>,
+
+    /// Repository root, defaults to the one containing this tool
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// Only these events
+    #[arg(long)]
+    event: Vec<Event>,
+
+    /// Only these years
+    #[arg(long)]
+    year: Vec<u16>,
+
+    /// Only these days
+    #[arg(long)]
+    day: Vec<u8>,
+
+    /// Maximum time for each day, in seconds
+    #[arg(long, default_value_t = 10)]
+    timeout: u64,
+
+    /// Don't build the days before
+    #[arg(long)]
+    no_build: bool,
+}
+
+impl Args {
+    fn selected(&self, day: &Day) -> bool {
+        (self.event.is_empty() || self.event.contains(&day.id.event))
+            && (self.year.is_empty() || day.id.year.is_some_and(|y| self.year.contains(&y)))
+            && (self.day.is_empty() || day.id.day.is_some_and(|d| self.day.contains(&d)))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Summary {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+fn binary_name(binary: &Path) -> String {
+    binary.file_name().unwrap().to_string_lossy().to_string()
+}
+
+fn build(workspace: &Path, days: &[Day]) -> bool {
+    let mut cmd = Command::new("cargo");
+    cmd.arg("build").arg("--release").current_dir(workspace);
+    for day in days {
+        cmd.arg("-p").arg(&day.package);
+        for extra in &day.extra_binaries {
+            cmd.arg("-p").arg(binary_name(extra));
+        }
+    }
+    cmd.status().is_ok_and(|s| s.success())
+}
+
+// Checks the answers given by one binary of the day, named `name` in the messages.
+fn check_binary(
+    day: &Day,
+    binary: &Path,
+    name: &str,
+    registry: &Registry,
+    timeout: Duration,
+    summary: &mut Summary,
+) {
+    let (output, duration) = match run_with_timeout(binary, &day.input(), timeout) {
+        Ok(RunResult::Success { output, duration }) => (output, duration),
+        Ok(RunResult::Timeout) => {
+            println!("❌ {}: timeout after {:?}", name, timeout);
+            summary.failed += 1;
+            return;
+        }
+        Ok(RunResult::Failed { stderr }) => {
+            println!("❌ {}: failed", name);
+            eprint!("{}", stderr);
+            summary.failed += 1;
+            return;
+        }
+        Err(e) => {
+            println!("❌ {}: cannot run {}: {}", name, binary.display(), e);
+            summary.failed += 1;
+            return;
+        }
+    };
+
+    let answers = parse_output(&output);
+    if answers.is_empty() {
+        println!("❔ {}: no answer in output", name);
+        summary.missing += 1;
+    }
+    for (part, answer) in answers {
+        match registry.compare(&day.id, part, &answer) {
+            Change::Same => {
+                println!("✅ {}, part {} ({:.1?})", name, part, duration);
+                summary.passed += 1;
+            }
+            Change::New => {
+                println!("❔ {}, part {}: no answer recorded", name, part);
+                summary.missing += 1;
+            }
+            Change::Changed(expected) => {
+                println!("❌ {}, part {}", name, part);
+                println!("Incorrect result is {}, expected {}", answer, expected);
+                summary.failed += 1;
+            }
+        }
+    }
+}
+
+// Checks the main binary of the day, and its extra ones against the same answers.
+fn check_day(day: &Day, registry: &Registry, timeout: Duration, summary: &mut Summary) {
+    if !day.input().exists() {
+        println!("❔ {}: no input", day.id);
+        summary.missing += 1;
+        return;
+    }
+    let name = day.id.to_string();
+    check_binary(day, &day.binary, &name, registry, timeout, summary);
+    for extra in &day.extra_binaries {
+        let name = format!("{} ({})", day.id, binary_name(extra));
+        check_binary(day, extra, &name, registry, timeout, summary);
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    let registry_path = args.registry.clone().unwrap_or_else(registry::default_path);
+    let registry = Registry::load(&registry_path).unwrap_or_else(|e| {
+        eprintln!("Failed to load {}: {}", registry_path.display(), e);
+        process::exit(2);
+    });
+    let root = args.root.clone().unwrap_or_else(workspace::default_root);
+    let timeout = Duration::from_secs(args.timeout);
+
+    let mut summary = Summary::default();
+    for ws in workspace::find_workspaces(&root).unwrap() {
+        let days: Vec<Day> = match workspace::workspace_days(&ws) {
+            Ok(days) => days.into_iter().filter(|d| args.selected(d)).collect(),
+            Err(e) => {
+                println!("❌ {}: {}", ws.display(), e);
+                summary.failed += 1;
+                continue;
+            }
+        };
+        if days.is_empty() {
+            continue;
+        }
+        if !args.no_build && !build(&ws, &days) {
+            println!("❌ {}: build failed", ws.display());
+            summary.failed += days.len();
+            continue;
+        }
+        for day in &days {
+            check_day(day, &registry, timeout, &mut summary);
+        }
+    }
+
+    println!();
+    println!(
+        "{} passed, {} failed, {} without answer",
+        summary.passed, summary.failed, summary.missing
+    );
+    if summary.failed > 0 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,136 @@
+//! Conversion from and to the previous answer formats:
+//! - The file where `record_answer.sh` appended the directory and the output of each day.
+//! - The `virtual_cpu` answers list, with `dayYYYY_DD_P answer` lines.
+//! - The `partN.answer` files used by the 2019 Intcode tests.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::output::parse_output;
+use crate::puzzle::{Event, PuzzleId};
+use crate::registry::Registry;
+
+/// Parses the file built by appending the current directory followed by the day output.
+pub fn parse_appended_outputs(content: &str) -> Vec<(PuzzleId, u8, String)> {
+    let mut answers = Vec::new();
+    let mut current: Option<(PuzzleId, String)> = None;
+    let mut flush = |current: Option<(PuzzleId, String)>| {
+        if let Some((id, output)) = current {
+            answers.extend(
+                parse_output(&output)
+                    .into_iter()
+                    .map(|(part, answer)| (id, part, answer)),
+            );
+        }
+    };
+    for line in content.lines() {
+        let id = line
+            .starts_with('/')
+            .then(|| PuzzleId::from_path(Path::new(line)))
+            .flatten();
+        if let Some(id) = id {
+            flush(current.replace((id, String::new())));
+        } else if let Some((_, output)) = current.as_mut() {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    flush(current);
+    answers
+}
+
+/// Parses the `virtual_cpu` answers list.
+pub fn parse_virtual_cpu(content: &str) -> Vec<(PuzzleId, u8, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (name, answer) = line.split_once(' ')?;
+            let mut parts = name.strip_prefix("day")?.split('_');
+            let year = parts.next()?.parse().ok()?;
+            let day = parts.next()?.parse().ok()?;
+            let part = parts.next()?.parse().ok()?;
+            Some((
+                PuzzleId::advent_of_code(year, day),
+                part,
+                answer.to_string(),
+            ))
+        })
+        .collect()
+}
+
+/// Formats the Advent of Code answers as a `virtual_cpu` answers list.
+/// Multi-line answers are not supported by that format and are skipped.
+pub fn format_virtual_cpu(registry: &Registry) -> String {
+    registry
+        .iter()
+        .filter(|(id, _, answer)| id.event == Event::AdventOfCode && !answer.contains('\n'))
+        .filter_map(|(id, part, answer)| {
+            Some(format!(
+                "day{}_{:0>2}_{} {}\n",
+                id.year?, id.day?, part, answer
+            ))
+        })
+        .collect()
+}
+
+/// Writes the `partN.answer` files of the 2019 days found in the year directory.
+/// Returns the number of files written.
+pub fn write_intcode_answers(registry: &Registry, year_dir: &Path) -> io::Result<usize> {
+    let mut count = 0;
+    for (id, part, answer) in registry.iter() {
+        if id.event != Event::AdventOfCode || id.year != Some(2019) {
+            continue;
+        }
+        let Some(day) = id.day else {
+            continue;
+        };
+        let resources = year_dir.join(format!("day{:0>2}", day)).join("resources");
+        if resources.is_dir() {
+            fs::write(resources.join(format!("part{}.answer", part)), answer)?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_appended_outputs() {
+        let content = "/home/me/aoc/2023/day01
+Part 1: 54644
+Part 2: 53348
+/home/me/aoc/2023/day02
+\"some debug\"
+Part 1: 2679
+";
+        assert_eq!(
+            parse_appended_outputs(content),
+            [
+                (PuzzleId::advent_of_code(2023, 1), 1, "54644".to_string()),
+                (PuzzleId::advent_of_code(2023, 1), 2, "53348".to_string()),
+                (PuzzleId::advent_of_code(2023, 2), 1, "2679".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_virtual_cpu() {
+        let content = "day2016_12_1 318083\nday2017_23_2 907\n";
+        let answers = parse_virtual_cpu(content);
+        assert_eq!(
+            answers[1],
+            (PuzzleId::advent_of_code(2017, 23), 2, "907".to_string())
+        );
+
+        let mut registry = Registry::default();
+        for (id, part, answer) in &answers {
+            registry.record(id, *part, answer);
+        }
+        registry.record(&PuzzleId::advent_of_code(2016, 8), 2, "#\n#");
+        assert_eq!(format_virtual_cpu(&registry), content);
+    }
+}
@@ -0,0 +1,11 @@
+//! Registry of the puzzle answers, for all years and events.
+//!
+//! Answers are recorded from the output of the days, see [`output::parse_output`],
+//! so that they can be checked after any change.
+
+pub mod legacy;
+pub mod output;
+pub mod puzzle;
+pub mod registry;
+pub mod run;
+pub mod workspace;
@@ -0,0 +1,215 @@
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+
+use clap::{Args, Parser, Subcommand};
+
+use answers::legacy;
+use answers::output::parse_output;
+use answers::puzzle::{Event, PuzzleId};
+use answers::registry::{self, Change, Registry};
+
+/// Registry of the puzzle answers
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Registry file, defaults to answers.tsv at the top of the repository
+    #[arg(long)]
+    registry: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Records the answers from the day output read on stdin
+    Record {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+        /// Replace answers that changed
+        #[arg(long)]
+        force: bool,
+    },
+    /// Checks the day output read on stdin against the recorded answers
+    Check {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+    },
+    /// Prints the recorded answers
+    Get {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+        #[arg(long)]
+        part: Option<u8>,
+    },
+    /// Imports the file built by the old record_answer.sh script
+    ImportOutputs { file: PathBuf },
+    /// Imports a virtual_cpu answers list
+    ImportVirtualCpu { file: PathBuf },
+    /// Writes the virtual_cpu answers list
+    ExportVirtualCpu { file: PathBuf },
+    /// Writes the partN.answer files of the 2019 days
+    ExportIntcode { year_dir: PathBuf },
+}
+
+/// The puzzle is found from the directory, unless the year or the day is specified.
+#[derive(Args, Debug)]
+struct PuzzleArgs {
+    /// Day directory, defaults to the current one
+    #[arg(long)]
+    dir: Option<PathBuf>,
+    #[arg(long, default_value = "aoc")]
+    event: Event,
+    #[arg(long)]
+    year: Option<u16>,
+    #[arg(long)]
+    day: Option<u8>,
+}
+
+impl PuzzleArgs {
+    fn puzzle_id(&self) -> PuzzleId {
+        if self.year.is_some() || self.day.is_some() {
+            return PuzzleId::new(self.event, self.year, self.day);
+        }
+        let dir = self
+            .dir
+            .clone()
+            .unwrap_or_else(|| env::current_dir().unwrap());
+        let dir = fs::canonicalize(&dir).unwrap_or(dir);
+        PuzzleId::from_path(&dir).unwrap_or_else(|| {
+            eprintln!("Cannot find the puzzle from {}", dir.display());
+            process::exit(2);
+        })
+    }
+}
+
+fn read_stdin() -> String {
+    let mut output = String::new();
+    io::stdin().read_to_string(&mut output).unwrap();
+    output
+}
+
+fn save(registry: &Registry, path: &Path) {
+    registry.save(path).unwrap_or_else(|e| {
+        eprintln!("Failed to save {}: {}", path.display(), e);
+        process::exit(2);
+    });
+}
+
+// Returns false if some answers were changed but not recorded.
+fn record(registry: &mut Registry, id: &PuzzleId, output: &str, force: bool) -> bool {
+    let answers = parse_output(output);
+    if answers.is_empty() {
+        eprintln!("No answer found in output");
+    }
+    let mut ok = true;
+    for (part, answer) in answers {
+        match registry.compare(id, part, &answer) {
+            Change::New => println!("{} part {}: new answer {}", id, part, answer),
+            Change::Same => println!("{} part {}: same answer", id, part),
+            Change::Changed(previous) => {
+                println!(
+                    "{} part {}: answer changed from {} to {}{}",
+                    id,
+                    part,
+                    previous,
+                    answer,
+                    if force { "" } else { ", not recorded" }
+                );
+                if !force {
+                    ok = false;
+                    continue;
+                }
+            }
+        }
+        registry.record(id, part, &answer);
+    }
+    ok
+}
+
+// Returns true if all answers are correct.
+fn check(registry: &Registry, id: &PuzzleId, output: &str) -> bool {
+    let mut ok = true;
+    for (part, answer) in parse_output(output) {
+        match registry.compare(id, part, &answer) {
+            Change::Same => println!("✅ {}, part {}", id, part),
+            Change::New => println!("❔ {}, part {}: no answer recorded", id, part),
+            Change::Changed(expected) => {
+                ok = false;
+                println!("❌ {}, part {}", id, part);
+                println!("Incorrect result is {}, expected {}", answer, expected);
+            }
+        }
+    }
+    ok
+}
+
+fn import(registry: &mut Registry, answers: Vec<(PuzzleId, u8, String)>) {
+    let mut changed = 0;
+    let count = answers.len();
+    for (id, part, answer) in answers {
+        if let Change::Changed(previous) = registry.record(&id, part, &answer) {
+            println!(
+                "{} part {}: answer changed from {} to {}",
+                id, part, previous, answer
+            );
+            changed += 1;
+        }
+    }
+    println!("Imported {} answers, {} changed", count, changed);
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let path = cli.registry.unwrap_or_else(registry::default_path);
+    let mut registry = Registry::load(&path).unwrap_or_else(|e| {
+        eprintln!("Failed to load {}: {}", path.display(), e);
+        process::exit(2);
+    });
+
+    let ok = match cli.command {
+        Commands::Record { puzzle, force } => {
+            let ok = record(&mut registry, &puzzle.puzzle_id(), &read_stdin(), force);
+            save(&registry, &path);
+            ok
+        }
+        Commands::Check { puzzle } => check(&registry, &puzzle.puzzle_id(), &read_stdin()),
+        Commands::Get { puzzle, part } => {
+            let id = puzzle.puzzle_id();
+            let answers: Vec<_> = registry
+                .get_all(&id)
+                .into_iter()
+                .filter(|(p, _)| part.is_none_or(|part| part == *p))
+                .collect();
+            for (p, answer) in &answers {
+                println!("Part {}: {}", p, answer);
+            }
+            !answers.is_empty()
+        }
+        Commands::ImportOutputs { file } => {
+            let content = fs::read_to_string(file).unwrap();
+            import(&mut registry, legacy::parse_appended_outputs(&content));
+            save(&registry, &path);
+            true
+        }
+        Commands::ImportVirtualCpu { file } => {
+            let content = fs::read_to_string(file).unwrap();
+            import(&mut registry, legacy::parse_virtual_cpu(&content));
+            save(&registry, &path);
+            true
+        }
+        Commands::ExportVirtualCpu { file } => {
+            fs::write(file, legacy::format_virtual_cpu(&registry)).unwrap();
+            true
+        }
+        Commands::ExportIntcode { year_dir } => {
+            let count = legacy::write_intcode_answers(&registry, &year_dir).unwrap();
+            println!("Wrote {} answer files", count);
+            true
+        }
+    };
+    if !ok {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,62 @@
+//! Parsing of the answers printed by the days.
+
+/// Extracts the answers from the output of a day, aka the `Part 1: ...` and `Part 2: ...` lines.
+///
+/// When nothing follows the colon, the answer is on the next lines, until the next part
+/// or the end of the output. This is used for answers drawn as ASCII art.
+pub fn parse_output(output: &str) -> Vec<(u8, String)> {
+    let mut answers: Vec<(u8, String)> = Vec::new();
+    let mut multiline: Option<(u8, Vec<&str>)> = None;
+    for line in output.lines() {
+        let part = line
+            .strip_prefix("Part ")
+            .and_then(|l| l.split_once(':'))
+            .and_then(|(nb, rest)| Some((nb.parse::<u8>().ok()?, rest.trim())));
+        match part {
+            Some((nb, rest)) => {
+                if let Some((m_nb, lines)) = multiline.take() {
+                    answers.push((m_nb, lines.join("\n").trim_end().to_string()));
+                }
+                if rest.is_empty() {
+                    multiline = Some((nb, Vec::new()));
+                } else {
+                    answers.push((nb, rest.to_string()));
+                }
+            }
+            None => {
+                if let Some((_, lines)) = multiline.as_mut() {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+    if let Some((nb, lines)) = multiline {
+        answers.push((nb, lines.join("\n").trim_end().to_string()));
+    }
+    answers.retain(|(_, answer)| !answer.is_empty());
+    answers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_output() {
+        let output = "Some debug\nPart 1: 1234\nPart 2: abc def\n";
+        assert_eq!(
+            parse_output(output),
+            [(1, "1234".to_string()), (2, "abc def".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_parse_output_multiline() {
+        let output = "Part 1: 12\nPart 2:\n#..#\n####\n#..#\n\n";
+        assert_eq!(
+            parse_output(output),
+            [(1, "12".to_string()), (2, "#..#\n####\n#..#".to_string())]
+        );
+        assert_eq!(parse_output("Part 1:\n"), []);
+    }
+}
@@ -0,0 +1,181 @@
+//! Identification of a puzzle: which event, year and day.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Event {
+    AdventOfCode,
+    I18nPuzzles,
+    Codyssi,
+    PiCodingQuest,
+}
+
+impl Event {
+    pub const ALL: [Event; 4] = [
+        Event::AdventOfCode,
+        Event::I18nPuzzles,
+        Event::Codyssi,
+        Event::PiCodingQuest,
+    ];
+
+    /// Name used in the registry, also the directory name for the clones.
+    pub fn name(self) -> &'static str {
+        match self {
+            Event::AdventOfCode => "aoc",
+            Event::I18nPuzzles => "i18n-puzzles",
+            Event::Codyssi => "codyssi",
+            Event::PiCodingQuest => "pi-coding-quest",
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Event {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Event::ALL
+            .into_iter()
+            .find(|e| e.name() == s)
+            .ok_or_else(|| format!("Unknown event {}", s))
+    }
+}
+
+/// A puzzle. Depending on the event, the year or the day may not apply:
+/// - Advent of Code and Codyssi have both.
+/// - i18n puzzles only have a puzzle number, stored as day.
+/// - Pi Coding Quest has one puzzle per year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub event: Event,
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+}
+
+// Parses a number made only of digits, nothing else.
+fn parse_digits<T: FromStr>(s: &str) -> Option<T> {
+    if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+impl PuzzleId {
+    pub fn new(event: Event, year: Option<u16>, day: Option<u8>) -> Self {
+        Self { event, year, day }
+    }
+
+    pub fn advent_of_code(year: u16, day: u8) -> Self {
+        Self::new(Event::AdventOfCode, Some(year), Some(day))
+    }
+
+    /// Finds the puzzle from the directory of its crate, like `2024/day05`, `i18n-puzzles/puzzle03`,
+    /// `codyssi/puzzle_2025_03` or `pi-coding-quest/year2024`.
+    /// Sub-directories of the crate are accepted too.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let components: Vec<String> = path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+        components.windows(2).rev().find_map(|w| {
+            let (parent, dir) = (w[0].as_str(), w[1].as_str());
+            match parent {
+                "i18n-puzzles" => {
+                    let day = parse_digits(dir.strip_prefix("puzzle")?)?;
+                    Some(Self::new(Event::I18nPuzzles, None, Some(day)))
+                }
+                "codyssi" => {
+                    let (year, day) = dir.strip_prefix("puzzle_")?.split_once('_')?;
+                    Some(Self::new(
+                        Event::Codyssi,
+                        Some(parse_digits(year)?),
+                        Some(parse_digits(day)?),
+                    ))
+                }
+                "pi-coding-quest" => {
+                    let year = parse_digits(dir.strip_prefix("year")?)?;
+                    Some(Self::new(Event::PiCodingQuest, Some(year), None))
+                }
+                _ => {
+                    let year = parse_digits(parent)?;
+                    let day = parse_digits(dir.strip_prefix("day")?)?;
+                    Some(Self::advent_of_code(year, day))
+                }
+            }
+        })
+    }
+}
+
+impl fmt::Display for PuzzleId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.event {
+            Event::AdventOfCode => {}
+            _ => write!(f, "{} ", self.event)?,
+        }
+        if let Some(year) = self.year {
+            write!(f, "{}", year)?;
+            if self.day.is_some() {
+                write!(f, " ")?;
+            }
+        }
+        if let Some(day) = self.day {
+            match self.event {
+                Event::AdventOfCode => write!(f, "day {}", day)?,
+                _ => write!(f, "puzzle {}", day)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(
+            PuzzleId::from_path(Path::new("/home/me/aoc/2024/day05")),
+            Some(PuzzleId::advent_of_code(2024, 5))
+        );
+        assert_eq!(
+            PuzzleId::from_path(Path::new("/home/me/aoc/2024/day05/src")),
+            Some(PuzzleId::advent_of_code(2024, 5))
+        );
+        assert_eq!(
+            PuzzleId::from_path(Path::new("i18n-puzzles/puzzle03")),
+            Some(PuzzleId::new(Event::I18nPuzzles, None, Some(3)))
+        );
+        assert_eq!(
+            PuzzleId::from_path(Path::new("codyssi/puzzle_2025_12")),
+            Some(PuzzleId::new(Event::Codyssi, Some(2025), Some(12)))
+        );
+        assert_eq!(
+            PuzzleId::from_path(Path::new("pi-coding-quest/year2024")),
+            Some(PuzzleId::new(Event::PiCodingQuest, Some(2024), None))
+        );
+        assert_eq!(PuzzleId::from_path(Path::new("2024/intcode")), None);
+        assert_eq!(PuzzleId::from_path(Path::new("tools/answers")), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(PuzzleId::advent_of_code(2015, 7).to_string(), "2015 day 7");
+        assert_eq!(
+            PuzzleId::new(Event::Codyssi, Some(2025), Some(3)).to_string(),
+            "codyssi 2025 puzzle 3"
+        );
+        assert_eq!(
+            PuzzleId::new(Event::PiCodingQuest, Some(2024), None).to_string(),
+            "pi-coding-quest 2024"
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! The answers registry, stored as a tab-separated text file:
+//!
+//! ```text
+//! # event   year    day     part    answer
+//! aoc       2024    5       1       4814
+//! codyssi   2025    3       2       abc
+//! ```
+//!
+//! `-` is used when the year or the day doesn't apply.
+//! In answers, new lines, tabs and backslashes are escaped.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::puzzle::PuzzleId;
+
+const HEADER: &str = "# event\tyear\tday\tpart\tanswer";
+
+/// Result of recording an answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    New,
+    Same,
+    /// The answer was different, the previous one is included.
+    Changed(String),
+}
+
+#[derive(Debug, Default)]
+pub struct Registry {
+    answers: BTreeMap<(PuzzleId, u8), String>,
+}
+
+/// Default location of the registry, at the top of the repository.
+/// Not in git, like the inputs.
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../answers.tsv")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+}
+
+fn unescape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                Some(other) => result.push(other),
+                None => result.push('\\'),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn format_option<T: ToString>(v: Option<T>) -> String {
+    v.map_or_else(|| "-".to_string(), |v| v.to_string())
+}
+
+fn parse_option<T: std::str::FromStr>(s: &str) -> Result<Option<T>, String> {
+    if s == "-" {
+        Ok(None)
+    } else {
+        s.parse()
+            .map(Some)
+            .map_err(|_| format!("Invalid number {}", s))
+    }
+}
+
+fn parse_line(line: &str) -> Result<(PuzzleId, u8, String), String> {
+    let fields: Vec<&str> = line.splitn(5, '\t').collect();
+    if fields.len() != 5 {
+        return Err(format!("Expected 5 fields: {}", line));
+    }
+    let id = PuzzleId::new(
+        fields[0].parse()?,
+        parse_option(fields[1])?,
+        parse_option(fields[2])?,
+    );
+    let part = fields[3]
+        .parse()
+        .map_err(|_| format!("Invalid part {}", fields[3]))?;
+    Ok((id, part, unescape(fields[4])))
+}
+
+impl Registry {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let answers = content
+            .lines()
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(|l| parse_line(l).map(|(id, part, answer)| ((id, part), answer)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { answers })
+    }
+
+    /// Loads the registry. A missing file is an empty registry.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                Self::parse(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, id: &PuzzleId, part: u8) -> Option<&str> {
+        self.answers.get(&(*id, part)).map(String::as_str)
+    }
+
+    /// All the answers of a puzzle, by part.
+    pub fn get_all(&self, id: &PuzzleId) -> Vec<(u8, &str)> {
+        self.answers
+            .range((*id, 0)..=(*id, u8::MAX))
+            .map(|((_, part), answer)| (*part, answer.as_str()))
+            .collect()
+    }
+
+    /// Compares the answer with the registered one, without changing anything.
+    pub fn compare(&self, id: &PuzzleId, part: u8, answer: &str) -> Change {
+        match self.get(id, part) {
+            None => Change::New,
+            Some(a) if a == answer => Change::Same,
+            Some(a) => Change::Changed(a.to_string()),
+        }
+    }
+
+    /// Records the answer, replacing the previous one.
+    pub fn record(&mut self, id: &PuzzleId, part: u8, answer: &str) -> Change {
+        let change = self.compare(id, part, answer);
+        self.answers.insert((*id, part), answer.to_string());
+        change
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&PuzzleId, u8, &str)> {
+        self.answers
+            .iter()
+            .map(|((id, part), answer)| (id, *part, answer.as_str()))
+    }
+}
+
+impl std::fmt::Display for Registry {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        for (id, part, answer) in self.iter() {
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}\t{}",
+                id.event,
+                format_option(id.year),
+                format_option(id.day),
+                part,
+                escape(answer)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::Event;
+
+    #[test]
+    fn test_record() {
+        let mut registry = Registry::default();
+        let id = PuzzleId::advent_of_code(2024, 5);
+        assert_eq!(registry.record(&id, 1, "4814"), Change::New);
+        assert_eq!(registry.record(&id, 1, "4814"), Change::Same);
+        assert_eq!(
+            registry.record(&id, 1, "4815"),
+            Change::Changed("4814".to_string())
+        );
+        assert_eq!(registry.get(&id, 1), Some("4815"));
+        assert_eq!(registry.get(&id, 2), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut registry = Registry::default();
+        let aoc = PuzzleId::advent_of_code(2016, 8);
+        let i18n = PuzzleId::new(Event::I18nPuzzles, None, Some(3));
+        registry.record(&aoc, 1, "110");
+        registry.record(&aoc, 2, "#..#\n####\t\\");
+        registry.record(&i18n, 1, "42");
+
+        let content = registry.to_string();
+        assert!(content.contains("aoc\t2016\t8\t2\t#..#\\n####\\t\\\\\n"));
+        assert!(content.contains("i18n-puzzles\t-\t3\t1\t42\n"));
+
+        let parsed = Registry::parse(&content).unwrap();
+        assert_eq!(parsed.get_all(&aoc), [(1, "110"), (2, "#..#\n####\t\\")]);
+        assert_eq!(parsed.get(&i18n, 1), Some("42"));
+        assert!(Registry::parse("aoc\t2016\t8").is_err());
+    }
+}
@@ -0,0 +1,61 @@
+//! Running a day binary on its input, with a time limit.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub enum RunResult {
+    Success { output: String, duration: Duration },
+    Failed { stderr: String },
+    Timeout,
+}
+
+// Reads the pipe in a thread, so that the child doesn't block when the pipe is full.
+fn read_in_thread<R: Read + Send + 'static>(mut pipe: R) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut content = String::new();
+        let _ = pipe.read_to_string(&mut content);
+        content
+    })
+}
+
+/// Runs the binary with the input file as stdin.
+/// The binary is killed if it takes more than the timeout.
+pub fn run_with_timeout(binary: &Path, input: &Path, timeout: Duration) -> io::Result<RunResult> {
+    let start = Instant::now();
+    let mut child = Command::new(binary)
+        .stdin(File::open(input)?)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = read_in_thread(child.stdout.take().unwrap());
+    let stderr = read_in_thread(child.stderr.take().unwrap());
+
+    // Polling more often at the beginning, for a precise duration of fast days.
+    let mut poll_interval = Duration::from_micros(20);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() > timeout {
+            child.kill()?;
+            child.wait()?;
+            return Ok(RunResult::Timeout);
+        }
+        thread::sleep(poll_interval);
+        poll_interval = (poll_interval * 2).min(Duration::from_millis(1));
+    };
+    let duration = start.elapsed();
+
+    let output = stdout.join().unwrap();
+    let stderr = stderr.join().unwrap();
+    if status.success() {
+        Ok(RunResult::Success { output, duration })
+    } else {
+        Ok(RunResult::Failed { stderr })
+    }
+}
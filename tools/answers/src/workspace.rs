@@ -0,0 +1,133 @@
+//! Discovery of the workspaces (years and Advent of Code clones) and of their days.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use toml::Table;
+
+use crate::puzzle::{Event, PuzzleId};
+
+/// A day crate, member of a workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Day {
+    pub id: PuzzleId,
+    pub dir: PathBuf,
+    /// Package name, which is also the binary name.
+    pub package: String,
+    /// Binary built in the workspace target directory.
+    pub binary: PathBuf,
+    /// Binaries of additional packages in sub-directories of the day, also workspace members,
+    /// like an alternative implementation of one part (`2023/day12/day12_part1`).
+    pub extra_binaries: Vec<PathBuf>,
+}
+
+impl Day {
+    pub fn input(&self) -> PathBuf {
+        self.dir.join("resources/input")
+    }
+}
+
+/// Default repository root, based on where this tool is.
+pub fn default_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+/// Finds the year and Advent of Code clones workspaces, sorted by name.
+pub fn find_workspaces(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut workspaces: Vec<PathBuf> = fs::read_dir(root)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            let is_year = name.len() == 4 && name.chars().all(|c| c.is_ascii_digit());
+            let is_event = Event::ALL.iter().any(|e| e.name() == name);
+            (is_year || is_event) && path.join("Cargo.toml").is_file()
+        })
+        .collect();
+    workspaces.sort();
+    Ok(workspaces)
+}
+
+fn read_toml(path: &Path) -> io::Result<Table> {
+    fs::read_to_string(path)?
+        .parse::<Table>()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn package_name(dir: &Path) -> io::Result<String> {
+    let manifest = read_toml(&dir.join("Cargo.toml"))?;
+    manifest
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+        .map(ToString::to_string)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "No package name"))
+}
+
+/// Lists the days of the workspace, in the order of its members.
+/// Members that aren't days, like the 2019 `intcode` library, are ignored.
+pub fn workspace_days(workspace: &Path) -> io::Result<Vec<Day>> {
+    let manifest = read_toml(&workspace.join("Cargo.toml"))?;
+    let members = manifest
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let workspace = fs::canonicalize(workspace)?;
+    let target = workspace.join("target/release");
+    let mut days: Vec<Day> = Vec::new();
+    for member in members.iter().filter_map(|m| m.as_str()) {
+        let dir = workspace.join(member);
+        let Some(id) = PuzzleId::from_path(&dir) else {
+            continue;
+        };
+        let package = package_name(&dir)?;
+        if let Some(day) = days.iter_mut().find(|d| dir.parent() == Some(&d.dir)) {
+            day.extra_binaries.push(target.join(&package));
+            continue;
+        }
+        days.push(Day {
+            id,
+            binary: target.join(&package),
+            dir,
+            package,
+            extra_binaries: Vec::new(),
+        });
+    }
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_workspaces() {
+        let workspaces = find_workspaces(&default_root()).unwrap();
+        let names: Vec<String> = workspaces
+            .iter()
+            .map(|w| w.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert!(names.contains(&"2015".to_string()));
+        assert!(names.contains(&"i18n-puzzles".to_string()));
+        assert!(!names.contains(&"tools".to_string()));
+    }
+
+    #[test]
+    fn test_workspace_days() {
+        let days = workspace_days(&default_root().join("2023")).unwrap();
+        assert_eq!(days[0].id, PuzzleId::advent_of_code(2023, 1));
+        let day12 = days.iter().find(|d| d.package == "day12").unwrap();
+        assert!(day12.binary.ends_with("2023/target/release/day12"));
+        assert_eq!(day12.extra_binaries.len(), 1);
+        assert!(day12.extra_binaries[0].ends_with("2023/target/release/day12_part1"));
+        assert_eq!(days.iter().filter(|d| d.id == day12.id).count(), 1);
+
+        // intcode isn't a day.
+        let days = workspace_days(&default_root().join("2019")).unwrap();
+        assert!(days.iter().all(|d| d.id.year == Some(2019)));
+    }
+}
@@ -0,0 +1,62 @@
+//! Local cache of puzzle statements and inputs, used when the aoc CLI can't be used.
+//!
+//! Files are organized by year and day:
+//!
+//! ```text
+//! <cache>/2024/day05/puzzle.md   (or puzzle.html)
+//! <cache>/2024/day05/input
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PUZZLE_FILES: [&str; 2] = ["puzzle.md", "puzzle.html"];
+const INPUT_FILE: &str = "input";
+
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    /// Cache in the specified directory. Relative paths are resolved now,
+    /// so the cache stays valid after changing the current directory.
+    pub fn new(dir: &Path) -> Self {
+        let dir = if dir.is_absolute() {
+            dir.to_path_buf()
+        } else {
+            std::env::current_dir().unwrap().join(dir)
+        };
+        Self { dir }
+    }
+
+    fn day_dir(&self, year: &str, day: u8) -> PathBuf {
+        self.dir.join(year).join(format!("day{:0>2}", day))
+    }
+
+    pub fn read_puzzle(&self, year: &str, day: u8) -> Option<String> {
+        let dir = self.day_dir(year, day);
+        PUZZLE_FILES
+            .iter()
+            .find_map(|f| fs::read_to_string(dir.join(f)).ok())
+    }
+
+    pub fn read_input(&self, year: &str, day: u8) -> Option<String> {
+        fs::read_to_string(self.day_dir(year, day).join(INPUT_FILE)).ok()
+    }
+
+    pub fn save_puzzle(&self, year: &str, day: u8, puzzle: &str) {
+        self.save(year, day, PUZZLE_FILES[0], puzzle);
+    }
+
+    pub fn save_input(&self, year: &str, day: u8, input: &str) {
+        self.save(year, day, INPUT_FILE, input);
+    }
+
+    // Failing to save in the cache isn't an error, it just means we won't be able to use it later.
+    fn save(&self, year: &str, day: u8, file: &str, content: &str) {
+        let dir = self.day_dir(year, day);
+        if let Err(e) = fs::create_dir_all(&dir).and_then(|()| fs::write(dir.join(file), content)) {
+            eprintln!("Couldn't save {} to cache: {}", file, e);
+        }
+    }
+}
@@ -1,33 +1,98 @@
 use std::fs::OpenOptions;
 use std::io::{self, Write};
-use std::path::Path;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output};
 use std::{env, fs, path};
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use regex::Regex;
 use toml::Table;
 
+mod cache;
+mod template;
+mod workspace;
+use cache::Cache;
+use template::Variant;
+use workspace::Event;
+
+const INPUT_FILE: &str = "resources/input";
+
 /// Starting a new Advent of Code day
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
     /// Day to create
-    day: u8,
+    #[arg(required = true)]
+    day: Option<u8>,
+
+    /// Directory with cached puzzles and inputs, used when the aoc CLI fails.
+    /// Defaults to the AOC_CACHE environment variable.
+    #[arg(long)]
+    cache: Option<PathBuf>,
+
+    /// Don't use the aoc CLI at all, only the cache
+    #[arg(long)]
+    offline: bool,
+
+    /// Puzzle title, instead of extracting it from the puzzle statement
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Template variant
+    #[arg(long, value_enum, default_value_t)]
+    template: Variant,
+
+    /// Template directory, defaults to the one in this repository
+    #[arg(long)]
+    template_dir: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Creates the workspace of a new year, from the top directory
+    InitYear { year: u16 },
+    /// Creates the workspace of an Advent of Code clone, from the top directory, without puzzles
+    InitEvent { event: Event },
 }
 
 fn main() {
     let args = Args::parse();
 
+    match args.command {
+        Some(Commands::InitYear { year }) => workspace::init_year(year),
+        Some(Commands::InitEvent { event }) => workspace::init_event(event),
+        None => new_day(&args),
+    }
+}
+
+fn new_day(args: &Args) {
     // Get all the data
     let year = get_year();
-    let day = get_day(&args);
+    let day = get_day(args);
     let day_dir = get_day_dir(day);
+    let cache = args
+        .cache
+        .clone()
+        .or_else(|| env::var_os("AOC_CACHE").map(PathBuf::from))
+        .map(|dir| Cache::new(&dir));
 
     println!("Starting year {}, day {}", year, day);
 
-    let puzzle_statement = read_puzzle(&year, day);
-    let title = extract_title(&puzzle_statement);
+    // Checking it before changing anything
+    if Path::new(&day_dir).exists() {
+        eprintln!("{} already exists", day_dir);
+        process::exit(1);
+    }
+
+    let title = args.title.clone().unwrap_or_else(|| {
+        let puzzle_statement = read_puzzle(&year, day, args.offline, cache.as_ref())
+            .expect("Puzzle not available, use --title to specify it");
+        extract_title(&puzzle_statement).expect("Failed to find title in puzzle")
+    });
     let url = build_url(&year, day);
     println!("Title {}; URL: {}", &title, &url);
 
@@ -36,17 +101,17 @@ fn main() {
     update_main_cargo(&day_dir);
 
     // Generate project and change directory
-    generate_template(&day_dir);
+    generate_template(args, &day_dir);
     change_dir(&day_dir);
 
     // Update day things
-    get_input(&year, day);
+    get_input(&year, day, args.offline, cache.as_ref());
     update_project_readme(day, &title, &url);
 }
 
 fn get_year() -> String {
     let path = std::env::current_dir().unwrap();
-    if let path::Component::Normal(y) = path.components().last().unwrap() {
+    if let path::Component::Normal(y) = path.components().next_back().unwrap() {
         let year = y.to_string_lossy();
         if year.contains(|c: char| !c.is_ascii_digit()) {
             panic!("Invalid year {}", year);
@@ -58,8 +123,9 @@ fn get_year() -> String {
 }
 
 fn get_day(args: &Args) -> u8 {
-    assert!(args.day >= 1 && args.day <= 25);
-    args.day
+    let day = args.day.unwrap();
+    assert!((1..=25).contains(&day));
+    day
 }
 
 fn get_day_dir(day: u8) -> String {
@@ -67,16 +133,15 @@ fn get_day_dir(day: u8) -> String {
     format!("day{:0>2}", day)
 }
 
-fn generate_template(day: &str) {
-    let output = Command::new("cargo")
-        .arg("generate")
-        .arg("aoc")
-        .arg("--name=".to_string() + day)
-        .output()
-        .expect("Failed to generate template");
-    io::stdout().write_all(&output.stdout).unwrap();
-    io::stderr().write_all(&output.stderr).unwrap();
-    assert!(output.status.success());
+fn generate_template(args: &Args, day: &str) {
+    let template_dir = args
+        .template_dir
+        .clone()
+        .unwrap_or_else(template::default_dir);
+    if let Err(e) = template::render(&template_dir, args.template, Path::new(day)) {
+        panic!("Failed to generate template: {}", e);
+    }
+    println!("Generated {} from {:?} template", day, args.template);
 }
 
 fn change_dir(day: &str) {
@@ -88,30 +153,61 @@ fn change_dir(day: &str) {
     );
 }
 
-fn get_input(year: &str, day: u8) {
-    const INPUT_FILE: &str = "resources/input";
-    let output = Command::new("aoc")
-        .arg("download")
-        .arg("--day")
-        .arg(day.to_string())
-        .arg("--year")
-        .arg(year)
-        .arg("--input-only")
-        .arg("--input-file")
-        .arg(INPUT_FILE)
-        .arg("--overwrite")
-        .output()
-        .expect("Failed to get input file");
-    io::stdout().write_all(&output.stdout).unwrap();
-    io::stderr().write_all(&output.stderr).unwrap();
-    assert!(output.status.success());
-
-    // On saving the file, a new line may be appended at the end,
-    // which can cause confusion on some tasks https://github.com/scarvalhojr/aoc-cli/issues/23
-    // So removing it here.
-    let mut input = fs::read_to_string(INPUT_FILE).unwrap();
-    trim_newline(&mut input);
-    fs::write(INPUT_FILE, input).expect("Failed to trim input file");
+// Runs the aoc CLI, returning its output if it succeeded.
+fn run_aoc(args: &[&str]) -> Option<Output> {
+    match Command::new("aoc").args(args).output() {
+        Ok(output) => {
+            io::stderr().write_all(&output.stderr).unwrap();
+            if output.status.success() {
+                Some(output)
+            } else {
+                eprintln!("aoc {} failed", args[0]);
+                None
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to run aoc: {}", e);
+            None
+        }
+    }
+}
+
+// Must be executed from day folder
+fn get_input(year: &str, day: u8, offline: bool, cache: Option<&Cache>) {
+    let day_str = day.to_string();
+    let downloaded = !offline
+        && run_aoc(&[
+            "download",
+            "--day",
+            &day_str,
+            "--year",
+            year,
+            "--input-only",
+            "--input-file",
+            INPUT_FILE,
+            "--overwrite",
+        ])
+        .inspect(|output| io::stdout().write_all(&output.stdout).unwrap())
+        .is_some();
+
+    let input = if downloaded {
+        // On saving the file, a new line may be appended at the end,
+        // which can cause confusion on some tasks https://github.com/scarvalhojr/aoc-cli/issues/23
+        // So removing it here.
+        let mut input = fs::read_to_string(INPUT_FILE).unwrap();
+        trim_newline(&mut input);
+        if let Some(cache) = cache {
+            cache.save_input(year, day, &input);
+        }
+        input
+    } else if let Some(input) = cache.and_then(|c| c.read_input(year, day)) {
+        println!("Using cached input");
+        input
+    } else {
+        eprintln!("Input not available, {} left empty", INPUT_FILE);
+        String::new()
+    };
+    fs::write(INPUT_FILE, input).expect("Failed to write input file");
 }
 
 fn trim_newline(s: &mut String) {
@@ -123,25 +219,35 @@ fn trim_newline(s: &mut String) {
     }
 }
 
-fn read_puzzle(year: &str, day: u8) -> String {
-    let output = Command::new("aoc")
-        .arg("read")
-        .arg("--day")
-        .arg(day.to_string())
-        .arg("--year")
-        .arg(year)
-        .output()
-        .expect("Failed to read puzzle");
-    io::stderr().write_all(&output.stderr).unwrap();
-    assert!(output.status.success());
-    String::from_utf8(output.stdout).unwrap()
+fn read_puzzle(year: &str, day: u8, offline: bool, cache: Option<&Cache>) -> Option<String> {
+    let day_str = day.to_string();
+    if !offline {
+        if let Some(output) = run_aoc(&["read", "--day", &day_str, "--year", year]) {
+            let puzzle = String::from_utf8(output.stdout).unwrap();
+            if let Some(cache) = cache {
+                cache.save_puzzle(year, day, &puzzle);
+            }
+            return Some(puzzle);
+        }
+    }
+    let puzzle = cache?.read_puzzle(year, day)?;
+    println!("Using cached puzzle");
+    Some(puzzle)
 }
 
-fn extract_title(puzzle: &str) -> String {
-    let re = Regex::new(r"--- Day (\d+): (.+) ---").unwrap();
-    let parts = re.captures(puzzle).unwrap();
-    assert_eq!(parts.len(), 3);
-    parts[2].to_string()
+// Works both with the Markdown of aoc read and with the HTML page.
+fn extract_title(puzzle: &str) -> Option<String> {
+    let re = Regex::new(r"--- Day (\d+): (.+?) ---").unwrap();
+    let parts = re.captures(puzzle)?;
+    Some(
+        parts[2]
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&#39;", "'")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&"),
+    )
 }
 
 fn build_url(year: &str, day: u8) -> String {
@@ -150,11 +256,7 @@ fn build_url(year: &str, day: u8) -> String {
 
 // Must be executed from year folder
 fn update_main_readme(day: u8, title: &str, day_dir: &str) {
-    let mut file = OpenOptions::new()
-        .write(true)
-        .append(true)
-        .open("README.md")
-        .unwrap();
+    let mut file = OpenOptions::new().append(true).open("README.md").unwrap();
     if let Err(e) = writeln!(
         file,
         "\n\n### Day {}: [{}]({}/README.md)",
@@ -180,3 +282,25 @@ fn update_project_readme(day: u8, title: &str, url: &str) {
     let new = data.replace("# \n", &format!("# Day {}: [{}]({})\n", day, title, url));
     fs::write("README.md", new).unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_title() {
+        assert_eq!(
+            extract_title("\\--- Day 5: Print Queue ---\n\nSatisfied with"),
+            Some("Print Queue".to_string())
+        );
+        assert_eq!(
+            extract_title("<article><h2>--- Day 11: Monkey in the Middle ---</h2><p>"),
+            Some("Monkey in the Middle".to_string())
+        );
+        assert_eq!(
+            extract_title("<h2>--- Day 4: Ceres &amp; Co ---</h2>"),
+            Some("Ceres & Co".to_string())
+        );
+        assert_eq!(extract_title("No title"), None);
+    }
+}
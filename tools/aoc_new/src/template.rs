@@ -0,0 +1,141 @@
+//! Rendering of the day template.
+//!
+//! The `template` folder is copied, with `{{project-name}}` replaced by the day name.
+//! Variants are in `template/variants/<name>`, with only the files that differ from the plain
+//! template, which replace the plain ones.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+
+const VARIANTS_DIR: &str = "variants";
+// Not part of the template itself.
+const IGNORED: [&str; 3] = ["target", "Cargo.lock", VARIANTS_DIR];
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum Variant {
+    /// Input as a string
+    #[default]
+    Plain,
+    /// Input as a grid of characters
+    Grid,
+    /// Using the 2019 Intcode computer
+    Intcode,
+    /// Using the virtual_cpu crate
+    VirtualCpu,
+    /// Implementing the Solution trait of the runner crate
+    Runner,
+}
+
+impl Variant {
+    fn dir(self) -> Option<&'static str> {
+        match self {
+            Variant::Plain => None,
+            Variant::Grid => Some("grid"),
+            Variant::Intcode => Some("intcode"),
+            Variant::VirtualCpu => Some("virtual_cpu"),
+            Variant::Runner => Some("runner"),
+        }
+    }
+}
+
+/// Default location of the template, based on where this tool is in the repository.
+pub fn default_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../template")
+}
+
+fn render_file(src: &Path, dest: &Path, project_name: &str) -> io::Result<()> {
+    let content = fs::read(src)?;
+    match String::from_utf8(content) {
+        Ok(text) => fs::write(dest, text.replace("{{project-name}}", project_name)),
+        Err(e) => fs::write(dest, e.into_bytes()),
+    }
+}
+
+fn render_dir(src: &Path, dest: &Path, project_name: &str, ignored: &[&str]) -> io::Result<()> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if ignored.iter().any(|i| name == *i) {
+            continue;
+        }
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            render_dir(&path, &dest.join(&name), project_name, &[])?;
+        } else {
+            render_file(&path, &dest.join(&name), project_name)?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates the `dest` directory from the template.
+/// Fails if `dest` already exists, to never overwrite a day.
+pub fn render(template_dir: &Path, variant: Variant, dest: &Path) -> io::Result<()> {
+    if dest.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", dest.display()),
+        ));
+    }
+    let project_name = dest.file_name().unwrap().to_string_lossy();
+    render_dir(template_dir, dest, &project_name, &IGNORED)?;
+    if let Some(variant_dir) = variant.dir() {
+        let variant_dir = template_dir.join(VARIANTS_DIR).join(variant_dir);
+        if !variant_dir.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No template variant in {}", variant_dir.display()),
+            ));
+        }
+        render_dir(&variant_dir, dest, &project_name, &[])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn render_in_temp(variant: Variant) -> PathBuf {
+        let dest = env::temp_dir()
+            .join(format!("aoc_new_test_{}_{:?}", std::process::id(), variant))
+            .join("day07");
+        render(&default_dir(), variant, &dest).unwrap();
+        dest
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let dest = render_in_temp(Variant::Plain);
+        let cargo = fs::read_to_string(dest.join("Cargo.toml")).unwrap();
+        assert!(cargo.contains("name = \"day07\""));
+        assert!(dest.join("resources/input_test_1").exists());
+        assert!(!dest.join(VARIANTS_DIR).exists());
+        assert!(!dest.join("Cargo.lock").exists());
+        // Never overwriting.
+        assert_eq!(
+            render(&default_dir(), Variant::Plain, &dest)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::AlreadyExists
+        );
+        fs::remove_dir_all(dest.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_render_variant() {
+        let dest = render_in_temp(Variant::VirtualCpu);
+        let cargo = fs::read_to_string(dest.join("Cargo.toml")).unwrap();
+        assert!(cargo.contains("name = \"day07\""));
+        assert!(cargo.contains("virtual_cpu = { path"));
+        let main = fs::read_to_string(dest.join("src/main.rs")).unwrap();
+        assert!(main.contains("use virtual_cpu::"));
+        assert!(dest.join("README.md").exists());
+        fs::remove_dir_all(dest.parent().unwrap()).unwrap();
+    }
+}
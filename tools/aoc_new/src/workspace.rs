@@ -0,0 +1,142 @@
+//! Bootstrapping of the workspaces holding the days: Advent of Code years and clone events.
+//!
+//! All workspaces share the same lints and package settings, only the description changes.
+//! Creation is idempotent: existing files are never overwritten.
+
+use std::fs;
+use std::path::Path;
+
+use clap::ValueEnum;
+
+const EDITION: &str = "2024";
+
+/// Advent of Code clones, each in its own workspace.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Event {
+    /// Internationalization Puzzles
+    I18nPuzzles,
+    /// Codyssi Puzzles
+    Codyssi,
+    /// Pi Coding Quest
+    PiCodingQuest,
+}
+
+impl Event {
+    fn dir(self) -> &'static str {
+        match self {
+            Event::I18nPuzzles => "i18n-puzzles",
+            Event::Codyssi => "codyssi",
+            Event::PiCodingQuest => "pi-coding-quest",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            // With a soft hyphen, as on the site.
+            Event::I18nPuzzles => "Internationali\u{ad}zation Puzzles",
+            Event::Codyssi => "Codyssi Puzzles",
+            Event::PiCodingQuest => "Pi Coding Quest",
+        }
+    }
+
+    fn url(self) -> &'static str {
+        match self {
+            Event::I18nPuzzles => "https://i18n-puzzles.com",
+            Event::Codyssi => "https://www.codyssi.com",
+            Event::PiCodingQuest => "https://ivanr3d.com/projects/pi/",
+        }
+    }
+}
+
+fn cargo_toml(description: &str) -> String {
+    format!(
+        r#"[workspace]
+members = []
+resolver = "2"
+
+[workspace.lints.clippy]
+pedantic = "deny"
+
+[workspace.lints.rust]
+unsafe_code = "forbid"
+
+[workspace.package]
+authors = ["Vincent Oberle"]
+description = "{}"
+edition = "{}"
+version = "0.1.0"
+"#,
+        description, EDITION
+    )
+}
+
+fn readme(name: &str, url: &str, with_days: bool) -> String {
+    let mut readme = format!(
+        "# {}\n\nMy Rust implementations of [{}]({}).\n",
+        name, name, url
+    );
+    if with_days {
+        readme += "\n## Days\n";
+    }
+    readme
+}
+
+// Writes the file only if it doesn't exist yet.
+fn create_file(path: &Path, content: &str) {
+    if path.exists() {
+        println!("{} already exists, leaving it as is", path.display());
+    } else {
+        fs::write(path, content).unwrap();
+        println!("Created {}", path.display());
+    }
+}
+
+fn init_workspace(dir: &Path, description: &str, readme: &str) {
+    fs::create_dir_all(dir).unwrap();
+    create_file(&dir.join("Cargo.toml"), &cargo_toml(description));
+    create_file(&dir.join("README.md"), readme);
+}
+
+/// Creates the workspace for an Advent of Code year, in the current directory.
+pub fn init_year(year: u16) {
+    let name = format!("Advent of Code {}", year);
+    let url = format!("https://adventofcode.com/{}", year);
+    init_workspace(
+        Path::new(&year.to_string()),
+        &name,
+        &readme(&name, &url, true),
+    );
+}
+
+/// Creates the workspace for an Advent of Code clone, in the current directory.
+/// Its members are then added by hand, days being created only in year workspaces.
+pub fn init_event(event: Event) {
+    init_workspace(
+        Path::new(event.dir()),
+        event.name(),
+        &readme(event.name(), event.url(), false),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toml::Table;
+
+    #[test]
+    fn test_cargo_toml() {
+        let values = cargo_toml("Advent of Code 2026").parse::<Table>().unwrap();
+        assert!(values["workspace"]["members"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            values["workspace"]["lints"]["clippy"]["pedantic"].as_str(),
+            Some("deny")
+        );
+        assert_eq!(
+            values["workspace"]["package"]["description"].as_str(),
+            Some("Advent of Code 2026")
+        );
+    }
+}
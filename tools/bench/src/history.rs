@@ -0,0 +1,150 @@
+//! History of the benchmark results, to compare a run with the previous one.
+//!
+//! Stored as a tab-separated file, one line per binary and run:
+//!
+//! ```text
+//! # time  event   year    day     binary  mean    stddev
+//! 1733000000      aoc     2023    12      day12   8.21    0.35
+//! ```
+//!
+//! Time is in seconds since the Unix epoch, mean and standard deviation in milliseconds.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use answers::puzzle::PuzzleId;
+
+const HEADER: &str = "# time\tevent\tyear\tday\tbinary\tmean\tstddev";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub time: u64,
+    pub id: PuzzleId,
+    pub binary: String,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+fn format_option<T: ToString>(v: Option<T>) -> String {
+    v.map_or_else(|| "-".to_string(), |v| v.to_string())
+}
+
+fn parse_option<T: std::str::FromStr>(s: &str) -> Option<Option<T>> {
+    if s == "-" {
+        Some(None)
+    } else {
+        s.parse().ok().map(Some)
+    }
+}
+
+impl Entry {
+    fn parse(line: &str) -> Option<Self> {
+        let f: Vec<&str> = line.split('\t').collect();
+        if f.len() != 7 {
+            return None;
+        }
+        Some(Self {
+            time: f[0].parse().ok()?,
+            id: PuzzleId::new(f[1].parse().ok()?, parse_option(f[2])?, parse_option(f[3])?),
+            binary: f[4].to_string(),
+            mean: f[5].parse().ok()?,
+            stddev: f[6].parse().ok()?,
+        })
+    }
+}
+
+impl std::fmt::Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{:.4}\t{:.4}",
+            self.time,
+            self.id.event,
+            format_option(self.id.year),
+            format_option(self.id.day),
+            self.binary,
+            self.mean,
+            self.stddev
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<Entry>,
+}
+
+impl History {
+    /// Parses the history, ignoring invalid lines.
+    pub fn parse(content: &str) -> Self {
+        Self {
+            entries: content
+                .lines()
+                .filter(|l| !l.starts_with('#'))
+                .filter_map(Entry::parse)
+                .collect(),
+        }
+    }
+
+    /// Loads the history. A missing file is an empty history.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The most recent entry for this binary.
+    pub fn previous(&self, id: &PuzzleId, binary: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .filter(|e| e.id == *id && e.binary == binary)
+            .max_by_key(|e| e.time)
+    }
+
+    /// Appends the new entries to the history file.
+    pub fn append(path: &Path, entries: &[Entry]) -> io::Result<()> {
+        let is_new = !path.exists();
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if is_new {
+            writeln!(file, "{}", HEADER)?;
+        }
+        for entry in entries {
+            writeln!(file, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_previous() {
+        let id = PuzzleId::advent_of_code(2023, 12);
+        let entry = |time, binary: &str, mean| Entry {
+            time,
+            id,
+            binary: binary.to_string(),
+            mean,
+            stddev: 0.1,
+        };
+        let content: String = [
+            HEADER.to_string(),
+            entry(100, "day12", 8.0).to_string(),
+            entry(200, "day12", 9.0).to_string(),
+            entry(300, "day12_part1", 900.0).to_string(),
+            "invalid".to_string(),
+        ]
+        .join("\n");
+        let history = History::parse(&content);
+        assert_eq!(history.previous(&id, "day12").unwrap().mean, 9.0);
+        assert_eq!(history.previous(&id, "day12_part1").unwrap().time, 300);
+        assert!(history
+            .previous(&PuzzleId::advent_of_code(2023, 1), "day01")
+            .is_none());
+    }
+}
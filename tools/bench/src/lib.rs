@@ -0,0 +1,5 @@
+//! Benchmarking of the days, also used by other tools to read the benchmark history.
+
+pub mod history;
+pub mod report;
+pub mod stats;
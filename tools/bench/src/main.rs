@@ -0,0 +1,241 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::Parser;
+
+use answers::puzzle::{Event, PuzzleId};
+use answers::run::{run_with_timeout, RunResult};
+use answers::workspace::{self, Day};
+
+use bench::history::{Entry, History};
+use bench::report::{self, BenchResult, Outcome};
+use bench::stats::Stats;
+
+/// Benchmarks the days of all years and Advent of Code clones.
+///
+/// When run from a year or day directory without filters, only this year or day is benchmarked.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Repository root, defaults to the one containing this tool
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// Only these events
+    #[arg(long)]
+    event: Vec<Event>,
+
+    /// Only these years
+    #[arg(long)]
+    year: Vec<u16>,
+
+    /// Only these days
+    #[arg(long)]
+    day: Vec<u8>,
+
+    /// Runs before measuring
+    #[arg(long, default_value_t = 2)]
+    warmup: usize,
+
+    /// Measured runs
+    #[arg(long, default_value_t = 10)]
+    runs: usize,
+
+    /// Maximum time for one run, in seconds
+    #[arg(long, default_value_t = 10)]
+    timeout: u64,
+
+    /// Change in percents above which a slower day is highlighted as a regression
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+
+    /// History file, defaults to bench_history.tsv at the top of the repository
+    #[arg(long)]
+    history: Option<PathBuf>,
+
+    /// Don't save the results in the history
+    #[arg(long)]
+    no_history: bool,
+
+    /// Markdown report
+    #[arg(long, default_value = "benchmarks.md")]
+    markdown: PathBuf,
+
+    /// JSON report
+    #[arg(long)]
+    json: Option<PathBuf>,
+
+    /// Don't build the days before
+    #[arg(long)]
+    no_build: bool,
+}
+
+impl Args {
+    // Without any filter, restricts to the current day or year directory.
+    fn default_filters(&mut self) {
+        if !(self.event.is_empty() && self.year.is_empty() && self.day.is_empty()) {
+            return;
+        }
+        let Ok(cwd) = std::env::current_dir() else {
+            return;
+        };
+        if let Some(id) = PuzzleId::from_path(&cwd) {
+            self.event.push(id.event);
+            self.year.extend(id.year);
+            self.day.extend(id.day);
+        } else if let Some(year) = cwd
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.parse().ok())
+        {
+            self.year.push(year);
+        }
+    }
+
+    fn selected(&self, day: &Day) -> bool {
+        (self.event.is_empty() || self.event.contains(&day.id.event))
+            && (self.year.is_empty() || day.id.year.is_some_and(|y| self.year.contains(&y)))
+            && (self.day.is_empty() || day.id.day.is_some_and(|d| self.day.contains(&d)))
+    }
+}
+
+fn binary_name(binary: &Path) -> String {
+    binary.file_name().unwrap().to_string_lossy().to_string()
+}
+
+fn build(workspace: &Path, days: &[Day]) -> bool {
+    let mut cmd = Command::new("cargo");
+    cmd.arg("build").arg("--release").current_dir(workspace);
+    for day in days {
+        cmd.arg("-p").arg(&day.package);
+        for extra in &day.extra_binaries {
+            cmd.arg("-p").arg(binary_name(extra));
+        }
+    }
+    cmd.status().is_ok_and(|s| s.success())
+}
+
+fn measure(binary: &Path, input: &Path, args: &Args) -> Outcome {
+    if !input.exists() {
+        return Outcome::NoInput;
+    }
+    let timeout = Duration::from_secs(args.timeout);
+    let mut times = Vec::with_capacity(args.runs);
+    for i in 0..args.warmup + args.runs.max(1) {
+        match run_with_timeout(binary, input, timeout) {
+            Ok(RunResult::Success { duration, .. }) => {
+                if i >= args.warmup {
+                    times.push(duration);
+                }
+            }
+            Ok(RunResult::Timeout) => return Outcome::TimedOut,
+            Ok(RunResult::Failed { stderr }) => {
+                return Outcome::Error(stderr.lines().last().unwrap_or_default().to_string())
+            }
+            Err(e) => return Outcome::Error(e.to_string()),
+        }
+    }
+    Outcome::Measured(Stats::new(&times))
+}
+
+fn print_result(result: &BenchResult, threshold: f64) {
+    let name = format!("{} ({})", result.id, result.binary);
+    match &result.outcome {
+        Outcome::Measured(s) => {
+            let change = match result.change() {
+                Some(c) if result.is_regression(threshold) => format!(", 🔺 {:+.1}%", c),
+                Some(c) => format!(", {:+.1}%", c),
+                None => String::new(),
+            };
+            println!(
+                "{}: {:.2} ms ± {:.2} ms [{:.2} ms … {:.2} ms]{}",
+                name, s.mean, s.stddev, s.min, s.max, change
+            );
+        }
+        Outcome::TimedOut => println!("{}: timed out", name),
+        Outcome::Error(e) => println!("{}: error {}", name, e),
+        Outcome::NoInput => println!("{}: no input", name),
+    }
+}
+
+fn main() {
+    let mut args = Args::parse();
+    args.default_filters();
+    let root = args.root.clone().unwrap_or_else(workspace::default_root);
+    let history_path = args
+        .history
+        .clone()
+        .unwrap_or_else(|| root.join("bench_history.tsv"));
+    let history = History::load(&history_path).unwrap();
+
+    let mut results = Vec::new();
+    for ws in workspace::find_workspaces(&root).unwrap() {
+        let ws_name = ws.file_name().unwrap().to_string_lossy().to_string();
+        let days: Vec<Day> = workspace::workspace_days(&ws)
+            .unwrap()
+            .into_iter()
+            .filter(|d| args.selected(d))
+            .collect();
+        if days.is_empty() {
+            continue;
+        }
+        let built = args.no_build || build(&ws, &days);
+        for day in &days {
+            let binaries = std::iter::once(&day.binary).chain(&day.extra_binaries);
+            for (i, binary) in binaries.enumerate() {
+                let name = binary_name(binary);
+                let outcome = if built {
+                    measure(binary, &day.input(), &args)
+                } else {
+                    Outcome::Error("Build failed".to_string())
+                };
+                let result = BenchResult {
+                    workspace: ws_name.clone(),
+                    id: day.id,
+                    previous_mean: history.previous(&day.id, &name).map(|e| e.mean),
+                    binary: name,
+                    main_binary: i == 0,
+                    outcome,
+                };
+                print_result(&result, args.threshold);
+                results.push(result);
+            }
+        }
+    }
+
+    fs::write(&args.markdown, report::markdown(&results, args.threshold)).unwrap();
+    if let Some(json) = &args.json {
+        fs::write(json, report::json(&results, args.threshold)).unwrap();
+    }
+    if !args.no_history {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let entries: Vec<Entry> = results
+            .iter()
+            .filter_map(|r| match &r.outcome {
+                Outcome::Measured(s) => Some(Entry {
+                    time,
+                    id: r.id,
+                    binary: r.binary.clone(),
+                    mean: s.mean,
+                    stddev: s.stddev,
+                }),
+                _ => None,
+            })
+            .collect();
+        History::append(&history_path, &entries).unwrap();
+    }
+
+    let regressions = results
+        .iter()
+        .filter(|r| r.is_regression(args.threshold))
+        .count();
+    if regressions > 0 {
+        println!("\n🔺 {} regression(s)", regressions);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,233 @@
+//! Markdown and JSON reports of the benchmark results.
+
+use answers::puzzle::PuzzleId;
+
+use crate::stats::Stats;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Measured(Stats),
+    TimedOut,
+    Error(String),
+    NoInput,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    /// Workspace name, like "2023" or "codyssi".
+    pub workspace: String,
+    pub id: PuzzleId,
+    pub binary: String,
+    /// Whether this is the main binary of the day.
+    pub main_binary: bool,
+    pub outcome: Outcome,
+    /// Mean of the previous run, if any, in milliseconds.
+    pub previous_mean: Option<f64>,
+}
+
+impl BenchResult {
+    /// Change of the mean compared with the previous run, in percents.
+    pub fn change(&self) -> Option<f64> {
+        match (&self.outcome, self.previous_mean) {
+            (Outcome::Measured(stats), Some(previous)) if previous > 0.0 => {
+                Some((stats.mean - previous) / previous * 100.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Slower than the previous run by more than the threshold (in percents),
+    /// and by more than the noise.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        match (&self.outcome, self.previous_mean, self.change()) {
+            (Outcome::Measured(stats), Some(previous), Some(change)) => {
+                change > threshold && stats.mean - previous > 2.0 * stats.stddev
+            }
+            _ => false,
+        }
+    }
+
+    fn label(&self) -> String {
+        if self.main_binary {
+            format!("`{}`", self.id)
+        } else {
+            format!("`{}` ({})", self.id, self.binary)
+        }
+    }
+}
+
+fn format_change(result: &BenchResult, threshold: f64) -> String {
+    match result.change() {
+        Some(change) if result.is_regression(threshold) => format!("🔺 {:+.1}%", change),
+        Some(change) if change < -threshold => format!("🔻 {:+.1}%", change),
+        Some(change) => format!("{:+.1}%", change),
+        None => String::new(),
+    }
+}
+
+/// Markdown tables, one per workspace.
+pub fn markdown(results: &[BenchResult], threshold: f64) -> String {
+    let mut md = String::new();
+    let mut workspace: Option<&str> = None;
+    for result in results {
+        if workspace != Some(&result.workspace) {
+            workspace = Some(&result.workspace);
+            if !md.is_empty() {
+                md += "\n";
+            }
+            md += &format!("## {}\n\n", result.workspace);
+            md += "| Day | Mean [ms] | Std dev [ms] | Min [ms] | Max [ms] | Change |\n";
+            md += "|:---|---:|---:|---:|---:|---:|\n";
+        }
+        let values = match &result.outcome {
+            Outcome::Measured(s) => format!(
+                "{:.2} | {:.2} | {:.2} | {:.2} | {}",
+                s.mean,
+                s.stddev,
+                s.min,
+                s.max,
+                format_change(result, threshold)
+            ),
+            Outcome::TimedOut => "Timed out | | | |".to_string(),
+            Outcome::Error(_) => "Error | | | |".to_string(),
+            Outcome::NoInput => "No input | | | |".to_string(),
+        };
+        md += &format!("| {} | {} |\n", result.label(), values);
+    }
+    md
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            c if c.is_control() => escaped += &format!("\\u{:04x}", c as u32),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_option<T: ToString>(v: Option<T>) -> String {
+    v.map_or_else(|| "null".to_string(), |v| v.to_string())
+}
+
+/// JSON array with one object per binary.
+pub fn json(results: &[BenchResult], threshold: f64) -> String {
+    let objects: Vec<String> = results
+        .iter()
+        .map(|r| {
+            let (status, stats) = match &r.outcome {
+                Outcome::Measured(s) => (
+                    "ok".to_string(),
+                    format!(
+                        ", \"runs\": {}, \"mean_ms\": {:.4}, \"stddev_ms\": {:.4}, \"median_ms\": {:.4}, \"min_ms\": {:.4}, \"max_ms\": {:.4}",
+                        s.runs, s.mean, s.stddev, s.median, s.min, s.max
+                    ),
+                ),
+                Outcome::TimedOut => ("timeout".to_string(), String::new()),
+                Outcome::Error(e) => (
+                    "error".to_string(),
+                    format!(", \"error\": {}", json_string(e)),
+                ),
+                Outcome::NoInput => ("no_input".to_string(), String::new()),
+            };
+            format!(
+                "  {{ \"workspace\": {}, \"event\": {}, \"year\": {}, \"day\": {}, \"binary\": {}, \"status\": {}{}, \"previous_mean_ms\": {}, \"regression\": {} }}",
+                json_string(&r.workspace),
+                json_string(r.id.event.name()),
+                json_option(r.id.year),
+                json_option(r.id.day),
+                json_string(&r.binary),
+                json_string(&status),
+                stats,
+                json_option(r.previous_mean.map(|m| format!("{:.4}", m))),
+                r.is_regression(threshold)
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(
+        binary: &str,
+        main_binary: bool,
+        outcome: Outcome,
+        previous_mean: Option<f64>,
+    ) -> BenchResult {
+        BenchResult {
+            workspace: "2023".to_string(),
+            id: PuzzleId::advent_of_code(2023, 12),
+            binary: binary.to_string(),
+            main_binary,
+            outcome,
+            previous_mean,
+        }
+    }
+
+    fn stats(mean: f64) -> Stats {
+        Stats {
+            runs: 10,
+            mean,
+            stddev: 0.1,
+            median: mean,
+            min: mean - 0.2,
+            max: mean + 0.2,
+        }
+    }
+
+    #[test]
+    fn test_regression() {
+        assert!(
+            result("day12", true, Outcome::Measured(stats(12.0)), Some(10.0)).is_regression(10.0)
+        );
+        assert!(
+            !result("day12", true, Outcome::Measured(stats(10.5)), Some(10.0)).is_regression(10.0)
+        );
+        // Within the noise.
+        assert!(
+            !result("day12", true, Outcome::Measured(stats(0.3)), Some(0.2)).is_regression(10.0)
+        );
+        assert!(!result("day12", true, Outcome::TimedOut, Some(10.0)).is_regression(10.0));
+    }
+
+    #[test]
+    fn test_markdown() {
+        let results = [
+            result("day12", true, Outcome::Measured(stats(12.0)), Some(10.0)),
+            result("day12_part1", false, Outcome::TimedOut, None),
+        ];
+        assert_eq!(
+            markdown(&results, 10.0),
+            "## 2023
+
+| Day | Mean [ms] | Std dev [ms] | Min [ms] | Max [ms] | Change |
+|:---|---:|---:|---:|---:|---:|
+| `2023 day 12` | 12.00 | 0.10 | 11.80 | 12.20 | 🔺 +20.0% |
+| `2023 day 12` (day12_part1) | Timed out | | | | |
+"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let results = [result(
+            "day12",
+            true,
+            Outcome::Error("bad \"input\"".to_string()),
+            None,
+        )];
+        assert_eq!(
+            json(&results, 10.0),
+            "[\n  { \"workspace\": \"2023\", \"event\": \"aoc\", \"year\": 2023, \"day\": 12, \"binary\": \"day12\", \"status\": \"error\", \"error\": \"bad \\\"input\\\"\", \"previous_mean_ms\": null, \"regression\": false }\n]\n"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//! Statistics on the run times.
+
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    /// All values in milliseconds.
+    pub mean: f64,
+    pub stddev: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Stats {
+    pub fn new(times: &[Duration]) -> Self {
+        assert!(!times.is_empty());
+        let mut ms: Vec<f64> = times.iter().map(|t| t.as_secs_f64() * 1000.0).collect();
+        ms.sort_by(f64::total_cmp);
+        let n = ms.len() as f64;
+        let mean = ms.iter().sum::<f64>() / n;
+        // Sample standard deviation, like hyperfine.
+        let stddev = if ms.len() > 1 {
+            (ms.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        let median = if ms.len().is_multiple_of(2) {
+            (ms[ms.len() / 2 - 1] + ms[ms.len() / 2]) / 2.0
+        } else {
+            ms[ms.len() / 2]
+        };
+        Self {
+            runs: ms.len(),
+            mean,
+            stddev,
+            median,
+            min: ms[0],
+            max: ms[ms.len() - 1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let times: Vec<Duration> = [4, 1, 3, 2]
+            .iter()
+            .map(|ms| Duration::from_millis(*ms))
+            .collect();
+        let stats = Stats::new(&times);
+        assert_eq!(stats.runs, 4);
+        assert!((stats.mean - 2.5).abs() < 1e-9);
+        assert!((stats.median - 2.5).abs() < 1e-9);
+        assert!((stats.stddev - 1.290_994).abs() < 1e-6);
+        assert!((stats.min - 1.0).abs() < 1e-9);
+        assert!((stats.max - 4.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,168 @@
+//! Metadata of a day, as front-matter at the top of its README.
+//!
+//! It's a small subset of YAML, which GitHub renders as a table:
+//!
+//! ```text
+//! ---
+//! stars: 2
+//! tags: [Regex, LazyLock]
+//! notes: |
+//!   Brute-forced, a bit slow.
+//!   Graphviz support.
+//! ---
+//! # Day 5: [Print Queue](https://adventofcode.com/2024/day/5)
+//! ```
+//!
+//! All fields are optional. Title and URL are normally taken from the README heading,
+//! they only need to be in the front-matter to override it.
+
+use std::fmt;
+
+const FENCE: &str = "---";
+const BLOCK_INDENT: &str = "  ";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrontMatter {
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub stars: Option<u8>,
+    pub tags: Vec<String>,
+    /// Free-form notes, shown under the day in the year README.
+    pub notes: Option<String>,
+}
+
+// "[a, b]" or "a, b".
+fn parse_list(value: &str) -> Vec<String> {
+    let value = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .unwrap_or(value);
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+impl FrontMatter {
+    /// Splits a README into its front-matter and the rest of the content.
+    /// Without front-matter, the whole content is returned.
+    pub fn split(readme: &str) -> Result<(Option<Self>, &str), String> {
+        let Some(rest) = readme
+            .strip_prefix(FENCE)
+            .and_then(|r| r.strip_prefix('\n'))
+        else {
+            return Ok((None, readme));
+        };
+        let (yaml, body) = if let Some(yaml) = rest.strip_prefix("---\n") {
+            // Empty front-matter.
+            (&rest[..0], yaml)
+        } else {
+            let end = rest
+                .find("\n---\n")
+                .ok_or("Front-matter isn't closed".to_string())?;
+            (&rest[..=end], &rest[end + 5..])
+        };
+        Ok((Some(Self::parse(yaml)?), body))
+    }
+
+    fn parse(yaml: &str) -> Result<Self, String> {
+        let mut front_matter = Self::default();
+        let mut lines = yaml.lines().peekable();
+        while let Some(line) = lines.next() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("Invalid front-matter line: {}", line))?;
+            let mut value = value.trim().to_string();
+            if value == "|" {
+                let mut block = Vec::new();
+                while let Some(next) = lines.next_if(|l| l.is_empty() || l.starts_with(' ')) {
+                    block.push(next.strip_prefix(BLOCK_INDENT).unwrap_or(next.trim()));
+                }
+                value = block.join("\n").trim_end().to_string();
+            }
+            match key.trim() {
+                "title" => front_matter.title = Some(value),
+                "url" => front_matter.url = Some(value),
+                "stars" => {
+                    let stars = value
+                        .parse()
+                        .ok()
+                        .filter(|s| *s <= 2)
+                        .ok_or_else(|| format!("Invalid stars: {}", value))?;
+                    front_matter.stars = Some(stars);
+                }
+                "tags" => front_matter.tags = parse_list(&value),
+                "notes" => front_matter.notes = Some(value).filter(|n| !n.is_empty()),
+                other => return Err(format!("Unknown front-matter field: {}", other)),
+            }
+        }
+        Ok(front_matter)
+    }
+}
+
+impl fmt::Display for FrontMatter {
+    /// Formats the front-matter with its fences, ready to be put at the top of the README.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", FENCE)?;
+        if let Some(title) = &self.title {
+            writeln!(f, "title: {}", title)?;
+        }
+        if let Some(url) = &self.url {
+            writeln!(f, "url: {}", url)?;
+        }
+        if let Some(stars) = self.stars {
+            writeln!(f, "stars: {}", stars)?;
+        }
+        if !self.tags.is_empty() {
+            writeln!(f, "tags: [{}]", self.tags.join(", "))?;
+        }
+        if let Some(notes) = &self.notes {
+            writeln!(f, "notes: |")?;
+            for line in notes.lines() {
+                if line.is_empty() {
+                    writeln!(f)?;
+                } else {
+                    writeln!(f, "{}{}", BLOCK_INDENT, line)?;
+                }
+            }
+        }
+        writeln!(f, "{}", FENCE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let readme = "---\nstars: 1\ntags: [Regex, LazyLock]\nnotes: |\n  Brute-force.\n\n  Rayon.\n---\n# Day 4\n\n---\n";
+        let (front_matter, body) = FrontMatter::split(readme).unwrap();
+        let front_matter = front_matter.unwrap();
+        assert_eq!(front_matter.stars, Some(1));
+        assert_eq!(front_matter.tags, vec!["Regex", "LazyLock"]);
+        assert_eq!(
+            front_matter.notes.as_deref(),
+            Some("Brute-force.\n\nRayon.")
+        );
+        assert_eq!(front_matter.title, None);
+        assert_eq!(body, "# Day 4\n\n---\n");
+        // Formatting it back gives the same.
+        assert_eq!(format!("{}{}", front_matter, body), readme);
+
+        // A rule later in the README isn't front-matter.
+        assert_eq!(
+            FrontMatter::split("# Day 4\n---\n"),
+            Ok((None, "# Day 4\n---\n"))
+        );
+
+        assert!(FrontMatter::split("---\nstars: 3\n---\n").is_err());
+        assert!(FrontMatter::split("---\nstars: 2\n").is_err());
+        assert!(FrontMatter::split("---\nauthor: me\n---\n").is_err());
+    }
+}
@@ -0,0 +1,473 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use clap::{Parser, Subcommand};
+
+use answers::puzzle::PuzzleId;
+use answers::workspace::{self, Day};
+use bench::history::History;
+
+mod front_matter;
+mod year;
+
+use front_matter::FrontMatter;
+use year::{Entry, YearReadme};
+
+/// Generates the list of days of the year READMEs from the front-matter of the day READMEs
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Commands,
+
+    /// Repository root, defaults to the one containing this tool
+    #[arg(long, global = true)]
+    root: Option<PathBuf>,
+
+    /// Only these years
+    #[arg(long, global = true)]
+    year: Vec<u16>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Reports the inconsistencies between the year READMEs and the days
+    Check,
+    /// Regenerates the list of days of the year READMEs
+    Generate {
+        /// Adds the runtime of each day, from the benchmark history
+        #[arg(long)]
+        runtime: bool,
+
+        /// Benchmark history, defaults to bench_history.tsv at the top of the repository
+        #[arg(long)]
+        history: Option<PathBuf>,
+    },
+    /// Adds front-matter to the days without it, from their entry in the year README
+    Migrate,
+}
+
+// Lines of the notes generated from the front-matter, not part of the notes themselves.
+const TAGS_PREFIX: &str = "Tags: ";
+const RUNTIME_PREFIX: &str = "Runtime: ";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Issue {
+    MissingEntry(u8),
+    NoDirectory(u8),
+    HeadingLevel(u8, usize),
+    Link {
+        day: u8,
+        expected: String,
+        actual: String,
+    },
+    Title {
+        day: u8,
+        day_readme: String,
+        year_readme: String,
+    },
+    Stars {
+        day: u8,
+        front_matter: u8,
+        year_readme: u8,
+    },
+    NoHeading(u8),
+    FrontMatter(u8, String),
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Issue::MissingEntry(day) => write!(f, "day {} isn't in the README", day),
+            Issue::NoDirectory(day) => write!(f, "day {} is in the README but isn't a member", day),
+            Issue::HeadingLevel(day, level) => write!(
+                f,
+                "day {} heading has level {} instead of {}",
+                day,
+                level,
+                Entry::LEVEL
+            ),
+            Issue::Link {
+                day,
+                expected,
+                actual,
+            } => write!(f, "day {} links to {} instead of {}", day, actual, expected),
+            Issue::Title {
+                day,
+                day_readme,
+                year_readme,
+            } => write!(
+                f,
+                "day {} is \"{}\" in the README, but \"{}\" in its own README",
+                day, year_readme, day_readme
+            ),
+            Issue::Stars {
+                day,
+                front_matter,
+                year_readme,
+            } => write!(
+                f,
+                "day {} has {} stars in the README, but {} in its front-matter",
+                day, year_readme, front_matter
+            ),
+            Issue::NoHeading(day) => write!(
+                f,
+                "day {} README has no \"# Day N: [Title](URL)\" heading",
+                day
+            ),
+            Issue::FrontMatter(day, error) => write!(f, "day {} front-matter: {}", day, error),
+        }
+    }
+}
+
+/// What we know about a day from its directory.
+struct DayInfo {
+    day: u8,
+    id: PuzzleId,
+    package: String,
+    readme_path: PathBuf,
+    /// Link from the year README.
+    link: String,
+    /// Title and URL from the heading of the day README.
+    heading: Option<(String, String)>,
+    front_matter: Result<Option<FrontMatter>, String>,
+}
+
+// Parses "# Day 5: [Print Queue](https://adventofcode.com/2024/day/5)".
+fn parse_day_heading(body: &str) -> Option<(String, String)> {
+    let line = body.lines().find(|l| !l.trim().is_empty())?;
+    let rest = line.strip_prefix("# Day ")?.split_once(':')?.1;
+    let rest = rest.trim_start().strip_prefix('[')?;
+    let (title, rest) = rest.rsplit_once("](")?;
+    let url = rest.strip_suffix(')')?;
+    Some((title.to_string(), url.to_string()))
+}
+
+impl DayInfo {
+    fn load(year_dir: &Path, day: &Day) -> Self {
+        let readme_path = day.dir.join("README.md");
+        let readme = fs::read_to_string(&readme_path).unwrap_or_default();
+        let (front_matter, heading) = match FrontMatter::split(&readme) {
+            Ok((front_matter, body)) => (Ok(front_matter), parse_day_heading(body)),
+            Err(e) => (Err(e), parse_day_heading(&readme)),
+        };
+        let dir = day.dir.strip_prefix(year_dir).unwrap_or(&day.dir);
+        Self {
+            day: day.id.day.unwrap(),
+            id: day.id,
+            package: day.package.clone(),
+            readme_path,
+            link: format!("{}/README.md", dir.display()),
+            heading,
+            front_matter,
+        }
+    }
+
+    fn front_matter(&self) -> Option<&FrontMatter> {
+        self.front_matter.as_ref().ok().and_then(Option::as_ref)
+    }
+}
+
+fn check(readme: &YearReadme, days: &[DayInfo]) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for info in days {
+        let day = info.day;
+        if let Err(e) = &info.front_matter {
+            issues.push(Issue::FrontMatter(day, e.clone()));
+        }
+        if info.heading.is_none() {
+            issues.push(Issue::NoHeading(day));
+        }
+        let Some(entry) = readme.entry(day) else {
+            issues.push(Issue::MissingEntry(day));
+            continue;
+        };
+        if entry.level != Entry::LEVEL {
+            issues.push(Issue::HeadingLevel(day, entry.level));
+        }
+        if entry.link != info.link {
+            issues.push(Issue::Link {
+                day,
+                expected: info.link.clone(),
+                actual: entry.link.clone(),
+            });
+        }
+        let title = info
+            .front_matter()
+            .and_then(|f| f.title.as_ref())
+            .or(info.heading.as_ref().map(|h| &h.0));
+        if let Some(title) = title.filter(|t| **t != entry.title) {
+            issues.push(Issue::Title {
+                day,
+                day_readme: title.clone(),
+                year_readme: entry.title.clone(),
+            });
+        }
+        if let Some(stars) = info.front_matter().and_then(|f| f.stars) {
+            if stars != entry.stars {
+                issues.push(Issue::Stars {
+                    day,
+                    front_matter: stars,
+                    year_readme: entry.stars,
+                });
+            }
+        }
+    }
+    for entry in &readme.entries {
+        if !days.iter().any(|d| d.day == entry.day) {
+            issues.push(Issue::NoDirectory(entry.day));
+        }
+    }
+    issues
+}
+
+fn format_runtime(ms: f64) -> String {
+    if ms >= 1000.0 {
+        format!("{:.2} s", ms / 1000.0)
+    } else {
+        format!("{:.2} ms", ms)
+    }
+}
+
+// Notes of a day without front-matter, from the README, without the lines we generated.
+fn own_notes(entry: &Entry) -> String {
+    entry
+        .notes
+        .lines()
+        .filter(|l| !l.starts_with(TAGS_PREFIX) && !l.starts_with(RUNTIME_PREFIX))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn generate(readme: &YearReadme, days: &[DayInfo], history: Option<&History>) -> YearReadme {
+    let mut entries: Vec<Entry> = days
+        .iter()
+        .map(|info| {
+            let existing = readme.entry(info.day);
+            let front_matter = info.front_matter();
+            let title = front_matter
+                .and_then(|f| f.title.clone())
+                .or(info.heading.as_ref().map(|h| h.0.clone()))
+                .or(existing.map(|e| e.title.clone()))
+                .unwrap_or_default();
+            let stars = front_matter
+                .and_then(|f| f.stars)
+                .or(existing.map(|e| e.stars))
+                .unwrap_or_default();
+            // With front-matter, it's the only source of the notes.
+            let mut notes = match front_matter {
+                Some(f) => f.notes.clone().unwrap_or_default(),
+                None => existing.map(own_notes).unwrap_or_default(),
+            };
+            let mut metadata = Vec::new();
+            if let Some(f) = front_matter.filter(|f| !f.tags.is_empty()) {
+                metadata.push(format!("{}{}", TAGS_PREFIX, f.tags.join(", ")));
+            }
+            if let Some(previous) = history.and_then(|h| h.previous(&info.id, &info.package)) {
+                metadata.push(format!(
+                    "{}{}",
+                    RUNTIME_PREFIX,
+                    format_runtime(previous.mean)
+                ));
+            }
+            if !metadata.is_empty() {
+                if !notes.is_empty() {
+                    notes += "\n\n";
+                }
+                notes += &metadata.join("\n");
+            }
+            Entry {
+                day: info.day,
+                title,
+                link: info.link.clone(),
+                stars,
+                level: Entry::LEVEL,
+                notes,
+            }
+        })
+        .collect();
+    // Entries without a directory are kept, they are reported by the check.
+    entries.extend(
+        readme
+            .entries
+            .iter()
+            .filter(|e| !days.iter().any(|d| d.day == e.day))
+            .cloned(),
+    );
+    entries.sort_by_key(|e| e.day);
+    YearReadme {
+        before: readme.before.clone(),
+        entries,
+        after: readme.after.clone(),
+    }
+}
+
+// Adds front-matter to the days that don't have it, returning how many were changed.
+fn migrate(readme: &YearReadme, days: &[DayInfo]) -> usize {
+    let mut count = 0;
+    for info in days {
+        if !matches!(info.front_matter, Ok(None)) {
+            continue;
+        }
+        let Some(entry) = readme.entry(info.day) else {
+            continue;
+        };
+        let notes = own_notes(entry);
+        let front_matter = FrontMatter {
+            stars: Some(entry.stars),
+            notes: Some(notes).filter(|n| !n.is_empty()),
+            ..Default::default()
+        };
+        let content = fs::read_to_string(&info.readme_path).unwrap_or_default();
+        fs::write(&info.readme_path, format!("{}{}", front_matter, content)).unwrap();
+        count += 1;
+    }
+    count
+}
+
+fn find_years(root: &Path, filter: &[u16]) -> Vec<(u16, PathBuf)> {
+    workspace::find_workspaces(root)
+        .unwrap()
+        .into_iter()
+        .filter_map(|dir| {
+            let year: u16 = dir.file_name()?.to_str()?.parse().ok()?;
+            (filter.is_empty() || filter.contains(&year)).then_some((year, dir))
+        })
+        .collect()
+}
+
+fn main() {
+    let args = Args::parse();
+    let root = fs::canonicalize(args.root.unwrap_or_else(workspace::default_root)).unwrap();
+
+    let history = match &args.command {
+        Commands::Generate {
+            runtime: true,
+            history,
+        } => {
+            let path = history
+                .clone()
+                .unwrap_or_else(|| root.join("bench_history.tsv"));
+            Some(History::load(&path).unwrap_or_else(|e| {
+                eprintln!("Failed to read {}: {}", path.display(), e);
+                process::exit(1);
+            }))
+        }
+        _ => None,
+    };
+
+    let mut issue_count = 0;
+    for (year, dir) in find_years(&root, &args.year) {
+        let readme_path = dir.join("README.md");
+        let content = fs::read_to_string(&readme_path).unwrap_or_default();
+        let readme = match YearReadme::parse(&content) {
+            Ok(readme) => readme,
+            Err(e) => {
+                println!("{}: {}", readme_path.display(), e);
+                issue_count += 1;
+                continue;
+            }
+        };
+        let days: Vec<DayInfo> = workspace::workspace_days(&dir)
+            .unwrap()
+            .iter()
+            .map(|day| DayInfo::load(&dir, day))
+            .collect();
+
+        let issues = match args.command {
+            Commands::Check => check(&readme, &days),
+            Commands::Generate { .. } => {
+                let generated = generate(&readme, &days, history.as_ref());
+                let new_content = generated.to_string();
+                if new_content != content {
+                    fs::write(&readme_path, &new_content).unwrap();
+                    println!("Updated {}", readme_path.display());
+                }
+                check(&generated, &days)
+            }
+            Commands::Migrate => {
+                let count = migrate(&readme, &days);
+                if count > 0 {
+                    println!("{}: added front-matter to {} days", year, count);
+                }
+                Vec::new()
+            }
+        };
+        for issue in &issues {
+            println!("{}: {}", year, issue);
+        }
+        issue_count += issues.len();
+    }
+
+    if matches!(args.command, Commands::Check) && issue_count > 0 {
+        println!();
+        println!("{} issues", issue_count);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day_info(day: u8, title: &str, front_matter: Option<FrontMatter>) -> DayInfo {
+        DayInfo {
+            day,
+            id: PuzzleId::advent_of_code(2016, day),
+            package: format!("day{:02}", day),
+            readme_path: PathBuf::new(),
+            link: format!("day{:02}/README.md", day),
+            heading: Some((title.to_string(), String::new())),
+            front_matter: Ok(front_matter),
+        }
+    }
+
+    const README: &str = "# Advent of Code 2016
+
+## Days
+
+### Day 1: [No Time for a Taxicab](day01/README.md) 🌟🌟
+
+Tracking path.
+
+## Day 15: [Timing is Everything](day15/README.md) 🌟🌟
+
+Simple.
+";
+
+    #[test]
+    fn test_check_and_generate() {
+        let readme = YearReadme::parse(README).unwrap();
+        let days = vec![
+            day_info(1, "No Time for a Taxicab", None),
+            day_info(
+                2,
+                "Bathroom Security",
+                Some(FrontMatter {
+                    stars: Some(2),
+                    tags: vec!["Generics".to_string()],
+                    ..Default::default()
+                }),
+            ),
+            day_info(15, "Timing is Everything", None),
+        ];
+        assert_eq!(
+            check(&readme, &days),
+            vec![Issue::MissingEntry(2), Issue::HeadingLevel(15, 2)]
+        );
+
+        let generated = generate(&readme, &days, None);
+        assert!(check(&generated, &days).is_empty());
+        let expected = README.replace(
+            "## Day 15",
+            "### Day 2: [Bathroom Security](day02/README.md) 🌟🌟\n\nTags: Generics\n\n### Day 15",
+        );
+        assert_eq!(generated.to_string(), expected);
+        // Generating again changes nothing.
+        assert_eq!(generate(&generated, &days, None), generated);
+    }
+}
@@ -0,0 +1,209 @@
+//! The README of a year, split between the list of days, which is generated,
+//! and the free-form sections around it, which are kept as is.
+//!
+//! ```text
+//! # Advent of Code 2024
+//!
+//! ## Wrapup
+//! ...
+//!
+//! ## Days
+//!
+//! ### Day 1: [Historian Hysteria](day01/README.md) 🌟🌟
+//!
+//! Iterator:unzip().
+//! ```
+
+use std::fmt::Write;
+
+const DAYS_HEADING: &str = "## Days";
+const STAR: char = '🌟';
+
+/// A day in the list of days of the year README.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub day: u8,
+    pub title: String,
+    pub link: String,
+    pub stars: u8,
+    /// Number of `#` of the heading, 3 when well-formed.
+    pub level: usize,
+    pub notes: String,
+}
+
+impl Entry {
+    pub const LEVEL: usize = 3;
+
+    // Parses "### Day 9: [Movie Theater](day09/README.md) 🌟".
+    fn parse_heading(line: &str) -> Option<Self> {
+        let level = line.chars().take_while(|c| *c == '#').count();
+        let rest = line[level..].strip_prefix(" Day ")?;
+        let (day, rest) = rest.split_once(':')?;
+        let rest = rest.trim_start().strip_prefix('[')?;
+        // Titles can contain brackets, like "[Day 6: Wait For It]", so we look for the link.
+        let (title, rest) = rest.rsplit_once("](")?;
+        let (link, rest) = rest.split_once(')')?;
+        let stars = rest.chars().filter(|c| *c == STAR).count();
+        Some(Self {
+            day: day.trim().parse().ok()?,
+            title: title.to_string(),
+            link: link.to_string(),
+            stars: stars.min(2) as u8,
+            level,
+            notes: String::new(),
+        })
+    }
+
+    fn format(&self, out: &mut String) {
+        let stars: String = std::iter::repeat_n(STAR, self.stars.into()).collect();
+        let _ = write!(
+            out,
+            "{} Day {}: [{}]({})",
+            "#".repeat(Self::LEVEL),
+            self.day,
+            self.title,
+            self.link
+        );
+        if !stars.is_empty() {
+            out.push(' ');
+            out.push_str(&stars);
+        }
+        out.push('\n');
+        if !self.notes.is_empty() {
+            out.push('\n');
+            out.push_str(&self.notes);
+            out.push('\n');
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct YearReadme {
+    /// Everything up to the days heading included.
+    pub before: String,
+    pub entries: Vec<Entry>,
+    /// Sections after the list of days, if any.
+    pub after: String,
+}
+
+impl YearReadme {
+    pub fn parse(readme: &str) -> Result<Self, String> {
+        let start = readme
+            .lines()
+            .position(|l| l.trim_end() == DAYS_HEADING)
+            .ok_or_else(|| format!("No \"{}\" section", DAYS_HEADING))?;
+        let lines: Vec<&str> = readme.lines().collect();
+        let mut before = lines[..=start].join("\n");
+        before.push('\n');
+
+        let mut entries: Vec<Entry> = Vec::new();
+        let mut notes: Vec<&str> = Vec::new();
+        let mut after = String::new();
+        let flush = |entries: &mut Vec<Entry>, notes: &mut Vec<&str>| {
+            if let Some(entry) = entries.last_mut() {
+                entry.notes = notes.join("\n").trim().to_string();
+            }
+            notes.clear();
+        };
+        for (i, line) in lines.iter().enumerate().skip(start + 1) {
+            if let Some(entry) = Entry::parse_heading(line) {
+                flush(&mut entries, &mut notes);
+                entries.push(entry);
+            } else if line.starts_with("## ") {
+                // A new section, ending the list of days.
+                after = lines[i..].join("\n");
+                after.push('\n');
+                break;
+            } else {
+                notes.push(line);
+            }
+        }
+        flush(&mut entries, &mut notes);
+        Ok(Self {
+            before,
+            entries,
+            after,
+        })
+    }
+
+    pub fn entry(&self, day: u8) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.day == day)
+    }
+}
+
+impl std::fmt::Display for YearReadme {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut out = self.before.clone();
+        for entry in &self.entries {
+            out.push('\n');
+            entry.format(&mut out);
+        }
+        if !self.after.is_empty() {
+            out.push('\n');
+            out.push_str(&self.after);
+        }
+        f.write_str(&out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const README: &str = "# Advent of Code 2016
+
+## Wrapup
+
+Great fun.
+
+## Days
+
+### Day 1: [No Time for a Taxicab](day01/README.md) 🌟🌟
+
+Tracking path.
+
+### Day 6: [Day 6: Wait For It](day06/README.md) 🌟🌟
+
+### Day 14: [One-Time Pad](day14/README.md) 🌟🌟
+
+MD5.
+
+Brute-force.
+
+## Day 15: [Timing is Everything](day15/README.md) 🌟
+
+## Links
+
+Somewhere.
+";
+
+    #[test]
+    fn test_parse() {
+        let year = YearReadme::parse(README).unwrap();
+        assert!(year.before.ends_with("Great fun.\n\n## Days\n"));
+        assert_eq!(year.entries.len(), 4);
+        assert_eq!(year.entries[0].notes, "Tracking path.");
+        assert_eq!(year.entries[1].title, "Day 6: Wait For It");
+        assert_eq!(year.entries[1].notes, "");
+        assert_eq!(year.entries[2].notes, "MD5.\n\nBrute-force.");
+        let day15 = year.entry(15).unwrap();
+        assert_eq!(day15.level, 2);
+        assert_eq!(day15.stars, 1);
+        assert_eq!(day15.link, "day15/README.md");
+        assert_eq!(year.after, "## Links\n\nSomewhere.\n");
+
+        assert!(YearReadme::parse("# Advent of Code 2016\n").is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        let mut year = YearReadme::parse(README).unwrap();
+        // Only the wrong heading level is changed.
+        let fixed = README.replace("## Day 15", "### Day 15");
+        assert_eq!(year.to_string(), fixed);
+
+        year.entries.pop();
+        year.after.clear();
+        assert!(year.to_string().ends_with("MD5.\n\nBrute-force.\n"));
+    }
+}
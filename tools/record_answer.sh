@@ -1,4 +1,5 @@
 #!/bin/bash
 
-pwd >> ../../answers
-cat resources/input | cargo r >> ../../answers
+# Records the answers of the current day in the answers registry, see tools/answers.
+CDIR=$( dirname "$0" )
+cat resources/input | cargo r --release | tee /dev/stderr | "$CDIR/answers/target/release/answers" record "$@"
@@ -0,0 +1,123 @@
+//! Stars of a year, read from the day headings of its README:
+//!
+//! ```text
+//! ### Day 9: [Movie Theater](day09/README.md) 🌟
+//! ```
+
+use std::fmt;
+
+const STAR: char = '🌟';
+
+/// Number of days in the calendar of the year.
+/// Since 2025, there are only 12 days.
+pub fn days_in_year(year: u16) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Missing {
+    Day(u8),
+    Part2(u8),
+}
+
+impl fmt::Display for Missing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Missing::Day(day) => write!(f, "day {}", day),
+            Missing::Part2(day) => write!(f, "day {} part 2", day),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calendar {
+    pub year: u16,
+    /// Stars of each day, index 0 being day 1.
+    pub stars: Vec<u8>,
+}
+
+// Parses "### Day 9: [Movie Theater](day09/README.md) 🌟", returning the day and its stars.
+fn parse_heading(line: &str) -> Option<(u8, u8)> {
+    let rest = line.strip_prefix("### Day ")?;
+    let (day, rest) = rest.split_once(':')?;
+    let day = day.trim().parse().ok()?;
+    let stars = rest.chars().filter(|c| *c == STAR).count();
+    Some((day, stars.min(2) as u8))
+}
+
+impl Calendar {
+    pub fn parse(year: u16, readme: &str) -> Self {
+        let mut stars = vec![0; days_in_year(year) as usize];
+        for (day, count) in readme.lines().filter_map(parse_heading) {
+            if (1..=stars.len()).contains(&(day as usize)) {
+                stars[day as usize - 1] = count;
+            }
+        }
+        Self { year, stars }
+    }
+
+    pub fn star_count(&self) -> usize {
+        self.stars.iter().map(|s| *s as usize).sum()
+    }
+
+    pub fn max_stars(&self) -> usize {
+        self.stars.len() * 2
+    }
+
+    pub fn missing(&self) -> Vec<Missing> {
+        self.stars
+            .iter()
+            .enumerate()
+            .filter_map(|(i, stars)| {
+                let day = i as u8 + 1;
+                match stars {
+                    0 => Some(Missing::Day(day)),
+                    1 => Some(Missing::Part2(day)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const README: &str = "# Advent of Code 2025
+
+## Days
+
+### Day 1: [Secret Entrance](day01/README.md) 🌟🌟
+
+Some text with a 🌟 that isn't counted.
+
+### Day 2: [Gift Shop](day02/README.md) 🌟🌟
+
+### Day 4: [Printing Department](day04/README.md) 🌟
+";
+
+    #[test]
+    fn test_parse() {
+        let calendar = Calendar::parse(2025, README);
+        assert_eq!(calendar.stars.len(), 12);
+        assert_eq!(calendar.star_count(), 5);
+        assert_eq!(calendar.max_stars(), 24);
+        let missing = calendar.missing();
+        assert_eq!(missing[0], Missing::Day(3));
+        assert_eq!(missing[1], Missing::Part2(4));
+        assert_eq!(missing.len(), 10);
+        assert_eq!(missing[1].to_string(), "day 4 part 2");
+    }
+
+    #[test]
+    fn test_days_in_year() {
+        assert_eq!(days_in_year(2015), 25);
+        assert_eq!(days_in_year(2024), 25);
+        assert_eq!(days_in_year(2025), 12);
+    }
+}
@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+
+mod calendar;
+
+use calendar::Calendar;
+
+/// Counts the stars of all years, from their README
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Repository root, defaults to the one containing this tool
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// Prints a Markdown table with badges
+    #[arg(long)]
+    markdown: bool,
+}
+
+// Finds all year directories, sorted.
+fn find_years(root: &Path) -> Vec<u16> {
+    let mut years: Vec<u16> = fs::read_dir(root)
+        .unwrap()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().join("README.md").is_file())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.len() == 4 {
+                name.parse().ok()
+            } else {
+                None
+            }
+        })
+        .collect();
+    years.sort_unstable();
+    years
+}
+
+fn percentage(stars: usize, max: usize) -> f64 {
+    stars as f64 / max as f64 * 100.0
+}
+
+fn print_text(calendars: &[Calendar]) {
+    for c in calendars {
+        let missing = c.missing();
+        print!("{}: {}/{} 🌟", c.year, c.star_count(), c.max_stars());
+        if !missing.is_empty() {
+            let list: Vec<String> = missing.iter().map(ToString::to_string).collect();
+            print!(" - missing {}", list.join(", "));
+        }
+        println!();
+    }
+    let stars: usize = calendars.iter().map(Calendar::star_count).sum();
+    let max: usize = calendars.iter().map(Calendar::max_stars).sum();
+    println!();
+    println!("Total: {}/{} 🌟", stars, max);
+    println!("Percentage completed: {:.2} %", percentage(stars, max));
+    println!();
+    println!("See your calendars with \"aoc calendar -y YYYY\"");
+}
+
+fn badge(label: &str, stars: usize, max: usize) -> String {
+    let color = if stars == max {
+        "brightgreen"
+    } else {
+        "yellow"
+    };
+    format!(
+        "![{} stars](https://img.shields.io/badge/{}-{}%2F{}%20stars-{})",
+        label, label, stars, max, color
+    )
+}
+
+fn print_markdown(calendars: &[Calendar]) {
+    println!("| Year | Stars |");
+    println!("|:---|:---|");
+    for c in calendars {
+        println!(
+            "| [{}]({}/README.md) | {} |",
+            c.year,
+            c.year,
+            badge(&c.year.to_string(), c.star_count(), c.max_stars())
+        );
+    }
+    let stars: usize = calendars.iter().map(Calendar::star_count).sum();
+    let max: usize = calendars.iter().map(Calendar::max_stars).sum();
+    println!("| Total | {} |", badge("total", stars, max));
+}
+
+fn main() {
+    let args = Args::parse();
+    let root = args
+        .root
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../.."));
+
+    let calendars: Vec<Calendar> = find_years(&root)
+        .into_iter()
+        .map(|year| {
+            let readme = fs::read_to_string(root.join(year.to_string()).join("README.md")).unwrap();
+            Calendar::parse(year, &readme)
+        })
+        .collect();
+
+    if args.markdown {
+        print_markdown(&calendars);
+    } else {
+        print_text(&calendars);
+    }
+}
@@ -0,0 +1,544 @@
+//! Cellular automata, on grids of any dimension or on hexagonal grids.
+//!
+//! Cells are alive or dead. A puzzle only defines the [`Neighborhood`] of a cell
+//! and the [`Rule`] giving its next state, the [`Automaton`] runs the generations.
+//!
+//! The cells are stored either densely, inside fixed bounds, or sparsely on an infinite grid.
+//! In both cases, the cells that were never set have the background state,
+//! which evolves with the rule like any other cell: A rule making empty areas alive
+//! makes the background toggle at each generation.
+//!
+//! Neighborhoods are usually the same offsets for all cells, but they can also be given per cell
+//! for grids that aren't regular, like the seats in line of sight of 2020 day 11
+//! or the recursive grids of 2019 day 24.
+//!
+//! ```text
+//! let mut automaton = Automaton::sparse(Neighborhood::<4>::moore(), Rule::life());
+//! automaton.set_text(input, '#');
+//! automaton.run(6);
+//! automaton.alive_count()
+//! ```
+
+use std::{fmt, rc::Rc};
+
+use fxhash::FxHashSet;
+
+/// Coordinates of a cell.
+pub type Point<const N: usize> = [i32; N];
+
+fn add<const N: usize>(p: &Point<N>, offset: &Point<N>) -> Point<N> {
+    std::array::from_fn(|d| p[d] + offset[d])
+}
+
+fn sub<const N: usize>(p: &Point<N>, offset: &Point<N>) -> Point<N> {
+    std::array::from_fn(|d| p[d] - offset[d])
+}
+
+/// Gives the neighbors of a cell.
+pub type NeighborsFn<const N: usize> = dyn Fn(&Point<N>) -> Vec<Point<N>>;
+
+/// The cells whose states give the next state of a cell.
+#[derive(Clone)]
+pub enum Neighborhood<const N: usize> {
+    /// The same offsets from every cell.
+    Offsets(Vec<Point<N>>),
+    /// The neighbors of each cell.
+    /// The relation must be symmetric, as the sparse storage looks for the cells that may change
+    /// among the neighbors of the alive ones. The background never changes.
+    Fn(Rc<NeighborsFn<N>>),
+}
+
+impl<const N: usize> Neighborhood<N> {
+    /// A custom neighborhood. The order of the offsets matters for the [`Rule::Table`] rules.
+    pub fn new(offsets: Vec<Point<N>>) -> Self {
+        Self::Offsets(offsets)
+    }
+
+    /// A neighborhood given for each cell.
+    pub fn from_fn(neighbors: impl Fn(&Point<N>) -> Vec<Point<N>> + 'static) -> Self {
+        Self::Fn(Rc::new(neighbors))
+    }
+
+    /// All the cells in the block of size 3 around the cell, including the cell itself,
+    /// in lexicographic order. With `[row, col]` points, it's the reading order.
+    pub fn block() -> Self {
+        let mut offsets = vec![[0; N]];
+        for d in 0..N {
+            offsets = offsets
+                .into_iter()
+                .flat_map(|offset| {
+                    (-1..=1).map(move |v| {
+                        let mut offset = offset;
+                        offset[d] = v;
+                        offset
+                    })
+                })
+                .collect();
+        }
+        Self::Offsets(offsets)
+    }
+
+    /// The 3^N - 1 cells touching the cell, diagonals included.
+    pub fn moore() -> Self {
+        let mut neighborhood = Self::block();
+        if let Self::Offsets(offsets) = &mut neighborhood {
+            offsets.retain(|offset| *offset != [0; N]);
+        }
+        neighborhood
+    }
+
+    /// The 2 * N cells sharing a side with the cell.
+    pub fn von_neumann() -> Self {
+        let offsets = (0..N)
+            .flat_map(|d| {
+                [-1, 1].map(|v| {
+                    let mut offset = [0; N];
+                    offset[d] = v;
+                    offset
+                })
+            })
+            .collect();
+        Self::Offsets(offsets)
+    }
+
+    /// Number of neighbors of each cell, None if given per cell.
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            Self::Offsets(offsets) => Some(offsets.len()),
+            Self::Fn(_) => None,
+        }
+    }
+}
+
+impl<const N: usize> fmt::Debug for Neighborhood<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Offsets(offsets) => f.debug_tuple("Offsets").field(offsets).finish(),
+            Self::Fn(_) => f.write_str("Fn"),
+        }
+    }
+}
+
+impl Neighborhood<2> {
+    /// The 6 cells around a hexagon, with axial coordinates `[q, r]`:
+    /// East is `[1, 0]`, south-east `[0, 1]`, south-west `[-1, 1]`, west `[-1, 0]`,
+    /// north-west `[0, -1]` and north-east `[1, -1]`.
+    pub fn hex() -> Self {
+        Self::new(vec![[1, 0], [0, 1], [-1, 1], [-1, 0], [0, -1], [1, -1]])
+    }
+}
+
+/// How the next state of a cell is computed from its state and the ones of its neighbors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// Depends only on the number of alive neighbors: A dead cell becomes alive if this number is in `birth`,
+    /// an alive one stays alive if it's in `survive`.
+    Totalistic {
+        birth: Vec<usize>,
+        survive: Vec<usize>,
+    },
+    /// The next state is `table[index]`, the index having one bit per neighbor,
+    /// 1 if alive, the first neighbor being the most significant bit.
+    /// The state of the cell is only used if the neighborhood contains it.
+    /// With a neighborhood given per cell, the table must be long enough for all cells.
+    Table(Vec<bool>),
+}
+
+impl Rule {
+    /// Conway's Game of Life.
+    pub fn life() -> Self {
+        Self::parse("B3/S23")
+    }
+
+    /// A totalistic rule in the B/S notation, like "B3/S23" for the Game of Life.
+    pub fn parse(notation: &str) -> Self {
+        let counts = |part: &str, prefix: char| -> Vec<usize> {
+            part.strip_prefix(prefix)
+                .expect("Invalid rule notation")
+                .chars()
+                .map(|c| c.to_digit(10).expect("Invalid count in rule") as usize)
+                .collect()
+        };
+        let (birth, survive) = notation.split_once('/').expect("Invalid rule notation");
+        Self::Totalistic {
+            birth: counts(birth, 'B'),
+            survive: counts(survive, 'S'),
+        }
+    }
+
+    /// A table rule from a string of '#' for alive and '.' for dead.
+    pub fn table(table: &str) -> Self {
+        Self::Table(
+            table
+                .chars()
+                .map(|c| match c {
+                    '#' => true,
+                    '.' => false,
+                    _ => panic!("Invalid char in rule table"),
+                })
+                .collect(),
+        )
+    }
+
+    fn next(&self, alive: bool, neighbors: impl Iterator<Item = bool>) -> bool {
+        match self {
+            Self::Totalistic { birth, survive } => {
+                let count = neighbors.filter(|n| *n).count();
+                if alive {
+                    survive.contains(&count)
+                } else {
+                    birth.contains(&count)
+                }
+            }
+            Self::Table(table) => {
+                table[neighbors.fold(0, |index, n| (index << 1) | usize::from(n))]
+            }
+        }
+    }
+}
+
+/// What happened during one generation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub generation: usize,
+    /// Number of alive cells, None if infinite.
+    pub alive: Option<usize>,
+    /// Number of cells that became alive or died. On an infinite grid,
+    /// the cells following the background aren't counted.
+    pub born: usize,
+    pub died: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Cells<const N: usize> {
+    // All the cells inside the bounds, the ones outside having the background state.
+    Dense { size: [usize; N], states: Vec<bool> },
+    // Only the cells that differ from the background.
+    Sparse(FxHashSet<Point<N>>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton<const N: usize> {
+    neighborhood: Neighborhood<N>,
+    rule: Rule,
+    cells: Cells<N>,
+    background: bool,
+    generation: usize,
+}
+
+impl<const N: usize> Automaton<N> {
+    fn new(neighborhood: Neighborhood<N>, rule: Rule, cells: Cells<N>) -> Self {
+        if let (Rule::Table(table), Some(len)) = (&rule, neighborhood.len()) {
+            assert_eq!(
+                table.len(),
+                1 << len,
+                "Rule table doesn't match the neighborhood"
+            );
+        }
+        Self {
+            neighborhood,
+            rule,
+            cells,
+            background: false,
+            generation: 0,
+        }
+    }
+
+    /// An automaton on the cells with coordinates from 0 to `size` excluded, all dead.
+    pub fn dense(size: [usize; N], neighborhood: Neighborhood<N>, rule: Rule) -> Self {
+        let states = vec![false; size.iter().product()];
+        Self::new(neighborhood, rule, Cells::Dense { size, states })
+    }
+
+    /// An automaton on an infinite grid, all dead.
+    pub fn sparse(neighborhood: Neighborhood<N>, rule: Rule) -> Self {
+        Self::new(neighborhood, rule, Cells::Sparse(FxHashSet::default()))
+    }
+
+    /// Sets the state of the cells that were never set.
+    #[must_use]
+    pub fn with_background(mut self, alive: bool) -> Self {
+        self.background = alive;
+        self
+    }
+
+    /// Sets the cells from a text, each line being a row.
+    /// The points are `[row, col, 0, ...]`, so N must be at least 2.
+    pub fn set_text(&mut self, input: &str, alive: char) {
+        for (row, line) in input.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                let mut p = [0; N];
+                p[0] = i32::try_from(row).unwrap();
+                p[1] = i32::try_from(col).unwrap();
+                self.set(p, c == alive);
+            }
+        }
+    }
+
+    fn index(size: &[usize; N], p: &Point<N>) -> Option<usize> {
+        let mut index = 0;
+        for d in 0..N {
+            let v = usize::try_from(p[d]).ok().filter(|v| *v < size[d])?;
+            index = index * size[d] + v;
+        }
+        Some(index)
+    }
+
+    fn point(size: &[usize; N], mut index: usize) -> Point<N> {
+        let mut p = [0; N];
+        for d in (0..N).rev() {
+            p[d] = i32::try_from(index % size[d]).unwrap();
+            index /= size[d];
+        }
+        p
+    }
+
+    pub fn get(&self, p: &Point<N>) -> bool {
+        match &self.cells {
+            Cells::Dense { size, states } => {
+                Self::index(size, p).map_or(self.background, |index| states[index])
+            }
+            Cells::Sparse(cells) => cells.contains(p) != self.background,
+        }
+    }
+
+    /// Sets the state of a cell. On a dense automaton, the cell must be inside the bounds.
+    pub fn set(&mut self, p: Point<N>, alive: bool) {
+        match &mut self.cells {
+            Cells::Dense { size, states } => {
+                let index = Self::index(size, &p).expect("Cell outside of the automaton");
+                states[index] = alive;
+            }
+            Cells::Sparse(cells) => {
+                if alive == self.background {
+                    cells.remove(&p);
+                } else {
+                    cells.insert(p);
+                }
+            }
+        }
+    }
+
+    /// The bounds of a dense automaton, None for a sparse one.
+    pub fn size(&self) -> Option<[usize; N]> {
+        match &self.cells {
+            Cells::Dense { size, .. } => Some(*size),
+            Cells::Sparse(_) => None,
+        }
+    }
+
+    /// State of the cells that were never set.
+    pub fn background(&self) -> bool {
+        self.background
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Number of alive cells, None if infinite.
+    pub fn alive_count(&self) -> Option<usize> {
+        if self.background {
+            return None;
+        }
+        Some(match &self.cells {
+            Cells::Dense { states, .. } => states.iter().filter(|s| **s).count(),
+            Cells::Sparse(cells) => cells.len(),
+        })
+    }
+
+    fn next_state(&self, p: &Point<N>) -> bool {
+        let alive = self.get(p);
+        match &self.neighborhood {
+            Neighborhood::Offsets(offsets) => self.rule.next(
+                alive,
+                offsets.iter().map(|offset| self.get(&add(p, offset))),
+            ),
+            Neighborhood::Fn(neighbors) => self
+                .rule
+                .next(alive, neighbors(p).iter().map(|n| self.get(n))),
+        }
+    }
+
+    /// Computes the next generation.
+    pub fn step(&mut self) -> Stats {
+        let next_background = self.neighborhood.len().map_or(self.background, |len| {
+            self.rule
+                .next(self.background, std::iter::repeat_n(self.background, len))
+        });
+        let mut born = 0;
+        let mut died = 0;
+        let mut count = |old: bool, new: bool| {
+            if old != new {
+                if new {
+                    born += 1;
+                } else {
+                    died += 1;
+                }
+            }
+        };
+
+        let cells = match &self.cells {
+            Cells::Dense { size, states } => {
+                let next_states = states
+                    .iter()
+                    .enumerate()
+                    .map(|(index, old)| {
+                        let new = self.next_state(&Self::point(size, index));
+                        count(*old, new);
+                        new
+                    })
+                    .collect();
+                Cells::Dense {
+                    size: *size,
+                    states: next_states,
+                }
+            }
+            Cells::Sparse(cells) => {
+                // Only cells with a neighbor different from the background can differ from it.
+                let mut candidates = FxHashSet::default();
+                for p in cells {
+                    candidates.insert(*p);
+                    match &self.neighborhood {
+                        Neighborhood::Offsets(offsets) => {
+                            candidates.extend(offsets.iter().map(|offset| sub(p, offset)));
+                        }
+                        // The relation being symmetric, the cells having p as neighbor are its neighbors.
+                        Neighborhood::Fn(neighbors) => candidates.extend(neighbors(p)),
+                    }
+                }
+                let next_cells = candidates
+                    .into_iter()
+                    .filter(|p| {
+                        let old = self.get(p);
+                        let new = self.next_state(p);
+                        if old != self.background || new != next_background {
+                            count(old, new);
+                        }
+                        new != next_background
+                    })
+                    .collect();
+                Cells::Sparse(next_cells)
+            }
+        };
+
+        self.cells = cells;
+        self.background = next_background;
+        self.generation += 1;
+        Stats {
+            generation: self.generation,
+            alive: self.alive_count(),
+            born,
+            died,
+        }
+    }
+
+    /// Computes that many generations, returning the stats of each.
+    pub fn run(&mut self, generations: usize) -> Vec<Stats> {
+        (0..generations).map(|_| self.step()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLIDER: &str = ".#.\n..#\n###";
+
+    #[test]
+    fn test_neighborhoods() {
+        let Neighborhood::Offsets(block) = Neighborhood::<2>::block() else {
+            panic!("Block without offsets");
+        };
+        assert_eq!(block[..4], [[-1, -1], [-1, 0], [-1, 1], [0, -1]]);
+        assert_eq!(Neighborhood::<2>::moore().len(), Some(8));
+        assert_eq!(Neighborhood::<4>::moore().len(), Some(80));
+        assert_eq!(Neighborhood::<3>::von_neumann().len(), Some(6));
+        assert_eq!(Neighborhood::hex().len(), Some(6));
+        assert_eq!(Neighborhood::<1>::from_fn(|p| vec![*p]).len(), None);
+    }
+
+    #[test]
+    fn test_life() {
+        let mut sparse = Automaton::sparse(Neighborhood::moore(), Rule::life());
+        sparse.set_text(GLIDER, '#');
+        let mut dense = Automaton::dense([6, 6], Neighborhood::moore(), Rule::life());
+        dense.set_text(GLIDER, '#');
+        assert_eq!(sparse.size(), None);
+        assert_eq!(dense.size(), Some([6, 6]));
+
+        let stats = sparse.step();
+        assert_eq!(stats, dense.step());
+        assert_eq!(
+            stats,
+            Stats {
+                generation: 1,
+                alive: Some(5),
+                born: 2,
+                died: 2
+            }
+        );
+        // After 4 generations, the glider moved by one cell diagonally.
+        sparse.run(3);
+        dense.run(3);
+        let mut moved = Automaton::sparse(Neighborhood::moore(), Rule::life());
+        moved.set_text("...\n..#.\n...#\n.###", '#');
+        for row in 0..6 {
+            for col in 0..6 {
+                assert_eq!(sparse.get(&[row, col]), moved.get(&[row, col]));
+                assert_eq!(dense.get(&[row, col]), moved.get(&[row, col]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_toggling_background() {
+        // Each cell becomes the opposite of the majority of its block, so empty areas light up.
+        let table = (0..512).map(|i: u32| i.count_ones() < 5).collect();
+        let mut automaton = Automaton::sparse(Neighborhood::<2>::block(), Rule::Table(table));
+        automaton.set_text("###\n###\n###", '#');
+        // Only the corners of the block stay alive, like the background.
+        let stats = automaton.step();
+        assert!(automaton.background());
+        assert_eq!(stats.alive, None);
+        assert_eq!((stats.born, stats.died), (0, 5));
+        assert!(!automaton.get(&[1, 1]));
+        // The center is the only cell with more dead cells than alive in its block.
+        automaton.step();
+        assert!(!automaton.background());
+        assert_eq!(automaton.alive_count(), Some(1));
+        assert!(automaton.get(&[1, 1]));
+    }
+
+    #[test]
+    fn test_neighbors_fn() {
+        // A ring of 5 cells, where a cell is born next to exactly one alive cell and never survives.
+        let ring = || Neighborhood::from_fn(|&[i]| vec![[(i + 1) % 5], [(i + 4) % 5]]);
+        let mut sparse = Automaton::sparse(ring(), Rule::parse("B1/S"));
+        sparse.set([0], true);
+        let mut dense = Automaton::dense([5], ring(), Rule::parse("B1/S"));
+        dense.set([0], true);
+        assert_eq!(sparse.run(3), dense.run(3));
+        for i in 0..5 {
+            assert_eq!(sparse.get(&[i]), i == 1 || i == 4);
+            assert_eq!(dense.get(&[i]), i == 1 || i == 4);
+        }
+        assert!(!sparse.background());
+    }
+
+    #[test]
+    fn test_hex() {
+        // B2/S12 on a line of 3 hexagons: They all survive, and the 4 cells touching 2 of them are born.
+        let mut automaton = Automaton::sparse(Neighborhood::hex(), Rule::parse("B2/S12"));
+        for q in 0..3 {
+            automaton.set([q, 0], true);
+        }
+        let stats = automaton.step();
+        assert_eq!(stats.born, 4);
+        assert_eq!(stats.died, 0);
+        assert!(automaton.get(&[1, -1]));
+        assert!(automaton.get(&[0, 1]));
+        assert!(automaton.get(&[1, 0]));
+        assert!(!automaton.get(&[0, -1]));
+    }
+}
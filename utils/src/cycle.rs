@@ -0,0 +1,211 @@
+//! Cycle detection, to know the state of a simulation after a huge number of steps.
+//!
+//! Many puzzles ask for the result after 1 billion steps or more, which is only possible
+//! because the simulation ends up repeating itself. Once the cycle is found, we can jump ahead.
+//!
+//! The step function modifies the state in place. Detection is done by storing a key for each state
+//! in a hash map: The full state, or only what matters, like the top of a tower or a pattern ignoring its position.
+//! With [`metric_at`], a value derived from the state that grows by the same amount at each cycle,
+//! like a height or a sum, is extrapolated linearly.
+//!
+//! [`brent`] finds a cycle without storing the states, but needs to run the simulation again.
+//!
+//! ```text
+//! let grid = cycle::state_at(grid, |g| *g = spin(g), 1_000_000_000);
+//! let height = cycle::metric_at(tower, drop_rock, |t| t.top_key(), |t| t.height(), 1_000_000_000_000);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The states starting at step `start` repeat every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step that has the same state as step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+// Runs the simulation until a key repeats, without going past step `max_steps`.
+// `visit` is called on each state, including the initial one.
+// When a cycle is found, the state is left at step `start + length`, otherwise at `max_steps`.
+fn detect<T, K: Eq + Hash>(
+    state: &mut T,
+    step: &mut impl FnMut(&mut T),
+    key: impl Fn(&T) -> K,
+    max_steps: usize,
+    mut visit: impl FnMut(&T),
+) -> Option<Cycle> {
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    for i in 0..=max_steps {
+        visit(state);
+        if let Some(start) = seen.insert(key(state), i) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+        if i < max_steps {
+            step(state);
+        }
+    }
+    None
+}
+
+/// Finds the cycle by storing the key of each state in a hash map.
+/// Runs forever if there is no cycle.
+pub fn find_cycle<T, K: Eq + Hash>(
+    mut state: T,
+    mut step: impl FnMut(&mut T),
+    key: impl Fn(&T) -> K,
+) -> Cycle {
+    detect(&mut state, &mut step, key, usize::MAX, |_| {}).expect("No cycle found")
+}
+
+/// The state after `n` steps.
+pub fn state_at<T: Clone + Eq + Hash>(state: T, step: impl FnMut(&mut T), n: usize) -> T {
+    state_at_by(state, step, T::clone, n)
+}
+
+/// The state after `n` steps, detecting the cycle on a key of the state.
+/// Two states with the same key must evolve the same way.
+pub fn state_at_by<T, K: Eq + Hash>(
+    mut state: T,
+    mut step: impl FnMut(&mut T),
+    key: impl Fn(&T) -> K,
+    n: usize,
+) -> T {
+    if let Some(cycle) = detect(&mut state, &mut step, key, n, |_| {}) {
+        // We are at step start + length, which is like step start.
+        let current = cycle.start + cycle.length;
+        for _ in 0..(n - current) % cycle.length {
+            step(&mut state);
+        }
+    }
+    state
+}
+
+/// A metric of the state after `n` steps.
+///
+/// The metric must grow by the same amount at each cycle, which allows the states
+/// to be only equivalent through their key, for example a pattern that moves.
+pub fn metric_at<T, K: Eq + Hash>(
+    mut state: T,
+    mut step: impl FnMut(&mut T),
+    key: impl Fn(&T) -> K,
+    metric: impl Fn(&T) -> i64,
+    n: usize,
+) -> i64 {
+    let mut metrics = Vec::new();
+    let Some(cycle) = detect(&mut state, &mut step, key, n, |s| metrics.push(metric(s))) else {
+        return metrics[n];
+    };
+    let current = cycle.start + cycle.length;
+    let delta = metrics[current] - metrics[cycle.start];
+    let cycles_left = (n - current) / cycle.length;
+    let remaining = (n - current) % cycle.length;
+    metrics[cycle.start + remaining] + i64::try_from(cycles_left + 1).unwrap() * delta
+}
+
+/// Finds the cycle with [Brent's algorithm](https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm),
+/// which only keeps a few states in memory.
+/// Runs forever if there is no cycle.
+pub fn brent<T: Clone + PartialEq>(initial: &T, mut step: impl FnMut(&mut T)) -> Cycle {
+    // Finding the length, with the hare going ahead by powers of 2.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    step(&mut hare);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        step(&mut hare);
+        length += 1;
+    }
+
+    // Finding the start, with the hare staying `length` steps ahead.
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        step(&mut hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        step(&mut tortoise);
+        step(&mut hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// The state after `n` steps, without detecting any cycle.
+pub fn iterate<T>(mut state: T, mut step: impl FnMut(&mut T), n: usize) -> T {
+    for _ in 0..n {
+        step(&mut state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, 3, 4, 5, 6, 7, then 3 again: Cycle of length 5 starting at 3.
+    fn step(x: &mut u32) {
+        *x = if *x == 7 { 3 } else { *x + 1 };
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let cycle = Cycle {
+            start: 3,
+            length: 5,
+        };
+        assert_eq!(find_cycle(0, step, |x| *x), cycle);
+        assert_eq!(brent(&0, step), cycle);
+        assert_eq!(cycle.equivalent_step(2), 2);
+        assert_eq!(cycle.equivalent_step(8), 3);
+        assert_eq!(cycle.equivalent_step(1_000_000_000), 5);
+    }
+
+    #[test]
+    fn test_state_at() {
+        for n in 0..30 {
+            assert_eq!(state_at(0, step, n), iterate(0, step, n));
+        }
+        assert_eq!(state_at(0, step, 1_000_000_000), 5);
+        // Step limit reached before the cycle is found.
+        assert_eq!(state_at(0, |x| *x += 1, 1000), 1000);
+    }
+
+    #[test]
+    fn test_metric_at() {
+        // A pattern moving right: The key is its shape, the metric its position.
+        let move_right = |(pos, len): &mut (i64, i64)| {
+            *pos += 1 + (*len % 2);
+            *len = (*len + 1) % 3;
+        };
+        for n in 0..30 {
+            let (pos, _) = iterate((10, 0), move_right, n);
+            assert_eq!(metric_at((10, 0), move_right, |s| s.1, |s| s.0, n), pos);
+        }
+        assert_eq!(
+            metric_at((10, 0), move_right, |s| s.1, |s| s.0, 3_000_000_000),
+            4_000_000_010
+        );
+    }
+}
@@ -76,7 +76,7 @@ impl Grid {
             North => pos < self.cols,
             East => pos % self.cols == self.cols - 1,
             South => pos / self.cols == self.rows - 1,
-            West => pos % self.cols == 0,
+            West => pos.is_multiple_of(self.cols),
         }
     }
 
@@ -119,7 +119,7 @@ impl Grid {
                 ((pos % self.cols) as isize + d_col) as usize,
             )
         })
-        .filter(|&(row, col)| (row < self.rows && col < self.cols))
+        .filter(|&(row, col)| row < self.rows && col < self.cols)
         .map(|(row, col)| row * self.cols + col)
         .collect()
     }
@@ -135,7 +135,7 @@ impl Grid {
                     ((pos % self.cols) as isize + d_col) as usize,
                 )
             })
-            .filter(|&(row, col)| (row < self.rows && col < self.cols))
+            .filter(|&(row, col)| row < self.rows && col < self.cols)
             .map(|(row, col)| row * self.cols + col)
     }
 }
@@ -4,12 +4,18 @@
 
 mod ansi;
 mod array_wrapping;
+mod automaton;
+mod cycle;
 mod dijkstra;
 mod direction;
 mod grid;
 mod grow_vec;
+mod jigsaw;
 mod map_in_hashmap;
 mod md5;
+mod ocr;
 mod overlapping_ranges;
 mod parsing;
 mod pipe;
+mod point_cloud;
+mod render;
@@ -0,0 +1,186 @@
+//! Points moving in straight lines at constant speed, which at some point form a message
+//! (2018 day 10) or a picture (2024 day 14).
+//!
+//! The message appears when the points are the most grouped. Without wrapping, the width and height
+//! of the bounding box are convex functions of time, so their sum has a single minimum that
+//! a ternary search finds.
+//!
+//! When the points wrap around a grid, the bounding box doesn't help. Instead each axis is
+//! periodic, with the grid width and height as periods: we look in each period for the time
+//! with the smallest variance, and combine both with the Chinese remainder theorem.
+
+use crate::ocr::{self, OcrError};
+
+/// Position and velocity, each as (x, y).
+pub type Point = ((i64, i64), (i64, i64));
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointCloud {
+    positions: Vec<(i64, i64)>,
+    velocities: Vec<(i64, i64)>,
+}
+
+impl PointCloud {
+    pub fn new(points: &[Point]) -> Self {
+        Self {
+            positions: points.iter().map(|p| p.0).collect(),
+            velocities: points.iter().map(|p| p.1).collect(),
+        }
+    }
+
+    pub fn positions_at(&self, time: i64) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.positions
+            .iter()
+            .zip(&self.velocities)
+            .map(move |(p, v)| (p.0 + v.0 * time, p.1 + v.1 * time))
+    }
+
+    /// Positions when wrapping around a grid of this size.
+    pub fn wrapped_positions_at(
+        &self,
+        time: i64,
+        width: i64,
+        height: i64,
+    ) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.positions_at(time)
+            .map(move |(x, y)| (x.rem_euclid(width), y.rem_euclid(height)))
+    }
+
+    /// Bounding box as ((min x, min y), (max x, max y)).
+    pub fn bounding_box_at(&self, time: i64) -> ((i64, i64), (i64, i64)) {
+        self.positions_at(time).fold(
+            ((i64::MAX, i64::MAX), (i64::MIN, i64::MIN)),
+            |(min, max), (x, y)| ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y))),
+        )
+    }
+
+    // Half perimeter of the bounding box, convex over time.
+    fn spread_at(&self, time: i64) -> i64 {
+        let (min, max) = self.bounding_box_at(time);
+        (max.0 - min.0) + (max.1 - min.1)
+    }
+
+    /// Time (not negative) at which the points are the closest together.
+    pub fn most_grouped_time(&self) -> i64 {
+        // First finding an upper bound, where the spread increases again.
+        let mut high = 1;
+        while self.spread_at(high + 1) < self.spread_at(high) {
+            high *= 2;
+        }
+        let mut low = 0;
+        high += 1;
+        while high - low > 2 {
+            let m1 = low + (high - low) / 3;
+            let m2 = high - (high - low) / 3;
+            if self.spread_at(m1) <= self.spread_at(m2) {
+                high = m2;
+            } else {
+                low = m1;
+            }
+        }
+        (low..=high).min_by_key(|t| self.spread_at(*t)).unwrap()
+    }
+
+    /// Renders the points with `#`, in their bounding box.
+    pub fn render_at(&self, time: i64) -> String {
+        let (min, max) = self.bounding_box_at(time);
+        let width = usize::try_from(max.0 - min.0 + 1).unwrap();
+        let height = usize::try_from(max.1 - min.1 + 1).unwrap();
+        let mut rows = vec![vec!['.'; width]; height];
+        for (x, y) in self.positions_at(time) {
+            rows[usize::try_from(y - min.1).unwrap()][usize::try_from(x - min.0).unwrap()] = '#';
+        }
+        rows.iter()
+            .map(|r| r.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Finds the time when the points are grouped, and reads the message they form then.
+    pub fn read_message(&self) -> (i64, Result<String, OcrError>) {
+        let time = self.most_grouped_time();
+        (time, ocr::recognize_points(self.positions_at(time)))
+    }
+
+    // Sum of the squared distances to the mean, times the number of points, to stay with integers.
+    fn variance(values: impl Iterator<Item = i64>) -> i64 {
+        let values: Vec<i64> = values.collect();
+        let n = i64::try_from(values.len()).unwrap();
+        let sum: i64 = values.iter().sum();
+        values.iter().map(|v| (v * n - sum).pow(2)).sum::<i64>() / n
+    }
+
+    /// Time at which the points are the most grouped when wrapping around a grid of this size.
+    /// The width and height need to be coprime, which they are in the puzzles.
+    pub fn most_grouped_wrapped_time(&self, width: i64, height: i64) -> i64 {
+        let best = |period: i64, axis: fn((i64, i64)) -> i64| {
+            (0..period)
+                .min_by_key(|t| {
+                    Self::variance(self.wrapped_positions_at(*t, width, height).map(axis))
+                })
+                .unwrap()
+        };
+        let tx = best(width, |p| p.0);
+        let ty = best(height, |p| p.1);
+        // Chinese remainder theorem: t = tx mod width and t = ty mod height.
+        (0..height)
+            .map(|k| tx + k * width)
+            .find(|t| t % height == ty)
+            .expect("Width and height must be coprime")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The "H" of the 10 rows font, with points moving so that they are grouped at time 7.
+    fn letter_h() -> PointCloud {
+        let rows = [
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+            "#....#", "#....#",
+        ];
+        let letter: Vec<(i64, i64)> = rows
+            .iter()
+            .zip(0..)
+            .flat_map(|(row, y)| {
+                row.chars()
+                    .zip(0..)
+                    .filter(|(c, _)| *c == '#')
+                    .map(move |(_, x)| (x, y))
+            })
+            .collect();
+        // Velocities unrelated to the positions, with both signs.
+        let points: Vec<Point> = letter
+            .iter()
+            .zip(0..)
+            .map(|(&(x, y), i)| {
+                let v = ((i * 3) % 7 - 3, (i * 5 + 2) % 7 - 3);
+                ((x - v.0 * 7, y - v.1 * 7), v)
+            })
+            .collect();
+        PointCloud::new(&points)
+    }
+
+    #[test]
+    fn test_read_message() {
+        let cloud = letter_h();
+        assert_eq!(cloud.most_grouped_time(), 7);
+        assert_eq!(cloud.read_message(), (7, Ok("H".to_string())));
+        assert!(cloud.render_at(7).starts_with("#....#\n#....#\n"));
+    }
+
+    #[test]
+    fn test_most_grouped_wrapped_time() {
+        let (width, height) = (13, 11);
+        let cloud = letter_h();
+        // Moving the letter in a grid, where it wraps.
+        let wrapped = PointCloud::new(
+            &cloud
+                .wrapped_positions_at(-40, width, height)
+                .zip(cloud.velocities.iter().copied())
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(wrapped.most_grouped_wrapped_time(width, height), 47);
+    }
+}
@@ -0,0 +1,406 @@
+//! Export of grids as images, to look at large simulations outside of the terminal
+//! and to compare frames in tests.
+//!
+//! Images are written as [PPM](https://netpbm.sourceforge.net/doc/ppm.html), the simplest format,
+//! or as [PNG](https://www.w3.org/TR/png/). To avoid any dependency, the PNG data isn't compressed.
+//!
+//! ```text
+//! let palette = Palette::new([0, 0, 0]).with('#', [200, 200, 200]).with('~', [30, 60, 220]);
+//! let image = Image::from_grid(grid.rows, grid.cols, |row, col| grid.get(row, col), &palette, 2);
+//! image.save("grid.png")?;
+//!
+//! let mut frames = FrameSequence::new("frames", "sand", Format::Png)?;
+//! frames.add(&image)?;  // frames/sand_0000.png
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub type Rgb = [u8; 3];
+
+/// Colors of the cells.
+#[derive(Debug, Clone)]
+pub struct Palette<T> {
+    colors: Vec<(T, Rgb)>,
+    default: Rgb,
+}
+
+impl<T: PartialEq> Palette<T> {
+    /// Palette where all the cells have the default color.
+    pub fn new(default: Rgb) -> Self {
+        Self {
+            colors: Vec::new(),
+            default,
+        }
+    }
+
+    pub fn with(mut self, cell: T, color: Rgb) -> Self {
+        self.colors.retain(|(c, _)| *c != cell);
+        self.colors.push((cell, color));
+        self
+    }
+
+    pub fn color(&self, cell: &T) -> Rgb {
+        self.colors
+            .iter()
+            .find(|(c, _)| c == cell)
+            .map_or(self.default, |(_, color)| *color)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Ppm,
+    Png,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+        }
+    }
+}
+
+/// An RGB image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, color: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![color; width * height],
+        }
+    }
+
+    /// Draws a grid, each cell being a square of `scale` x `scale` pixels.
+    pub fn from_grid<T: PartialEq>(
+        rows: usize,
+        cols: usize,
+        cell: impl Fn(usize, usize) -> T,
+        palette: &Palette<T>,
+        scale: usize,
+    ) -> Self {
+        let mut image = Self::new(cols * scale, rows * scale, palette.default);
+        for row in 0..rows {
+            for col in 0..cols {
+                let color = palette.color(&cell(row, col));
+                for y in row * scale..(row + 1) * scale {
+                    image.pixels
+                        [y * image.width + col * scale..y * image.width + (col + 1) * scale]
+                        .fill(color);
+                }
+            }
+        }
+        image
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        self.pixels[y * self.width + x] = color;
+    }
+
+    /// Positions (x, y) of the pixels that are different in the other image.
+    /// Images must have the same size.
+    pub fn diff(&self, other: &Image) -> Vec<(usize, usize)> {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "Images have different sizes"
+        );
+        self.pixels
+            .iter()
+            .zip(&other.pixels)
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(i, _)| (i % self.width, i / self.width))
+            .collect()
+    }
+
+    /// Binary PPM (P6).
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut data = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        data.extend(self.pixels.iter().flatten());
+        data
+    }
+
+    /// Reads a binary PPM, as written by `to_ppm`.
+    pub fn from_ppm(data: &[u8]) -> Result<Self, String> {
+        // Header is 4 fields separated by whitespace, then a single whitespace before the pixels.
+        let mut fields = Vec::new();
+        let mut pos = 0;
+        while fields.len() < 4 {
+            while data.get(pos).is_some_and(u8::is_ascii_whitespace) {
+                pos += 1;
+            }
+            let start = pos;
+            while data.get(pos).is_some_and(|c| !c.is_ascii_whitespace()) {
+                pos += 1;
+            }
+            if start == pos {
+                return Err("Truncated PPM header".to_string());
+            }
+            fields.push(String::from_utf8_lossy(&data[start..pos]).to_string());
+        }
+        if fields[0] != "P6" || fields[3] != "255" {
+            return Err("Only binary PPM with 8 bits colors is supported".to_string());
+        }
+        let width: usize = fields[1].parse().map_err(|_| "Invalid PPM width")?;
+        let height: usize = fields[2].parse().map_err(|_| "Invalid PPM height")?;
+        let pixels = data.get(pos + 1..).unwrap_or_default();
+        if pixels.len() != width * height * 3 {
+            return Err(format!(
+                "Expected {} bytes of pixels, got {}",
+                width * height * 3,
+                pixels.len()
+            ));
+        }
+        Ok(Self {
+            width,
+            height,
+            pixels: pixels.chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect(),
+        })
+    }
+
+    /// PNG, with uncompressed data.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend(u32::try_from(self.width).unwrap().to_be_bytes());
+        header.extend(u32::try_from(self.height).unwrap().to_be_bytes());
+        // Bit depth 8, color type RGB, default compression, filter and no interlace.
+        header.extend([8, 2, 0, 0, 0]);
+
+        // Each line starts with the filter type, 0 for none.
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for line in self.pixels.chunks(self.width.max(1)) {
+            raw.push(0);
+            raw.extend(line.iter().flatten());
+        }
+
+        let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut data, *b"IHDR", &header);
+        png_chunk(&mut data, *b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut data, *b"IEND", &[]);
+        data
+    }
+
+    /// Saves the image, in the format given by the file extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let data = match path.extension().and_then(|e| e.to_str()) {
+            Some("ppm") => self.to_ppm(),
+            Some("png") => self.to_png(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Unsupported image format: {}", path.display()),
+                ))
+            }
+        };
+        fs::write(path, data)
+    }
+}
+
+/// Writes numbered frames in a directory: `prefix_0000.png`, `prefix_0001.png`, etc.
+///
+/// They can be turned into an animation with `ffmpeg -i prefix_%04d.png anim.gif` for example.
+pub struct FrameSequence {
+    dir: PathBuf,
+    prefix: String,
+    format: Format,
+    count: usize,
+}
+
+impl FrameSequence {
+    /// Creates the directory if needed.
+    pub fn new(dir: impl AsRef<Path>, prefix: &str, format: Format) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+            prefix: prefix.to_string(),
+            format,
+            count: 0,
+        })
+    }
+
+    pub fn path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!(
+            "{}_{index:04}.{}",
+            self.prefix,
+            self.format.extension()
+        ))
+    }
+
+    /// Number of frames written.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Writes the next frame, returning its path.
+    pub fn add(&mut self, image: &Image) -> io::Result<PathBuf> {
+        let path = self.path(self.count);
+        image.save(&path)?;
+        self.count += 1;
+        Ok(path)
+    }
+}
+
+fn png_chunk(data: &mut Vec<u8>, kind: [u8; 4], content: &[u8]) {
+    data.extend(u32::try_from(content.len()).unwrap().to_be_bytes());
+    let start = data.len();
+    data.extend(kind);
+    data.extend(content);
+    let crc = crc32(&data[start..]);
+    data.extend(crc.to_be_bytes());
+}
+
+// zlib stream made of deflate blocks without compression.
+fn zlib_stored(raw: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65535;
+    let mut data = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if raw.is_empty() {
+        vec![&[]]
+    } else {
+        raw.chunks(MAX_BLOCK).collect()
+    };
+    for (i, block) in blocks.iter().enumerate() {
+        // Only the final block has its first bit set, and type 0 is no compression.
+        data.push(u8::from(i == blocks.len() - 1));
+        let len = u16::try_from(block.len()).unwrap();
+        data.extend(len.to_le_bytes());
+        data.extend((!len).to_le_bytes());
+        data.extend(*block);
+    }
+    data.extend(adler32(raw).to_be_bytes());
+    data
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1, 0);
+    for byte in data {
+        a = (a + u32::from(*byte)) % MOD;
+        b = (b + a) % MOD;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Rgb = [0, 0, 0];
+    const WHITE: Rgb = [255, 255, 255];
+    const BLUE: Rgb = [0, 0, 255];
+
+    fn grid_image(grid: &str, scale: usize) -> Image {
+        let lines: Vec<&[u8]> = grid.lines().map(str::as_bytes).collect();
+        let palette = Palette::new(BLACK).with(b'#', WHITE).with(b'~', BLUE);
+        Image::from_grid(
+            lines.len(),
+            lines[0].len(),
+            |row, col| lines[row][col],
+            &palette,
+            scale,
+        )
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let image = grid_image("#.\n.~", 2);
+        assert_eq!((image.width, image.height), (4, 4));
+        assert_eq!(image.get(0, 0), WHITE);
+        assert_eq!(image.get(1, 1), WHITE);
+        assert_eq!(image.get(2, 1), BLACK);
+        assert_eq!(image.get(3, 3), BLUE);
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = grid_image("#..\n...", 1);
+        let after = grid_image("#.~\n.#.", 1);
+        assert_eq!(before.diff(&after), [(2, 0), (1, 1)]);
+        assert!(after.diff(&after).is_empty());
+    }
+
+    #[test]
+    fn test_ppm() {
+        let image = grid_image("#.~", 1);
+        let ppm = image.to_ppm();
+        assert_eq!(ppm[..11], *b"P6\n3 1\n255\n");
+        assert_eq!(ppm[11..], [255, 255, 255, 0, 0, 0, 0, 0, 255]);
+        assert_eq!(Image::from_ppm(&ppm), Ok(image));
+        assert!(Image::from_ppm(b"P6\n3 1\n255\n\x00").is_err());
+        assert!(Image::from_ppm(b"P3\n1 1\n255\n0 0 0").is_err());
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn test_png() {
+        // Big enough to need several deflate blocks.
+        let image = Image::new(200, 150, BLUE);
+        let png = image.to_png();
+        assert_eq!(png[..8], *b"\x89PNG\r\n\x1a\n");
+        assert_eq!(png[12..16], *b"IHDR");
+        assert_eq!(png[16..24], [0, 0, 0, 200, 0, 0, 0, 150]);
+        assert_eq!(png[png.len() - 12..], *b"\0\0\0\0IEND\xae\x42\x60\x82");
+
+        let raw_len: usize = (200 * 3 + 1) * 150;
+        let blocks = raw_len.div_ceil(65535);
+        let idat_len = 2 + raw_len + blocks * 5 + 4;
+        assert_eq!(png.len(), 8 + 25 + 12 + idat_len + 12);
+    }
+
+    #[test]
+    fn test_frame_sequence() {
+        let dir = std::env::temp_dir().join(format!("render_test_{}", std::process::id()));
+        let mut frames = FrameSequence::new(&dir, "grid", Format::Ppm).unwrap();
+        assert!(frames.is_empty());
+        let first = grid_image("#.", 1);
+        let second = grid_image("~#", 1);
+        assert_eq!(frames.add(&first).unwrap(), dir.join("grid_0000.ppm"));
+        assert_eq!(frames.add(&second).unwrap(), dir.join("grid_0001.ppm"));
+        assert_eq!(frames.len(), 2);
+
+        let read = |i| Image::from_ppm(&fs::read(frames.path(i)).unwrap()).unwrap();
+        assert_eq!(read(0), first);
+        assert_eq!(read(0).diff(&read(1)), [(0, 0), (1, 0)]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,7 +1,8 @@
 use std::fs;
 
-use virtual_cpu::instruction::{build_list, Condition, Instruction};
-use virtual_cpu::intchar::IntChar;
+use virtual_cpu::c_code::gen::get_c_code;
+use virtual_cpu::instruction::{build_list, Instruction};
+use virtual_cpu::optimizer::optimize;
 use virtual_cpu::parsing::char;
 use virtual_cpu::registers::Registers;
 use virtual_cpu::run_utils;
@@ -15,76 +16,103 @@ fn build_instruction(s: &str) -> Instruction {
 }
 
 // Executes the instruction specified by ins, modifying the registers if needed.
-fn execute(instructions: &mut Vec<Instruction>, ir: &mut usize, regs: &mut Registers<i64>) {
-    let ins = &instructions[*ir];
+// Toggling is done on the original instructions, while the others are executed from the optimized list.
+// Returns true if an instruction was toggled.
+fn execute(
+    instructions: &mut [Instruction],
+    optimized_ins: &[Instruction],
+    ir: &mut usize,
+    regs: &mut Registers<i64>,
+) -> bool {
+    let ins = &optimized_ins[*ir];
     if let Instruction::Toggle(offset) = ins {
         let ir_to_toggle = (*ir as i64 + regs.get(*offset)) as usize;
         if ir_to_toggle < instructions.len() {
-            instructions[ir_to_toggle] = match &instructions[ir_to_toggle] {
-                Instruction::Set(r, x) => {
-                    Instruction::JumpIf(Condition::NotZero, *x, IntChar::Char(*r), |v| v != 0)
-                }
-                Instruction::Add(r, _) => Instruction::Sub(*r, IntChar::from(1)),
-                Instruction::Sub(r, _) => Instruction::Add(*r, IntChar::from(1)),
-                Instruction::JumpIf(_, v, o, _) => match o {
-                    IntChar::Integer(_) => Instruction::Nop,
-                    IntChar::Char(r) => Instruction::Set(*r, *v),
-                },
-                Instruction::Toggle(offset) => Instruction::Add(*offset, IntChar::from(1)),
-                // Instruction::Mult(a, b, r) => Instruction::Mult(*a, *b, *r),
-                _ => ins.clone(),
-            };
+            instructions[ir_to_toggle] = instructions[ir_to_toggle].toggle();
+            *ir += 1;
+            return true;
         }
         *ir += 1;
     } else {
         ins.execute(ir, regs);
     }
+    false
 }
 
 fn execute_all(instructions: &[Instruction], regs: &mut Registers<i64>) {
     let mut modifiable_ins: Vec<Instruction> = instructions.to_vec();
-    // optimizer(&mut modifiable_ins);
+    let mut optimized_ins = optimize(&modifiable_ins);
 
     let mut ir = 0;
     while ir < modifiable_ins.len() {
-        execute(&mut modifiable_ins, &mut ir, regs);
+        if execute(&mut modifiable_ins, &optimized_ins, &mut ir, regs) {
+            optimized_ins = optimize(&modifiable_ins);
+        }
     }
 }
 
-fn value_sent_to_safe(instructions: &[Instruction]) -> i64 {
+fn value_sent_to_safe(instructions: &[Instruction], eggs: i64) -> i64 {
     let mut regs = Registers::new();
-    regs.set('a', 7);
+    regs.set('a', eggs);
     execute_all(instructions, &mut regs);
     regs.get('a')
 }
 
 pub fn part1(input: &str) -> String {
     let instructions = build_list(input, build_instruction);
-    value_sent_to_safe(&instructions).to_string()
+    value_sent_to_safe(&instructions, 7).to_string()
 }
 
-// Cannot do C version, as toggle cannot be supported.
+pub fn part2(input: &str) -> String {
+    let instructions = build_list(input, build_instruction);
+    value_sent_to_safe(&instructions, 12).to_string()
+}
+
+pub fn part1_c_code(input: &str) -> String {
+    let instructions = build_list(input, build_instruction);
+    let mut initial_registers = Registers::new();
+    initial_registers.set('a', 7);
+    get_c_code(&instructions, &initial_registers, &['a'])
+}
 
 #[allow(dead_code)]
 fn main() {
     let input_file = run_utils::get_input_file("day2016_23");
     let input = fs::read_to_string(input_file).expect("Unable to read input file");
     println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
 }
 
 #[cfg(test)]
 mod tests {
+    use virtual_cpu::c_code::exec::exec_c_code;
     use virtual_cpu::instruction::build_list;
 
     use super::*;
 
-    const INPUT_TEST: &str = include_str!("test_input/day2016_23_input_test_1");
+    const INPUT_TEST_1: &str = include_str!("test_input/day2016_23_input_test_1");
+    const INPUT_TEST_2: &str = include_str!("test_input/day2016_23_input_test_2");
 
     #[test]
     fn test_part1() {
         assert_eq!(
-            value_sent_to_safe(&build_list(INPUT_TEST, build_instruction)),
+            value_sent_to_safe(&build_list(INPUT_TEST_1, build_instruction), 7),
             3
         );
     }
+
+    #[test]
+    fn test_part1_c_code() {
+        assert_eq!(exec_c_code(&part1_c_code(INPUT_TEST_1)), "3");
+        assert_eq!(
+            exec_c_code(&part1_c_code(INPUT_TEST_2)),
+            part1(INPUT_TEST_2)
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        // 12 * 12, plus 2 in the toggling loop, plus 4 * 3
+        assert_eq!(part2(INPUT_TEST_2), "158");
+    }
 }
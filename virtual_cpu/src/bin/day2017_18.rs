@@ -5,6 +5,7 @@ use virtual_cpu::intchar::IntChar;
 use virtual_cpu::parsing::char;
 use virtual_cpu::registers::Registers;
 use virtual_cpu::run_utils;
+use virtual_cpu::scheduler::{Outcome, Scheduler};
 
 fn build_instruction(s: &str) -> Instruction {
     let parts: Vec<_> = s.split(' ').collect();
@@ -56,16 +57,33 @@ fn recovered_frequency_value(instructions: &[Instruction]) -> i64 {
     panic!("Didn't find a recovered sound")
 }
 
+// Runs the two programs, returning how they ended and how many values program 1 sent.
+// Both a deadlock and going past the end count as the programs being terminated.
+fn duet(instructions: &[Instruction]) -> (Outcome, usize) {
+    let mut scheduler = Scheduler::new(instructions, 2);
+    for (id, machine) in scheduler.machines.iter_mut().enumerate() {
+        machine.regs.set('p', id as i64);
+    }
+    let outcome = scheduler.run();
+    (outcome, scheduler.machines[1].sent_count)
+}
+
 pub fn part1(input: &str) -> String {
     let instructions = build_list(input, build_instruction);
     recovered_frequency_value(&instructions).to_string()
 }
 
+pub fn part2(input: &str) -> String {
+    let instructions = build_list(input, build_instruction);
+    duet(&instructions).1.to_string()
+}
+
 #[allow(dead_code)]
 fn main() {
     let input_file = run_utils::get_input_file("day2017_18");
     let input = fs::read_to_string(input_file).expect("Unable to read input file");
     println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
 }
 
 #[cfg(test)]
@@ -73,6 +91,7 @@ mod tests {
     use super::*;
 
     const INPUT_TEST_1: &str = include_str!("test_input/day2017_18_input_test_1");
+    const INPUT_TEST_2: &str = include_str!("test_input/day2017_18_input_test_2");
 
     #[test]
     fn test_part1() {
@@ -81,4 +100,21 @@ mod tests {
             4
         );
     }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            duet(&build_list(INPUT_TEST_2, build_instruction)),
+            (Outcome::Deadlock, 3)
+        );
+    }
+
+    #[test]
+    fn test_part2_terminated() {
+        // Both programs run off the end without receiving anything.
+        assert_eq!(
+            duet(&build_list("snd 1\nsnd p\nsnd 2", build_instruction)),
+            (Outcome::Terminated, 3)
+        );
+    }
 }
@@ -1,7 +1,8 @@
 use std::fs;
 
 use virtual_cpu::c_code::gen::get_c_code_full;
-use virtual_cpu::instruction::Instruction;
+use virtual_cpu::instruction::{execute_all, Instruction};
+use virtual_cpu::optimizer::optimize;
 use virtual_cpu::registers::Registers;
 use virtual_cpu::run_utils;
 
@@ -36,6 +37,18 @@ pub fn part1(input: &str) -> String {
     mul_count(&instructions).to_string()
 }
 
+fn value_in_h(instructions: &[Instruction]) -> i64 {
+    let mut regs = Registers::new();
+    regs.set('a', 1);
+    execute_all(&optimize(instructions), &mut regs);
+    regs.get('h')
+}
+
+pub fn part2(input: &str) -> String {
+    let instructions = Instruction::build_list(input);
+    value_in_h(&instructions).to_string()
+}
+
 pub fn part2_c_code(input: &str) -> String {
     let instructions = Instruction::build_list(input);
     let mut initial_registers = Registers::new();
@@ -56,6 +69,35 @@ pub fn part2_c_code(input: &str) -> String {
 fn main() {
     let input_file = run_utils::get_input_file("day2017_23");
     let input = fs::read_to_string(input_file).expect("Unable to read input file");
-    let res = part1(&input);
-    println!("Part 1: {}", res);
+    println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
+}
+
+#[cfg(test)]
+mod tests {
+    use virtual_cpu::c_code::exec::exec_c_code;
+    use virtual_cpu::c_code::gen::get_c_code;
+
+    use super::*;
+
+    const INPUT_TEST: &str = include_str!("test_input/day2017_23_input_test_1");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(mul_count(&Instruction::build_list(INPUT_TEST)), 81);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(value_in_h(&Instruction::build_list(INPUT_TEST)), 6);
+    }
+
+    #[test]
+    fn test_part2_c_code_optimizer() {
+        let instructions = optimize(&Instruction::build_list(INPUT_TEST));
+        let mut initial_registers = Registers::new();
+        initial_registers.set('a', 1);
+        let code = get_c_code(&instructions, &initial_registers, &['h']);
+        assert_eq!(exec_c_code(&code), "6");
+    }
 }
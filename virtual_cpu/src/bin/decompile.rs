@@ -0,0 +1,32 @@
+//! Prints the decompiled version of a program.
+//!
+//!     cargo r --bin decompile -- src/bin/input/day2016_23_input
+
+use std::{env, fs};
+
+use virtual_cpu::decompiler::decompile;
+use virtual_cpu::instruction::{build_list, Instruction};
+use virtual_cpu::intchar::IntChar;
+use virtual_cpu::parsing::char;
+
+// Supports the instructions of all days.
+fn build_instruction(s: &str) -> Instruction {
+    let t = s.replace(',', "");
+    let parts: Vec<_> = t.split(' ').collect();
+    match *parts.first().unwrap() {
+        "tpl" => Instruction::Mul(char(parts[1]), IntChar::from(3)),
+        "hlf" => Instruction::Div(char(parts[1]), IntChar::from(2)),
+        "tgl" => Instruction::Toggle(char(parts[1])),
+        "out" => Instruction::Out(IntChar::new(parts[1])),
+        "snd" => Instruction::Snd(IntChar::new(parts[1])),
+        "rcv" => Instruction::Rcv(char(parts[1])),
+        _ => Instruction::build(&t),
+    }
+}
+
+fn main() {
+    let input_file = env::args().nth(1).expect("Usage: decompile INPUT_FILE");
+    let input = fs::read_to_string(input_file).expect("Unable to read input file");
+    let instructions = build_list(input.trim_end(), build_instruction);
+    print!("{}", decompile(&instructions));
+}
@@ -0,0 +1,239 @@
+//! C generation for self-modifying programs, aka programs using `tgl`.
+//!
+//! The program is stored as an array of instructions, each with its own opcode that can be toggled.
+//! Straight-line blocks are still inlined, as long as none of their instructions has been toggled.
+//! Otherwise, or when jumping into the middle of a block, instructions are executed one by one
+//! by a switch-based interpreter loop.
+
+use crate::{
+    instruction::{Condition, Instruction},
+    intchar::IntChar,
+    registers::Registers,
+};
+
+use super::gen::{condition_c_version, get_register_names, instruction_c_version};
+
+const HEADER: &str = r"#include <stdio.h>
+
+enum { SET, ADD, SUB, MUL, MOD, DIV, JNZ, JGZ, JMP, JIE, JIO, OUT, TGL, NOP };
+
+typedef struct {
+	int op;
+	int p1_reg;
+	long long p1;
+	int p2_reg;
+	long long p2;
+} ins_t;
+
+";
+
+// Same rules as Instruction::toggle().
+const TOGGLE_FN: &str = r"static void toggle(long long t) {
+	if (t < 0 || t >= PROG_LEN) {
+		return;
+	}
+	ins_t *ins = &prog[t];
+	switch (ins->op) {
+	case SET: ins->op = JNZ; break;
+	case ADD: ins->op = SUB; break;
+	case SUB: ins->op = ADD; break;
+	case JNZ: case JGZ: case JMP: case JIE: case JIO: ins->op = ins->p2_reg >= 0 ? SET : NOP; break;
+	case TGL: ins->op = ADD; ins->p2_reg = -1; ins->p2 = 1; break;
+	}
+	dirty[block_of[t]] = 1;
+}
+
+";
+
+const GENERIC_STEP: &str = r#"		// Generic execution of a single instruction.
+		const ins_t *ins = &prog[ir];
+		long long v1 = ins->p1_reg >= 0 ? *regs[ins->p1_reg] : ins->p1;
+		long long v2 = ins->p2_reg >= 0 ? *regs[ins->p2_reg] : ins->p2;
+		long long *r1 = ins->p1_reg >= 0 ? regs[ins->p1_reg] : NULL;
+		switch (ins->op) {
+		case SET: if (ins->p2_reg >= 0) *regs[ins->p2_reg] = v1; break;
+		case ADD: if (r1) *r1 += v2; break;
+		case SUB: if (r1) *r1 -= v2; break;
+		case MUL: if (r1) *r1 *= v2; break;
+		case MOD: if (r1) *r1 %= v2; break;
+		case DIV: if (r1) *r1 /= v2; break;
+		case JNZ: if (v1 != 0) { ir += v2; continue; } break;
+		case JGZ: if (v1 > 0) { ir += v2; continue; } break;
+		case JMP: ir += v2; continue;
+		case JIE: if (v1 % 2 == 0) { ir += v2; continue; } break;
+		case JIO: if (v1 == 1) { ir += v2; continue; } break;
+		case OUT: printf("%lli\n", v1); break;
+		case TGL: toggle(ir + v1); break;
+		}
+		ir++;
+"#;
+
+// Returns the opcode and the two parameters of the instruction, in the order used by toggle.
+fn encode(ins: &Instruction) -> (&'static str, IntChar<i64>, IntChar<i64>) {
+    let none = IntChar::Integer(0);
+    match ins {
+        Instruction::Set(x, y) => ("SET", *y, IntChar::Char(*x)),
+        Instruction::Add(x, y) => ("ADD", IntChar::Char(*x), *y),
+        Instruction::Sub(x, y) => ("SUB", IntChar::Char(*x), *y),
+        Instruction::Mul(x, y) => ("MUL", IntChar::Char(*x), *y),
+        Instruction::Mod(x, y) => ("MOD", IntChar::Char(*x), *y),
+        Instruction::Div(x, y) => ("DIV", IntChar::Char(*x), *y),
+        Instruction::JumpIf(cond, x, y, _) => (
+            match cond {
+                Condition::NotZero => "JNZ",
+                Condition::GreaterThanZero => "JGZ",
+                Condition::True => "JMP",
+                Condition::Even => "JIE",
+                Condition::EqualOne => "JIO",
+            },
+            *x,
+            *y,
+        ),
+        Instruction::Out(x) => ("OUT", *x, none),
+        Instruction::Toggle(x) => ("TGL", IntChar::Char(*x), none),
+        Instruction::Nop => ("NOP", none, none),
+        _ => panic!("Unsupported instruction for C dispatcher: {:?}", ins),
+    }
+}
+
+// C initializer for a parameter: register index (or -1) and value.
+fn param_c_version(p: IntChar<i64>, registers: &[char]) -> String {
+    match p {
+        IntChar::Integer(val) => format!("-1, {}", val),
+        IntChar::Char(r) => format!("{}, 0", registers.iter().position(|&n| n == r).unwrap()),
+    }
+}
+
+// Returns for each instruction the index of the first instruction of its block.
+// Blocks start at the beginning, at jump targets and after jumps and toggles.
+fn get_blocks(instructions: &[Instruction]) -> Vec<usize> {
+    let mut leaders = vec![false; instructions.len()];
+    leaders[0] = true;
+    for (i, ins) in instructions.iter().enumerate() {
+        match ins {
+            Instruction::JumpIf(_, _, y, _) => {
+                if let IntChar::Integer(offset) = y {
+                    let target = i as i64 + offset;
+                    if (0..instructions.len() as i64).contains(&target) {
+                        leaders[target as usize] = true;
+                    }
+                }
+            }
+            Instruction::Toggle(_) => {}
+            _ => continue,
+        }
+        if i + 1 < instructions.len() {
+            leaders[i + 1] = true;
+        }
+    }
+
+    let mut block_of = Vec::with_capacity(instructions.len());
+    for (i, is_leader) in leaders.into_iter().enumerate() {
+        block_of.push(if is_leader {
+            i
+        } else {
+            *block_of.last().unwrap()
+        });
+    }
+    block_of
+}
+
+// Generates the inlined C code of the block going from start to end (included).
+fn block_c_version(instructions: &[Instruction], start: usize, end: usize) -> String {
+    let mut code = format!("\t\tcase {}:\n\t\t\tif (dirty[{}]) break;\n", start, start);
+    for (i, ins) in instructions.iter().enumerate().take(end + 1).skip(start) {
+        let line = match ins {
+            Instruction::JumpIf(cond, x, IntChar::Integer(offset), _) => format!(
+                "if ({}) {{ ir = {}; continue; }}",
+                condition_c_version(cond, x),
+                i as i64 + offset
+            ),
+            Instruction::JumpIf(cond, x, IntChar::Char(r), _) => format!(
+                "if ({}) {{ ir = {} + {}; continue; }}",
+                condition_c_version(cond, x),
+                i,
+                r
+            ),
+            Instruction::Toggle(x) => format!("toggle({} + {});", i, x),
+            Instruction::Nop => continue,
+            _ => instruction_c_version(ins, i, &[]) + ";",
+        };
+        code += &format!("\t\t\t{}\n", line);
+    }
+    code += &format!("\t\t\tir = {};\n\t\t\tcontinue;\n", end + 1);
+    code
+}
+
+/// Transform the self-modifying instructions into C.
+///
+/// Parameters are the same as for `get_c_code`.
+pub(super) fn get_c_code_with_dispatcher(
+    instructions: &[Instruction],
+    initial_registers: &Registers<i64>,
+    registers_to_print: &[char],
+) -> String {
+    let mut registers: Vec<char> = get_register_names(instructions).into_iter().collect();
+    registers.sort_unstable();
+    let block_of = get_blocks(instructions);
+
+    let mut code = HEADER.to_string();
+    code += &format!("#define PROG_LEN {}\n\n", instructions.len());
+
+    code += "static ins_t prog[PROG_LEN] = {\n";
+    for ins in instructions {
+        let (op, p1, p2) = encode(ins);
+        code += &format!(
+            "\t{{ {}, {}, {} }},\n",
+            op,
+            param_c_version(p1, &registers),
+            param_c_version(p2, &registers)
+        );
+    }
+    code += "};\n\n";
+    code += "// Set when an instruction of the block starting at this index has been toggled.\n";
+    code += "static int dirty[PROG_LEN];\n";
+    code += &format!(
+        "static const int block_of[PROG_LEN] = {{ {} }};\n\n",
+        block_of
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    code += TOGGLE_FN;
+
+    code += "int main() {\n";
+    for r in &registers {
+        code += &format!("\tlong long {} = {};\n", r, initial_registers.get(*r));
+    }
+    code += &format!(
+        "\tlong long *regs[] = {{ {} }};\n\n",
+        registers
+            .iter()
+            .map(|r| format!("&{}", r))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    code += "\tlong long ir = 0;\n";
+    code += "\twhile (ir >= 0 && ir < PROG_LEN) {\n";
+    code += "\t\tswitch (ir) {\n";
+    let starts: Vec<usize> = (0..instructions.len())
+        .filter(|&i| block_of[i] == i)
+        .collect();
+    for (k, start) in starts.iter().enumerate() {
+        let end = starts.get(k + 1).map_or(instructions.len(), |next| *next) - 1;
+        code += &block_c_version(instructions, *start, end);
+    }
+    code += "\t\t}\n\n";
+    code += GENERIC_STEP;
+    code += "\t}\n\n";
+
+    for r in registers_to_print {
+        code += &format!("\tprintf(\"%lli\\n\", {});\n", r);
+    }
+    code += "\treturn 0;\n";
+    code += "}\n";
+
+    code
+}
@@ -1,15 +1,32 @@
-use std::{fs, process::Command};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, fs, process, process::Command};
+
+// Tests may execute C code in parallel, so each execution needs its own files.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
 
 pub fn exec_c_code(code: &str) -> String {
+    let name = format!(
+        "virtual_cpu_{}_{}",
+        process::id(),
+        NEXT_ID.fetch_add(1, Ordering::Relaxed)
+    );
+    let c_file = env::temp_dir().join(format!("{}.c", name));
+    let exe_file = env::temp_dir().join(name);
+
     // Write the C file.
-    fs::write("main.c", code).expect("Unable to write file");
+    fs::write(&c_file, code).expect("Unable to write file");
     // Compile it.
-    let _ = Command::new("gcc").arg("-O3").arg("main.c").output();
+    let _ = Command::new("gcc")
+        .arg("-O3")
+        .arg("-o")
+        .arg(&exe_file)
+        .arg(&c_file)
+        .output();
     // Run it.
-    let output = Command::new("./a.out").output().unwrap();
+    let output = Command::new(&exe_file).output().unwrap();
     // Clean the files.
-    let _ = Command::new("rm").arg("a.out").output();
-    let _ = Command::new("rm").arg("main.c").output();
+    let _ = fs::remove_file(&exe_file);
+    let _ = fs::remove_file(&c_file);
 
     String::from_utf8(output.stdout).unwrap().trim().to_string()
 }
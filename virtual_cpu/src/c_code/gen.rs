@@ -4,11 +4,14 @@ use fxhash::FxHashSet;
 
 use crate::{
     instruction::{Condition, Instruction},
+    intchar::IntChar,
     registers::Registers,
 };
 
+use super::dispatcher::get_c_code_with_dispatcher;
+
 // Returns the list of register names used in the program.
-fn get_register_names(instructions: &[Instruction]) -> FxHashSet<char> {
+pub(super) fn get_register_names(instructions: &[Instruction]) -> FxHashSet<char> {
     let mut names: FxHashSet<char> = FxHashSet::default();
     for ins in instructions {
         names.extend(ins.get_register_names());
@@ -16,6 +19,16 @@ fn get_register_names(instructions: &[Instruction]) -> FxHashSet<char> {
     names
 }
 
+// Returns the index of the instruction this one may jump to, if any.
+fn jump_target(ins: &Instruction, ir: usize) -> Option<usize> {
+    match ins {
+        Instruction::JumpIf(_, _, y, _) => Some((ir as i64 + y.get_integer()) as usize),
+        Instruction::AddProduct(_, _, _, _, len)
+        | Instruction::DivisorCheck(_, _, _, _, _, len) => Some(ir + len),
+        _ => None,
+    }
+}
+
 // Helper method to get the next alphabetical letter.
 fn move_shift(data: &str, shift: usize) -> String {
     data.chars()
@@ -30,10 +43,21 @@ fn gen_free_label_name(next_label_name: &mut String) -> String {
     free_name
 }
 
+/// Generates the C expression testing the jump condition.
+pub(super) fn condition_c_version(cond: &Condition, x: &IntChar<i64>) -> String {
+    match cond {
+        Condition::NotZero => format!("{} != 0", x),
+        Condition::GreaterThanZero => format!("{} > 0", x),
+        Condition::True => "1".to_string(),
+        Condition::Even => format!("{} % 2 == 0", x),
+        Condition::EqualOne => format!("{} == 1", x),
+    }
+}
+
 /// Generates C code for the instruction.
 ///
 /// * `labels` - List of goto labels for the specified index.
-fn instruction_c_version(ins: &Instruction, ir: usize, labels: &[String]) -> String {
+pub(super) fn instruction_c_version(ins: &Instruction, ir: usize, labels: &[String]) -> String {
     match ins {
         Instruction::Set(x, y) => format!("{} = {}", x, y),
         Instruction::Add(x, y) => format!("{} += {}", x, y),
@@ -43,15 +67,25 @@ fn instruction_c_version(ins: &Instruction, ir: usize, labels: &[String]) -> Str
         Instruction::Div(x, y) => format!("{} /= {}", x, y),
         Instruction::JumpIf(cond, x, y, _) => {
             let index = (ir as i64 + y.get_integer()) as usize;
-            match cond {
-                Condition::NotZero => format!("if ({} != 0) goto {}", x, &labels[index]),
-                Condition::GreaterThanZero => format!("if ({} > 0) goto {}", x, &labels[index]),
-                Condition::True => format!("if (1) goto {}", &labels[index]),
-                Condition::Even => format!("if ({} % 2 == 0) goto {}", x, &labels[index]),
-                Condition::EqualOne => format!("if ({} == 1) goto {}", x, &labels[index]),
-            }
+            format!("if ({}) goto {}", condition_c_version(cond, x), &labels[index])
         }
         Instruction::Out(x) => format!("printf(\"%lli\\n\", {})", x),
+        Instruction::AddProduct(x, y, z, counters, len) => {
+            let mut line = format!("{} += {} * {};", x, y, z);
+            for c in counters {
+                line += &format!(" {} = 0;", c);
+            }
+            line + &format!(" goto {}", &labels[ir + len])
+        }
+        Instruction::DivisorCheck(flag, divisor, number, counter, scratch, len) => format!(
+            "if ({d} != 0 && {n} % {d} == 0 && {n} / {d} >= {c} && {n} / {d} < {n}) {f} = 0; {c} = {n}; {s} = 0; goto {l}",
+            f = flag,
+            d = divisor,
+            n = number,
+            c = counter,
+            s = scratch,
+            l = &labels[ir + len]
+        ),
         Instruction::Nop => String::new(),
         _ => panic!("Unsupported instruction for C generation: {:?}", ins),
     }
@@ -63,14 +97,27 @@ fn instruction_c_version(ins: &Instruction, ir: usize, labels: &[String]) -> Str
 /// * `initial_registers` - The registers (aka variables) that must have their values set at something else than zero at the beginning.
 /// * `registers_to_print` - The register's values to print at the end.
 /// * `optimizations` - A block of instructions, indicated by its indexes, to be replaced
-/// with the specified code. If there are multiple ones, their indexes cannot overlap.
-#[allow(clippy::single_match)]
+///   with the specified code. If there are multiple ones, their indexes cannot overlap.
+///
+/// Programs using `tgl` are self-modifying, so they are generated with a dispatcher instead,
+/// see the `dispatcher` module. Optimizations are not supported for them.
 pub fn get_c_code_full(
     instructions: &[Instruction],
     initial_registers: &Registers<i64>,
     registers_to_print: &[char],
     optimizations: &[(RangeInclusive<usize>, String)],
 ) -> String {
+    if instructions
+        .iter()
+        .any(|ins| matches!(ins, Instruction::Toggle(_)))
+    {
+        assert!(
+            optimizations.is_empty(),
+            "Optimizations not supported with toggle instructions"
+        );
+        return get_c_code_with_dispatcher(instructions, initial_registers, registers_to_print);
+    }
+
     let mut code = String::new();
     code += r"#include <stdio.h>
 
@@ -98,12 +145,8 @@ int main() {
     let mut labels = vec![String::new(); instructions.len() + 2];
     let mut next_label_name = "A".to_string();
     for (i, ins) in instructions.iter().enumerate() {
-        match ins {
-            Instruction::JumpIf(_, _, y, _) => {
-                let index = (i as i64 + y.get_integer()) as usize;
-                labels[index] = gen_free_label_name(&mut next_label_name);
-            }
-            _ => {}
+        if let Some(index) = jump_target(ins, i) {
+            labels[index] = gen_free_label_name(&mut next_label_name);
         }
     }
 
@@ -1,2 +1,3 @@
+mod dispatcher;
 pub mod exec;
 pub mod gen;
@@ -0,0 +1,374 @@
+//! Control-flow graph of a program, made of basic blocks.
+//!
+//! Jumps with an offset stored in a register can go anywhere, so they are represented
+//! as an edge to an unknown destination.
+//!
+//! Blocks that cannot be reached from the first one can only be entered through such unknown
+//! edges, so they are considered as additional entry points.
+//!
+//! The graph can be exported in [Graphviz](https://graphviz.org/doc/info/lang.html) format:
+//!
+//! ```text
+//! dot -Tpdf cfg.gv > cfg.pdf
+//! ```
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::instruction::Instruction;
+use crate::intchar::IntChar;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Edge {
+    /// Index of the destination block.
+    Block(usize),
+    /// Jump going past the beginning or the end of the program.
+    Exit,
+    /// Jump with an offset only known at run time.
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasicBlock {
+    /// Index of the first instruction.
+    pub start: usize,
+    /// Index of the last instruction (inclusive).
+    pub end: usize,
+    pub successors: Vec<Edge>,
+}
+
+/// A natural loop, identified by a back edge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loop {
+    pub header: usize,
+    /// Block jumping back to the header.
+    pub latch: usize,
+    /// All the blocks of the loop, including the header and the latch.
+    pub body: BTreeSet<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cfg {
+    pub blocks: Vec<BasicBlock>,
+}
+
+// Where the flow can go after executing the instruction: instruction indexes (possibly out of the program) or None for unknown.
+pub(crate) fn next_instructions(ins: &Instruction, ir: usize) -> Vec<Option<i64>> {
+    let next = Some(ir as i64 + 1);
+    match ins {
+        Instruction::JumpIf(_, x, y, test_fn) => {
+            let target = match y {
+                IntChar::Integer(offset) => Some(ir as i64 + offset),
+                IntChar::Char(_) => None,
+            };
+            match x {
+                // Constant condition, like "jnz 1 3".
+                IntChar::Integer(v) if test_fn(*v) => vec![target],
+                IntChar::Integer(_) => vec![next],
+                IntChar::Char(_) => vec![next, target],
+            }
+        }
+        Instruction::AddProduct(_, _, _, _, len)
+        | Instruction::DivisorCheck(_, _, _, _, _, len) => vec![Some((ir + len) as i64)],
+        _ => vec![next],
+    }
+}
+
+// Checks if the instruction must be the last one of its block.
+fn ends_block(ins: &Instruction) -> bool {
+    matches!(
+        ins,
+        Instruction::JumpIf(..) | Instruction::AddProduct(..) | Instruction::DivisorCheck(..)
+    )
+}
+
+impl Cfg {
+    pub fn build(instructions: &[Instruction]) -> Self {
+        let len = instructions.len();
+        if len == 0 {
+            return Self { blocks: Vec::new() };
+        }
+
+        // Block leaders: first instruction, jump targets and instructions following a jump.
+        let mut leaders = vec![false; len];
+        leaders[0] = true;
+        for (i, ins) in instructions.iter().enumerate() {
+            if ends_block(ins) {
+                for target in next_instructions(ins, i).into_iter().flatten() {
+                    if (0..len as i64).contains(&target) {
+                        leaders[target as usize] = true;
+                    }
+                }
+                if i + 1 < len {
+                    leaders[i + 1] = true;
+                }
+            }
+        }
+
+        let starts: Vec<usize> = (0..len).filter(|&i| leaders[i]).collect();
+        let mut block_of = vec![0; len];
+        for (b, start) in starts.iter().enumerate() {
+            let end = starts.get(b + 1).map_or(len, |next| *next);
+            block_of[*start..end].fill(b);
+        }
+
+        let blocks = starts
+            .iter()
+            .enumerate()
+            .map(|(b, &start)| {
+                let end = starts.get(b + 1).map_or(len, |next| *next) - 1;
+                let mut successors: Vec<Edge> = next_instructions(&instructions[end], end)
+                    .into_iter()
+                    .map(|target| match target {
+                        Some(t) if (0..len as i64).contains(&t) => {
+                            Edge::Block(block_of[t as usize])
+                        }
+                        Some(_) => Edge::Exit,
+                        None => Edge::Unknown,
+                    })
+                    .collect();
+                successors.sort_unstable();
+                successors.dedup();
+                BasicBlock {
+                    start,
+                    end,
+                    successors,
+                }
+            })
+            .collect();
+        Self { blocks }
+    }
+
+    /// Returns the index of the block containing this instruction.
+    pub fn block_of(&self, ir: usize) -> Option<usize> {
+        self.blocks
+            .iter()
+            .position(|b| (b.start..=b.end).contains(&ir))
+    }
+
+    fn predecessors(&self) -> Vec<Vec<usize>> {
+        let mut preds = vec![Vec::new(); self.blocks.len()];
+        for (b, block) in self.blocks.iter().enumerate() {
+            for edge in &block.successors {
+                if let Edge::Block(s) = edge {
+                    preds[*s].push(b);
+                }
+            }
+        }
+        preds
+    }
+
+    // Marks all blocks reachable from the start block.
+    fn mark_reachable(&self, start: usize, reachable: &mut [bool]) {
+        let mut stack = vec![start];
+        while let Some(b) = stack.pop() {
+            if reachable[b] {
+                continue;
+            }
+            reachable[b] = true;
+            stack.extend(self.blocks[b].successors.iter().filter_map(|e| match e {
+                Edge::Block(s) => Some(*s),
+                _ => None,
+            }));
+        }
+    }
+
+    /// The entry points: the first block, and then the first block not reachable
+    /// from the previous entries, until all blocks are covered.
+    pub fn entries(&self) -> Vec<usize> {
+        let mut entries = Vec::new();
+        let mut reachable = vec![false; self.blocks.len()];
+        while let Some(b) = reachable.iter().position(|r| !r) {
+            entries.push(b);
+            self.mark_reachable(b, &mut reachable);
+        }
+        entries
+    }
+
+    /// For each block, the set of blocks dominating it.
+    /// Uses the simple iterative data-flow algorithm, ignoring unknown edges.
+    pub fn dominators(&self) -> Vec<BTreeSet<usize>> {
+        let all: BTreeSet<usize> = (0..self.blocks.len()).collect();
+        let mut dominators = vec![all; self.blocks.len()];
+        let entries = self.entries();
+        for e in &entries {
+            dominators[*e] = BTreeSet::from([*e]);
+        }
+        let preds = self.predecessors();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in (0..self.blocks.len()).filter(|b| !entries.contains(b)) {
+                let mut new_dom = preds[b]
+                    .iter()
+                    .map(|p| dominators[*p].clone())
+                    .reduce(|acc, d| acc.intersection(&d).copied().collect())
+                    .unwrap_or_default();
+                new_dom.insert(b);
+                if new_dom != dominators[b] {
+                    dominators[b] = new_dom;
+                    changed = true;
+                }
+            }
+        }
+        dominators
+    }
+
+    /// Finds the natural loops, aka loops formed by an edge jumping back to a block dominating it.
+    pub fn natural_loops(&self) -> Vec<Loop> {
+        let dominators = self.dominators();
+        let preds = self.predecessors();
+
+        let mut loops = Vec::new();
+        for (latch, block) in self.blocks.iter().enumerate() {
+            for edge in &block.successors {
+                let Edge::Block(header) = *edge else {
+                    continue;
+                };
+                if !dominators[latch].contains(&header) {
+                    continue;
+                }
+                // The body is all blocks that can reach the latch without going through the header.
+                let mut body = BTreeSet::from([header]);
+                let mut stack = vec![latch];
+                while let Some(b) = stack.pop() {
+                    if body.insert(b) {
+                        stack.extend(preds[b].iter().copied());
+                    }
+                }
+                loops.push(Loop {
+                    header,
+                    latch,
+                    body,
+                });
+            }
+        }
+        loops
+    }
+
+    /// Exports the graph in Graphviz format, with the instructions of each block.
+    /// Loop headers are highlighted, and back edges are in red.
+    pub fn to_dot(&self, instructions: &[Instruction]) -> String {
+        let loops = self.natural_loops();
+
+        let mut dot = String::from("digraph {\n");
+        dot += "\tnode [shape=box, fontname=\"monospace\"];\n";
+        for (b, block) in self.blocks.iter().enumerate() {
+            let label: String = (block.start..=block.end)
+                .map(|i| format!("{}: {}\\l", i, instructions[i]))
+                .collect();
+            let style = if loops.iter().any(|l| l.header == b) {
+                ", style=filled, fillcolor=lightblue"
+            } else {
+                ""
+            };
+            dot += &format!("\tB{} [label=\"{}\"{}];\n", b, label, style);
+        }
+        let mut has_exit = false;
+        let mut has_unknown = false;
+        for (b, block) in self.blocks.iter().enumerate() {
+            for edge in &block.successors {
+                match edge {
+                    Edge::Block(s) => {
+                        let is_back_edge = loops.iter().any(|l| l.latch == b && l.header == *s);
+                        dot += &format!(
+                            "\tB{} -> B{}{};\n",
+                            b,
+                            s,
+                            if is_back_edge { " [color=red]" } else { "" }
+                        );
+                    }
+                    Edge::Exit => {
+                        has_exit = true;
+                        dot += &format!("\tB{} -> exit;\n", b);
+                    }
+                    Edge::Unknown => {
+                        has_unknown = true;
+                        dot += &format!("\tB{} -> unknown [style=dashed];\n", b);
+                    }
+                }
+            }
+        }
+        if has_exit {
+            dot += "\texit [shape=doublecircle];\n";
+        }
+        if has_unknown {
+            dot += "\tunknown [shape=diamond, label=\"?\"];\n";
+        }
+        dot += "}\n";
+        dot
+    }
+
+    /// Writes the graph in Graphviz format to a file.
+    pub fn write_dot(&self, instructions: &[Instruction], path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_dot(instructions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT_TEST: &str = include_str!("bin/test_input/day2016_23_input_test_2");
+
+    fn build(input: &str) -> Vec<Instruction> {
+        input
+            .lines()
+            .map(|l| match l.split_once(' ') {
+                Some(("tgl", r)) => Instruction::Toggle(r.chars().next().unwrap()),
+                _ => Instruction::build(l),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_basic_blocks() {
+        let instructions = build(INPUT_TEST);
+        let cfg = Cfg::build(&instructions);
+        let starts: Vec<usize> = cfg.blocks.iter().map(|b| b.start).collect();
+        assert_eq!(starts, [0, 3, 4, 7, 9, 15, 16, 17, 20]);
+        // inc a; dec c; jnz c -2
+        assert_eq!(cfg.blocks[2].successors, [Edge::Block(2), Edge::Block(3)]);
+        // The toggling loop ends with jnz 1 c.
+        assert_eq!(cfg.blocks[4].successors, [Edge::Unknown]);
+        // jnz 3 d always jumps, by an unknown offset.
+        assert_eq!(cfg.blocks[5].successors, [Edge::Unknown]);
+        // jnz d -5 at the end
+        assert_eq!(cfg.blocks[8].successors, [Edge::Block(6), Edge::Exit]);
+        // 15 and 16 are after unconditional jumps, they are only reached once these are toggled.
+        assert_eq!(cfg.entries(), [0, 5, 6]);
+        assert_eq!(cfg.block_of(6), Some(2));
+    }
+
+    #[test]
+    fn test_natural_loops() {
+        let instructions = build(INPUT_TEST);
+        let cfg = Cfg::build(&instructions);
+        let loops: Vec<(usize, usize, Vec<usize>)> = cfg
+            .natural_loops()
+            .into_iter()
+            .map(|l| (l.header, l.latch, l.body.into_iter().collect()))
+            .collect();
+        assert_eq!(
+            loops,
+            [
+                (2, 2, vec![2]),
+                (1, 3, vec![1, 2, 3]),
+                (7, 7, vec![7]),
+                (6, 8, vec![6, 7, 8]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let instructions = build(INPUT_TEST);
+        let dot = Cfg::build(&instructions).to_dot(&instructions);
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains("B1 [label=\"3: set c b\\l\", style=filled, fillcolor=lightblue];"));
+        assert!(dot.contains("B3 -> B1 [color=red];"));
+        assert!(dot.contains("B4 -> unknown [style=dashed];"));
+    }
+}
@@ -0,0 +1,637 @@
+//! Decompiler from instructions to structured Rust-like pseudo-code.
+//!
+//! The program is first optimized, so that add and multiplication loops become expressions.
+//! Backward jumps become loops, forward jumps become `if` blocks, and jumps out of a loop become
+//! `break` or `return`. Anything that cannot be structured is kept as a `goto`.
+//! Sequences of operations on the same register are collapsed into a single expression.
+//!
+//! The output is annotated with the live-in registers (inputs) and the modified ones (outputs).
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::cfg::next_instructions;
+use crate::instruction::{Condition, Instruction};
+use crate::intchar::IntChar;
+use crate::optimizer::optimize;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Op::Add => '+',
+                Op::Sub => '-',
+                Op::Mul => '*',
+                Op::Div => '/',
+                Op::Mod => '%',
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Int(i64),
+    Reg(char),
+    Bin(Op, Box<Expr>, Box<Expr>),
+}
+
+impl From<IntChar<i64>> for Expr {
+    fn from(x: IntChar<i64>) -> Self {
+        match x {
+            IntChar::Integer(v) => Expr::Int(v),
+            IntChar::Char(r) => Expr::Reg(r),
+        }
+    }
+}
+
+impl Expr {
+    fn bin(op: Op, a: Expr, b: Expr) -> Self {
+        Expr::Bin(op, Box::new(a), Box::new(b))
+    }
+
+    fn as_int(&self) -> i64 {
+        if let Expr::Int(v) = self {
+            *v
+        } else {
+            panic!("Not an integer expression")
+        }
+    }
+
+    fn uses(&self, reg: char) -> bool {
+        match self {
+            Expr::Int(_) => false,
+            Expr::Reg(r) => *r == reg,
+            Expr::Bin(_, a, b) => a.uses(reg) || b.uses(reg),
+        }
+    }
+
+    // Replaces the register with the expression.
+    fn substitute(&self, reg: char, e: &Expr) -> Self {
+        match self {
+            Expr::Reg(r) if *r == reg => e.clone(),
+            Expr::Bin(op, a, b) => Expr::bin(*op, a.substitute(reg, e), b.substitute(reg, e)),
+            _ => self.clone(),
+        }
+    }
+
+    // Constant folding and a few simple identities.
+    fn simplify(self) -> Self {
+        let Expr::Bin(op, a, b) = self else {
+            return self;
+        };
+        let (a, b) = (a.simplify(), b.simplify());
+        match (op, a, b) {
+            (Op::Add, Expr::Int(x), Expr::Int(y)) => Expr::Int(x + y),
+            (Op::Sub, Expr::Int(x), Expr::Int(y)) => Expr::Int(x - y),
+            (Op::Mul, Expr::Int(x), Expr::Int(y)) => Expr::Int(x * y),
+            (Op::Div, Expr::Int(x), Expr::Int(y)) if y != 0 => Expr::Int(x / y),
+            (Op::Mod, Expr::Int(x), Expr::Int(y)) if y != 0 => Expr::Int(x % y),
+            (Op::Sub, a, Expr::Int(y)) => Expr::bin(Op::Add, a, Expr::Int(-y)).simplify(),
+            (Op::Add, a, Expr::Int(0)) | (Op::Mul | Op::Div, a, Expr::Int(1)) => a,
+            (Op::Add, Expr::Bin(Op::Add, a, b), Expr::Int(y)) if matches!(*b, Expr::Int(_)) => {
+                Expr::bin(Op::Add, *a, Expr::bin(Op::Add, *b, Expr::Int(y))).simplify()
+            }
+            (op, a, b) => Expr::bin(op, a, b),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Nested operations are always put in parenthesis, simpler than dealing with precedence.
+        fn operand(e: &Expr) -> String {
+            match e {
+                Expr::Bin(..) => format!("({})", e),
+                _ => e.to_string(),
+            }
+        }
+        match self {
+            Expr::Int(v) => write!(f, "{}", v),
+            Expr::Reg(r) => write!(f, "{}", r),
+            Expr::Bin(Op::Add, a, b) if matches!(**b, Expr::Int(v) if v < 0) => {
+                write!(f, "{} - {}", operand(a), -b.as_int())
+            }
+            Expr::Bin(op, a, b) => write!(f, "{} {} {}", operand(a), op, operand(b)),
+        }
+    }
+}
+
+/// A jump condition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cond {
+    cond: Condition,
+    x: IntChar<i64>,
+    negated: bool,
+}
+
+impl Cond {
+    fn new(cond: &Condition, x: IntChar<i64>) -> Self {
+        Self {
+            cond: cond.clone(),
+            x,
+            negated: false,
+        }
+    }
+
+    fn negate(&self) -> Self {
+        Self {
+            negated: !self.negated,
+            ..self.clone()
+        }
+    }
+}
+
+impl fmt::Display for Cond {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let x = self.x;
+        match (&self.cond, self.negated) {
+            (Condition::NotZero, false) => write!(f, "{} != 0", x),
+            (Condition::NotZero, true) => write!(f, "{} == 0", x),
+            (Condition::GreaterThanZero, false) => write!(f, "{} > 0", x),
+            (Condition::GreaterThanZero, true) => write!(f, "{} <= 0", x),
+            (Condition::True, negated) => write!(f, "{}", !negated),
+            (Condition::Even, false) => write!(f, "{} % 2 == 0", x),
+            (Condition::Even, true) => write!(f, "{} % 2 != 0", x),
+            (Condition::EqualOne, false) => write!(f, "{} == 1", x),
+            (Condition::EqualOne, true) => write!(f, "{} != 1", x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Stmt {
+    Assign(char, Expr),
+    If(Cond, Vec<Stmt>),
+    /// Loop, with an optional condition to continue, checked at the end of the body.
+    Loop(Vec<Stmt>, Option<Cond>),
+    While(Cond, Vec<Stmt>),
+    Break,
+    Continue,
+    Return,
+    Goto(usize),
+    Label(usize),
+    /// Anything else, like `out` or `tgl`.
+    Raw(String),
+}
+
+// The enclosing loop, used to recognize break and continue.
+#[derive(Debug, Clone, Copy)]
+struct LoopContext {
+    header: usize,
+    exit: usize,
+}
+
+struct Decompiler<'a> {
+    instructions: &'a [Instruction],
+    jump_targets: BTreeSet<usize>,
+    gotos: BTreeSet<usize>,
+}
+
+// Returns the condition of a jump, or None if it always jumps.
+// Also returns None if the instruction isn't a jump or never jumps, which must be checked before.
+fn jump_condition(ins: &Instruction) -> Option<Cond> {
+    match ins {
+        Instruction::JumpIf(_, IntChar::Integer(v), _, test_fn) if test_fn(*v) => None,
+        Instruction::JumpIf(cond, x, _, _) => Some(Cond::new(cond, *x)),
+        _ => None,
+    }
+}
+
+fn never_jumps(ins: &Instruction) -> bool {
+    matches!(ins, Instruction::JumpIf(_, IntChar::Integer(v), _, test_fn) if !test_fn(*v))
+}
+
+// Target of a jump with a constant offset.
+fn jump_target(ins: &Instruction, ir: usize) -> Option<i64> {
+    match ins {
+        Instruction::JumpIf(_, _, IntChar::Integer(offset), _) => Some(ir as i64 + offset),
+        _ => None,
+    }
+}
+
+// Converts an instruction that isn't a jump into statements.
+fn instruction_stmts(ins: &Instruction, ir: usize) -> Vec<Stmt> {
+    let op =
+        |op, x: char, y: IntChar<i64>| vec![Stmt::Assign(x, Expr::bin(op, Expr::Reg(x), y.into()))];
+    match ins {
+        Instruction::Set(x, y) => vec![Stmt::Assign(*x, (*y).into())],
+        Instruction::Add(x, y) => op(Op::Add, *x, *y),
+        Instruction::Sub(x, y) => op(Op::Sub, *x, *y),
+        Instruction::Mul(x, y) => op(Op::Mul, *x, *y),
+        Instruction::Mod(x, y) => op(Op::Mod, *x, *y),
+        Instruction::Div(x, y) => op(Op::Div, *x, *y),
+        Instruction::Nop | Instruction::JumpIf(..) => vec![],
+        Instruction::Snd(x) => vec![Stmt::Raw(format!("snd({})", x))],
+        Instruction::Rcv(x) => vec![Stmt::Raw(format!("{} = rcv()", x))],
+        Instruction::Out(x) => vec![Stmt::Raw(format!("out({})", x))],
+        Instruction::Toggle(x) => vec![Stmt::Raw(format!("toggle({} + {})", ir, x))],
+        Instruction::AddProduct(x, y, z, counters, _) => {
+            let mut stmts = vec![Stmt::Assign(
+                *x,
+                Expr::bin(
+                    Op::Add,
+                    Expr::Reg(*x),
+                    Expr::bin(Op::Mul, (*y).into(), (*z).into()),
+                ),
+            )];
+            stmts.extend(counters.iter().map(|c| Stmt::Assign(*c, Expr::Int(0))));
+            stmts
+        }
+        Instruction::DivisorCheck(flag, divisor, number, counter, scratch, _) => vec![
+            Stmt::Raw(format!(
+                "if {d} != 0 && {n} % {d} == 0 && {n} / {d} >= {c} && {n} / {d} < {n} {{ {f} = 0; }}",
+                f = flag,
+                d = divisor,
+                n = number,
+                c = counter,
+            )),
+            Stmt::Assign(*counter, Expr::Reg(*number)),
+            Stmt::Assign(*scratch, Expr::Int(0)),
+        ],
+    }
+}
+
+impl Decompiler<'_> {
+    // Statement for jumping to this target from inside a loop.
+    fn jump_stmt(&mut self, target: i64, ctx: Option<LoopContext>) -> Stmt {
+        if target < 0 || target >= self.instructions.len() as i64 {
+            return Stmt::Return;
+        }
+        let target = target as usize;
+        match ctx {
+            Some(c) if c.exit == target => Stmt::Break,
+            Some(c) if c.header == target => Stmt::Continue,
+            _ => {
+                self.gotos.insert(target);
+                Stmt::Goto(target)
+            }
+        }
+    }
+
+    // Finds the last instruction in the range jumping back to start.
+    fn find_loop_end(&self, start: usize, end: usize) -> Option<usize> {
+        (start..end).rev().find(|&j| {
+            !never_jumps(&self.instructions[j])
+                && jump_target(&self.instructions[j], j) == Some(start as i64)
+        })
+    }
+
+    fn structure(&mut self, start: usize, end: usize, ctx: Option<LoopContext>) -> Vec<Stmt> {
+        let mut stmts = Vec::new();
+        let mut i = start;
+        while i < end {
+            if self.jump_targets.contains(&i) {
+                stmts.push(Stmt::Label(i));
+            }
+            let ins = &self.instructions[i];
+
+            // High-level instructions include their loop.
+            if let Instruction::AddProduct(.., len) | Instruction::DivisorCheck(.., len) = ins {
+                stmts.extend(instruction_stmts(ins, i));
+                i += len;
+                continue;
+            }
+
+            if let Some(j) = self.find_loop_end(i, end) {
+                let loop_ctx = Some(LoopContext {
+                    header: i,
+                    exit: j + 1,
+                });
+                // Label is already set, don't put it again inside the loop.
+                self.jump_targets.remove(&i);
+                let body = self.structure(i, j, loop_ctx);
+                self.jump_targets.insert(i);
+                stmts.push(Stmt::Loop(body, jump_condition(&self.instructions[j])));
+                i = j + 1;
+                continue;
+            }
+
+            if never_jumps(ins) {
+                i += 1;
+                continue;
+            }
+
+            match (ins, jump_target(ins, i)) {
+                (Instruction::JumpIf(_, _, offset, _), None) => {
+                    let jump = Stmt::Raw(format!("jump({} + {})", i, offset));
+                    stmts.push(match jump_condition(ins) {
+                        Some(cond) => Stmt::If(cond, vec![jump]),
+                        None => jump,
+                    });
+                    i += 1;
+                }
+                (_, Some(t)) => {
+                    if let Some(cond) = jump_condition(ins) {
+                        // "if cond then skip the next jump" pattern.
+                        let next = self.instructions.get(i + 1);
+                        let next_target = next.and_then(|n| jump_target(n, i + 1));
+                        if let (true, Some(k), None) = (
+                            t == i as i64 + 2 && i + 1 < end,
+                            next_target,
+                            next.and_then(jump_condition),
+                        ) {
+                            if k > t && k <= end as i64 {
+                                let body = self.structure(i + 2, k as usize, ctx);
+                                stmts.push(Stmt::If(cond, body));
+                                i = k as usize;
+                            } else {
+                                let jump = self.jump_stmt(k, ctx);
+                                stmts.push(Stmt::If(cond.negate(), vec![jump]));
+                                i += 2;
+                            }
+                        } else if t > i as i64 && t <= end as i64 {
+                            let body = self.structure(i + 1, t as usize, ctx);
+                            stmts.push(Stmt::If(cond.negate(), body));
+                            i = t as usize;
+                        } else {
+                            let jump = self.jump_stmt(t, ctx);
+                            stmts.push(Stmt::If(cond, vec![jump]));
+                            i += 1;
+                        }
+                    } else if t == end as i64 && ctx.is_none_or(|c| c.exit != end) {
+                        // Jumping to the end of the block, the rest is dead code.
+                        i = end;
+                    } else {
+                        stmts.push(self.jump_stmt(t, ctx));
+                        i += 1;
+                    }
+                }
+                _ => {
+                    stmts.extend(instruction_stmts(ins, i));
+                    i += 1;
+                }
+            }
+        }
+        stmts
+    }
+}
+
+// Merges consecutive assignments to the same register, and turns loops starting with a break into while loops.
+fn collapse(stmts: Vec<Stmt>) -> Vec<Stmt> {
+    let mut result: Vec<Stmt> = Vec::new();
+    for stmt in stmts {
+        let stmt = match stmt {
+            Stmt::Assign(x, e) => Stmt::Assign(x, e.simplify()),
+            Stmt::If(c, body) => Stmt::If(c, collapse(body)),
+            Stmt::While(c, body) => Stmt::While(c, collapse(body)),
+            Stmt::Loop(body, until) => {
+                let mut body = collapse(body);
+                match (body.first(), &until) {
+                    (Some(Stmt::If(c, b)), None) if b == &[Stmt::Break] => {
+                        let cond = c.negate();
+                        body.remove(0);
+                        Stmt::While(cond, body)
+                    }
+                    _ => Stmt::Loop(body, until),
+                }
+            }
+            _ => stmt,
+        };
+        if let (Some(Stmt::Assign(prev_x, prev_e)), Stmt::Assign(x, e)) = (result.last(), &stmt) {
+            if prev_x == x {
+                let merged = if e.uses(*x) {
+                    e.substitute(*x, prev_e).simplify()
+                } else {
+                    e.clone()
+                };
+                *result.last_mut().unwrap() = Stmt::Assign(*x, merged);
+                continue;
+            }
+        }
+        result.push(stmt);
+    }
+    result
+}
+
+fn print_stmts(stmts: &[Stmt], gotos: &BTreeSet<usize>, indent: usize, out: &mut String) {
+    let pad = "    ".repeat(indent);
+    for stmt in stmts {
+        match stmt {
+            Stmt::Assign(x, Expr::Bin(op, a, b)) if **a == Expr::Reg(*x) => match (op, &**b) {
+                (Op::Add, Expr::Int(v)) if *v < 0 => *out += &format!("{pad}{x} -= {};\n", -v),
+                _ => *out += &format!("{pad}{x} {op}= {b};\n"),
+            },
+            Stmt::Assign(x, e) => *out += &format!("{pad}{x} = {e};\n"),
+            Stmt::If(c, body) => {
+                if let [single @ (Stmt::Break | Stmt::Continue | Stmt::Return | Stmt::Goto(_))] =
+                    body.as_slice()
+                {
+                    let mut line = String::new();
+                    print_stmts(std::slice::from_ref(single), gotos, 0, &mut line);
+                    *out += &format!("{pad}if {c} {{ {} }}\n", line.trim_end());
+                } else {
+                    *out += &format!("{pad}if {c} {{\n");
+                    print_stmts(body, gotos, indent + 1, out);
+                    *out += &format!("{pad}}}\n");
+                }
+            }
+            Stmt::Loop(body, until) => {
+                *out += &format!("{pad}loop {{\n");
+                print_stmts(body, gotos, indent + 1, out);
+                if let Some(c) = until {
+                    *out += &format!("{pad}    if {} {{ break; }}\n", c.negate());
+                }
+                *out += &format!("{pad}}}\n");
+            }
+            Stmt::While(c, body) => {
+                *out += &format!("{pad}while {c} {{\n");
+                print_stmts(body, gotos, indent + 1, out);
+                *out += &format!("{pad}}}\n");
+            }
+            Stmt::Break => *out += &format!("{pad}break;\n"),
+            Stmt::Continue => *out += &format!("{pad}continue;\n"),
+            Stmt::Return => *out += &format!("{pad}return;\n"),
+            Stmt::Goto(n) => *out += &format!("{pad}goto L{n};\n"),
+            Stmt::Label(n) => {
+                if gotos.contains(n) {
+                    *out += &format!("{pad}L{n}:\n");
+                }
+            }
+            Stmt::Raw(s) if s.ends_with('}') => *out += &format!("{pad}{s}\n"),
+            Stmt::Raw(s) => *out += &format!("{pad}{s};\n"),
+        }
+    }
+}
+
+// Registers read and written by an instruction.
+fn uses_defs(ins: &Instruction) -> (Vec<char>, Vec<char>) {
+    let reg = |x: &IntChar<i64>| match x {
+        IntChar::Char(r) => vec![*r],
+        IntChar::Integer(_) => vec![],
+    };
+    match ins {
+        Instruction::Set(x, y) => (reg(y), vec![*x]),
+        Instruction::Add(x, y)
+        | Instruction::Sub(x, y)
+        | Instruction::Mul(x, y)
+        | Instruction::Mod(x, y)
+        | Instruction::Div(x, y) => ([vec![*x], reg(y)].concat(), vec![*x]),
+        Instruction::JumpIf(_, x, y, _) => ([reg(x), reg(y)].concat(), vec![]),
+        Instruction::Snd(x) | Instruction::Out(x) => (reg(x), vec![]),
+        Instruction::Toggle(x) => (vec![*x], vec![]),
+        Instruction::Rcv(x) => (vec![], vec![*x]),
+        Instruction::AddProduct(x, y, z, counters, _) => (
+            [vec![*x], reg(y), reg(z)].concat(),
+            [vec![*x], counters.clone()].concat(),
+        ),
+        // The flag is only written conditionally, so it's read as well.
+        Instruction::DivisorCheck(flag, divisor, number, counter, scratch, _) => (
+            vec![*flag, *divisor, *number, *counter],
+            vec![*flag, *counter, *scratch],
+        ),
+        Instruction::Nop => (vec![], vec![]),
+    }
+}
+
+/// Returns the live-in registers (read before being written) and the modified registers.
+pub fn inputs_outputs(instructions: &[Instruction]) -> (BTreeSet<char>, BTreeSet<char>) {
+    let len = instructions.len();
+    // Unknown jumps may go anywhere.
+    let successors: Vec<Vec<usize>> = instructions
+        .iter()
+        .enumerate()
+        .map(|(i, ins)| {
+            next_instructions(ins, i)
+                .into_iter()
+                .flat_map(|t| match t {
+                    Some(t) if (0..len as i64).contains(&t) => vec![t as usize],
+                    Some(_) => vec![],
+                    None => (0..len).collect(),
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut live_in: Vec<BTreeSet<char>> = vec![BTreeSet::new(); len];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for i in (0..len).rev() {
+            let (uses, defs) = uses_defs(&instructions[i]);
+            let mut live: BTreeSet<char> = successors[i]
+                .iter()
+                .flat_map(|s| live_in[*s].iter().copied())
+                .collect();
+            for d in defs {
+                live.remove(&d);
+            }
+            live.extend(uses);
+            if live != live_in[i] {
+                live_in[i] = live;
+                changed = true;
+            }
+        }
+    }
+
+    let outputs = instructions
+        .iter()
+        .flat_map(|ins| uses_defs(ins).1)
+        .collect();
+    (live_in.into_iter().next().unwrap_or_default(), outputs)
+}
+
+/// Decompiles the instructions into Rust-like pseudo-code.
+pub fn decompile(instructions: &[Instruction]) -> String {
+    let optimized = optimize(instructions);
+    let jump_targets = optimized
+        .iter()
+        .enumerate()
+        .filter_map(|(i, ins)| jump_target(ins, i))
+        .filter(|t| *t >= 0)
+        .map(|t| t as usize)
+        .collect();
+    let mut decompiler = Decompiler {
+        instructions: &optimized,
+        jump_targets,
+        gotos: BTreeSet::new(),
+    };
+    let stmts = collapse(decompiler.structure(0, optimized.len(), None));
+
+    let (inputs, outputs) = inputs_outputs(instructions);
+    let list = |regs: BTreeSet<char>| {
+        if regs.is_empty() {
+            return "none".to_string();
+        }
+        regs.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut code = format!("// Inputs: {}\n", list(inputs));
+    code += &format!("// Outputs: {}\n", list(outputs));
+    code += "fn program() {\n";
+    print_stmts(&stmts, &decompiler.gotos, 1, &mut code);
+    code += "}\n";
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT_TEST_2016_12: &str = include_str!("bin/test_input/day2016_12_input_test_1");
+    const INPUT_TEST_2017_23: &str = include_str!("bin/test_input/day2017_23_input_test_1");
+
+    #[test]
+    fn test_collapse() {
+        let stmts = vec![
+            Stmt::Assign('a', Expr::Reg('b')),
+            Stmt::Assign('a', Expr::bin(Op::Add, Expr::Reg('a'), Expr::Int(5))),
+            Stmt::Assign('a', Expr::bin(Op::Sub, Expr::Reg('a'), Expr::Int(1))),
+            Stmt::Assign('a', Expr::bin(Op::Mul, Expr::Reg('a'), Expr::Int(3))),
+        ];
+        assert_eq!(
+            collapse(stmts),
+            [Stmt::Assign(
+                'a',
+                Expr::bin(
+                    Op::Mul,
+                    Expr::bin(Op::Add, Expr::Reg('b'), Expr::Int(4)),
+                    Expr::Int(3)
+                )
+            )]
+        );
+    }
+
+    #[test]
+    fn test_decompile_2016_12() {
+        let instructions = Instruction::build_list(INPUT_TEST_2016_12);
+        assert_eq!(
+            decompile(&instructions),
+            "// Inputs: none
+// Outputs: a
+fn program() {
+    a = 42;
+    if a == 0 {
+        a -= 1;
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_decompile_2017_23() {
+        let instructions = Instruction::build_list(INPUT_TEST_2017_23);
+        let code = decompile(&instructions);
+        assert!(code.starts_with("// Inputs: a, h\n// Outputs: b, c, d, e, f, g, h\n"));
+        assert!(code.contains("    if a != 0 {\n        b = (b * 3) + 4;\n"));
+        assert!(code.contains("        loop {\n            e = 2;\n            if d != 0 && b % d == 0 && b / d >= e && b / d < b { f = 0; }\n"));
+        assert!(code.contains("            if g == 0 { break; }\n"));
+        assert!(code.contains("        if f == 0 {\n            h += 1;\n"));
+        assert!(code.contains("        if g == 0 { return; }\n"));
+        assert!(!code.contains("goto"));
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use crate::intchar::IntChar;
 use crate::parsing::char;
 use crate::registers::Registers;
@@ -12,6 +14,7 @@ pub enum Condition {
 }
 use Condition::*;
 
+#[allow(unpredictable_function_pointer_comparisons)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum Instruction {
     Set(char, IntChar<i64>),
@@ -29,6 +32,16 @@ pub enum Instruction {
     // Day 2016 12, 23, 25
     Out(IntChar<i64>),
     Toggle(char),
+
+    // High-level instructions generated by the optimizer.
+    // They replace the first instruction of a loop and skip the rest of it (last usize parameter).
+    // Registers listed in the char vector are the loop counters, set to 0 at the end.
+    /// x += y * z
+    AddProduct(char, IntChar<i64>, IntChar<i64>, Vec<char>, usize),
+    /// Sets `flag` to 0 if `number` is `divisor` times any value in [counter, number[.
+    /// Parameters are flag, divisor, number, counter and scratch.
+    /// At the end, counter is equal to number and scratch is 0.
+    DivisorCheck(char, char, char, char, char, usize),
 }
 
 impl Instruction {
@@ -97,10 +110,45 @@ impl Instruction {
                 }
             }
             Instruction::Nop => *ir += 1,
+            Instruction::AddProduct(x, y, z, counters, len) => {
+                regs.set(*x, regs.get(*x) + regs.get_ic(*y) * regs.get_ic(*z));
+                for c in counters {
+                    regs.set(*c, 0);
+                }
+                *ir += len;
+            }
+            Instruction::DivisorCheck(flag, divisor, number, counter, scratch, len) => {
+                let d = regs.get(*divisor);
+                let n = regs.get(*number);
+                if d != 0 && n % d == 0 && (regs.get(*counter)..n).contains(&(n / d)) {
+                    regs.set(*flag, 0);
+                }
+                regs.set(*counter, n);
+                regs.set(*scratch, 0);
+                *ir += len;
+            }
             _ => panic!("Unsupported instruction in Instruction::execute()"),
         }
     }
 
+    /// Returns the toggled version of this instruction, as done by the 2016 day 23 `tgl` instruction.
+    pub fn toggle(&self) -> Self {
+        match self {
+            Instruction::Set(r, x) => {
+                Instruction::JumpIf(NotZero, *x, IntChar::Char(*r), |v| v != 0)
+            }
+            Instruction::Add(r, y) => Instruction::Sub(*r, *y),
+            Instruction::Sub(r, y) => Instruction::Add(*r, *y),
+            Instruction::JumpIf(_, v, o, _) => match o {
+                // Invalid instruction, cannot copy into an integer.
+                IntChar::Integer(_) => Instruction::Nop,
+                IntChar::Char(r) => Instruction::Set(*r, *v),
+            },
+            Instruction::Toggle(offset) => Instruction::Add(*offset, IntChar::from(1)),
+            _ => self.clone(),
+        }
+    }
+
     pub fn build_list(input: &str) -> Vec<Instruction> {
         input.lines().map(Instruction::build).collect()
     }
@@ -136,12 +184,64 @@ impl Instruction {
             Instruction::Rcv(x) | Instruction::Toggle(x) => {
                 regs.push(*x);
             }
+            Instruction::AddProduct(x, y, z, counters, _) => {
+                regs.push(*x);
+                if let IntChar::Char(c) = y {
+                    regs.push(*c);
+                }
+                if let IntChar::Char(c) = z {
+                    regs.push(*c);
+                }
+                regs.extend(counters);
+            }
+            Instruction::DivisorCheck(flag, divisor, number, counter, scratch, _) => {
+                regs.extend([*flag, *divisor, *number, *counter, *scratch]);
+            }
             Instruction::Nop => {}
         }
         regs
     }
 }
 
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Set(x, y) => write!(f, "set {} {}", x, y),
+            Instruction::Add(x, y) => write!(f, "add {} {}", x, y),
+            Instruction::Sub(x, y) => write!(f, "sub {} {}", x, y),
+            Instruction::Mul(x, y) => write!(f, "mul {} {}", x, y),
+            Instruction::Mod(x, y) => write!(f, "mod {} {}", x, y),
+            Instruction::Div(x, y) => write!(f, "div {} {}", x, y),
+            Instruction::JumpIf(cond, x, y, _) => match cond {
+                NotZero => write!(f, "jnz {} {}", x, y),
+                GreaterThanZero => write!(f, "jgz {} {}", x, y),
+                True => write!(f, "jmp {}", y),
+                Even => write!(f, "jie {} {}", x, y),
+                EqualOne => write!(f, "jio {} {}", x, y),
+            },
+            Instruction::Nop => write!(f, "nop"),
+            Instruction::Snd(x) => write!(f, "snd {}", x),
+            Instruction::Rcv(x) => write!(f, "rcv {}", x),
+            Instruction::Out(x) => write!(f, "out {}", x),
+            Instruction::Toggle(x) => write!(f, "tgl {}", x),
+            Instruction::AddProduct(x, y, z, counters, len) => write!(
+                f,
+                "addproduct {} {} {} [{}] {}",
+                x,
+                y,
+                z,
+                counters.iter().collect::<String>(),
+                len
+            ),
+            Instruction::DivisorCheck(flag, divisor, number, counter, scratch, len) => write!(
+                f,
+                "divisorcheck {} {} {} {} {} {}",
+                flag, divisor, number, counter, scratch, len
+            ),
+        }
+    }
+}
+
 pub fn build_list(input: &str, build_instruction: fn(&str) -> Instruction) -> Vec<Instruction> {
     input.lines().map(build_instruction).collect()
 }
@@ -1,6 +1,10 @@
 pub mod c_code;
+pub mod cfg;
+pub mod decompiler;
 pub mod instruction;
 pub mod intchar;
+pub mod optimizer;
 pub mod parsing;
 pub mod registers;
 pub mod run_utils;
+pub mod scheduler;
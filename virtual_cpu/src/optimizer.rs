@@ -0,0 +1,285 @@
+//! Peephole optimizer recognizing common loop idioms.
+//!
+//! The first instruction of a recognized loop is replaced with a high-level instruction that
+//! computes the effect of the whole loop and then jumps after it.
+//! The other instructions of the loop are left untouched, so the optimized list has the same
+//! length as the original one, and jumping into the middle of a loop still works.
+//!
+//! Loops are recognized in several passes, so that a nested loop can be matched
+//! once its inner loop has been optimized.
+
+use crate::instruction::{Condition, Instruction};
+use crate::intchar::IntChar;
+
+/// Returns an optimized version of the instructions.
+pub fn optimize(instructions: &[Instruction]) -> Vec<Instruction> {
+    let mut optimized = instructions.to_vec();
+    while optimize_pass(&mut optimized) {}
+    optimized
+}
+
+// Runs one optimization pass, returning true if something was optimized.
+fn optimize_pass(instructions: &mut [Instruction]) -> bool {
+    let mut optimized_something = false;
+    for i in 0..instructions.len() {
+        let block = &instructions[i..];
+        if let Some(ins) = divisor_check(block)
+            .or_else(|| multiplication_loop(block))
+            .or_else(|| add_loop(block))
+        {
+            instructions[i] = ins;
+            optimized_something = true;
+        }
+    }
+    optimized_something
+}
+
+// If the instruction increments or decrements a register by a constant, returns the register and the increment.
+fn delta(ins: &Instruction) -> Option<(char, i64)> {
+    match ins {
+        Instruction::Add(r, IntChar::Integer(k)) => Some((*r, *k)),
+        Instruction::Sub(r, IntChar::Integer(k)) => Some((*r, -k)),
+        _ => None,
+    }
+}
+
+// Checks if the instruction is jumping backwards by offset as long as the register isn't zero.
+fn is_loop_jump(ins: &Instruction, reg: char, offset: i64) -> bool {
+    matches!(ins, Instruction::JumpIf(Condition::NotZero, IntChar::Char(r), IntChar::Integer(o), _) if *r == reg && *o == offset)
+}
+
+// inc x
+// dec y
+// jnz y -2
+//
+// becomes x += y, y = 0. The first two instructions can be in any order.
+fn add_loop(block: &[Instruction]) -> Option<Instruction> {
+    if block.len() < 3 {
+        return None;
+    }
+    let (d1, d2) = (delta(&block[0])?, delta(&block[1])?);
+    let ((x, k), (y, _)) = if d2.1 == -1 && is_loop_jump(&block[2], d2.0, -2) {
+        (d1, d2)
+    } else if d1.1 == -1 && is_loop_jump(&block[2], d1.0, -2) {
+        (d2, d1)
+    } else {
+        return None;
+    };
+    if x == y {
+        return None;
+    }
+    Some(Instruction::AddProduct(
+        x,
+        IntChar::Char(y),
+        IntChar::Integer(k),
+        vec![y],
+        3,
+    ))
+}
+
+// cpy b c
+// <add loop adding c to a>
+// dec d
+// jnz d -5
+//
+// becomes a += b * d, c = 0, d = 0.
+fn multiplication_loop(block: &[Instruction]) -> Option<Instruction> {
+    if block.len() < 6 {
+        return None;
+    }
+    let Instruction::Set(c, y) = block[0] else {
+        return None;
+    };
+    let Instruction::AddProduct(x, IntChar::Char(inner), IntChar::Integer(k), counters, 3) =
+        &block[1]
+    else {
+        return None;
+    };
+    let (d, d_delta) = delta(&block[4])?;
+    if *inner != c || counters != &[c] || d_delta != -1 || !is_loop_jump(&block[5], d, -5) {
+        return None;
+    }
+    if *x == c || *x == d || c == d || y == IntChar::Char(*x) || y == IntChar::Char(d) {
+        return None;
+    }
+    // The increment of the inner loop can only be merged if the value copied is a constant.
+    let factor = match y {
+        IntChar::Integer(v) => IntChar::Integer(v * k),
+        IntChar::Char(_) if *k == 1 => y,
+        IntChar::Char(_) => return None,
+    };
+    Some(Instruction::AddProduct(
+        *x,
+        factor,
+        IntChar::Char(d),
+        vec![c, d],
+        6,
+    ))
+}
+
+// set g d
+// mul g e
+// sub g b
+// jnz g 2
+// set f 0
+// sub e -1
+// set g e
+// sub g b
+// jnz g -8
+//
+// sets f to 0 if b is d multiplied by any value of e up to b.
+fn divisor_check(block: &[Instruction]) -> Option<Instruction> {
+    if block.len() < 9 {
+        return None;
+    }
+    let Instruction::Set(g, IntChar::Char(d)) = block[0] else {
+        return None;
+    };
+    let Instruction::Mul(g1, IntChar::Char(e)) = block[1] else {
+        return None;
+    };
+    let Instruction::Sub(g2, IntChar::Char(b)) = block[2] else {
+        return None;
+    };
+    let Instruction::Set(f, IntChar::Integer(0)) = block[4] else {
+        return None;
+    };
+    let Instruction::Set(g3, IntChar::Char(e1)) = block[6] else {
+        return None;
+    };
+    let Instruction::Sub(g4, IntChar::Char(b1)) = block[7] else {
+        return None;
+    };
+    let skip_flag = matches!(block[3], Instruction::JumpIf(Condition::NotZero, IntChar::Char(r), IntChar::Integer(2), _) if r == g);
+    if !skip_flag
+        || delta(&block[5]) != Some((e, 1))
+        || !is_loop_jump(&block[8], g, -8)
+        || [g1, g2, g3, g4].iter().any(|r| *r != g)
+        || e1 != e
+        || b1 != b
+    {
+        return None;
+    }
+    let regs = [f, d, b, e, g];
+    if (1..regs.len()).any(|i| regs[i..].contains(&regs[i - 1])) {
+        return None;
+    }
+    Some(Instruction::DivisorCheck(f, d, b, e, g, 9))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIVISOR_CHECK: &str = "set g d
+mul g e
+sub g b
+jnz g 2
+set f 0
+sub e -1
+set g e
+sub g b
+jnz g -8";
+
+    fn optimize_str(input: &str) -> Vec<Instruction> {
+        optimize(&Instruction::build_list(input))
+    }
+
+    // Checks that the optimizer leaves the instructions as they are.
+    fn assert_unchanged(input: &str) {
+        assert_eq!(optimize_str(input), Instruction::build_list(input));
+    }
+
+    #[test]
+    fn test_add_loop() {
+        let expected =
+            Instruction::AddProduct('a', IntChar::Char('b'), IntChar::Integer(1), vec!['b'], 3);
+        let optimized = optimize_str("inc a\ndec b\njnz b -2");
+        assert_eq!(optimized[0], expected);
+        // The rest of the loop is kept.
+        assert_eq!(optimized[1..], Instruction::build_list("dec b\njnz b -2"));
+        assert_eq!(optimize_str("dec b\ninc a\njnz b -2")[0], expected);
+        assert_eq!(
+            optimize_str("sub a -3\ndec b\njnz b -2")[0],
+            Instruction::AddProduct('a', IntChar::Char('b'), IntChar::Integer(3), vec!['b'], 3)
+        );
+    }
+
+    #[test]
+    fn test_add_loop_refused() {
+        // Same register.
+        assert_unchanged("inc b\ndec b\njnz b -2");
+        // The counter must go down by one.
+        assert_unchanged("inc a\nsub b 2\njnz b -2");
+        assert_unchanged("inc a\ninc b\njnz b -2");
+        // Jumping on another register.
+        assert_unchanged("inc a\ndec b\njnz a -2");
+    }
+
+    #[test]
+    fn test_multiplication_loop() {
+        let optimized = optimize_str("cpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5");
+        assert_eq!(
+            optimized[0],
+            Instruction::AddProduct(
+                'a',
+                IntChar::Char('b'),
+                IntChar::Char('d'),
+                vec!['c', 'd'],
+                6
+            )
+        );
+        // The inner loop stays optimized, for jumps into it.
+        assert!(matches!(
+            optimized[1],
+            Instruction::AddProduct(_, _, _, _, 3)
+        ));
+        // With a constant, the increment of the inner loop is merged into it.
+        assert_eq!(
+            optimize_str("cpy 4 c\nsub a -2\ndec c\njnz c -2\ndec d\njnz d -5")[0],
+            Instruction::AddProduct(
+                'a',
+                IntChar::Integer(8),
+                IntChar::Char('d'),
+                vec!['c', 'd'],
+                6
+            )
+        );
+    }
+
+    #[test]
+    fn test_multiplication_loop_refused() {
+        for input in [
+            // Same register as the result, as value copied or as outer counter.
+            "cpy a c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5",
+            "cpy b c\ninc d\ndec c\njnz c -2\ndec d\njnz d -5",
+            // The outer counter must go down by one.
+            "cpy b c\ninc a\ndec c\njnz c -2\nsub d 2\njnz d -5",
+            "cpy b c\ninc a\ndec c\njnz c -2\ninc d\njnz d -5",
+            // An increment that can't be merged with a register value.
+            "cpy b c\nsub a -2\ndec c\njnz c -2\ndec d\njnz d -5",
+        ] {
+            assert!(
+                matches!(optimize_str(input)[0], Instruction::Set(_, _)),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_divisor_check() {
+        assert_eq!(
+            optimize_str(DIVISOR_CHECK)[0],
+            Instruction::DivisorCheck('f', 'd', 'b', 'e', 'g', 9)
+        );
+    }
+
+    #[test]
+    fn test_divisor_check_refused() {
+        // Same register used as flag and divisor.
+        assert_unchanged(&DIVISOR_CHECK.replace("set f 0", "set d 0"));
+        // The counter must go up by one.
+        assert_unchanged(&DIVISOR_CHECK.replace("sub e -1", "sub e -2"));
+        assert_unchanged(&DIVISOR_CHECK.replace("sub e -1", "sub e 1"));
+    }
+}
@@ -1,4 +1,4 @@
-/// Helping with parsing the input.
+//! Helping with parsing the input.
 
 #[inline]
 pub fn char(s: &str) -> char {
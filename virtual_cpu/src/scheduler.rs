@@ -0,0 +1,132 @@
+//! Runs several programs concurrently, communicating via message queues.
+//!
+//! All programs share the same instructions. `Snd` pushes a value on the queue of the next
+//! program (wrapping around, so with two programs they talk to each other), and `Rcv` pops
+//! from the program's own queue.
+//!
+//! Scheduling is cooperative and deterministic: each program runs until it terminates
+//! or waits on an empty queue, then the next one runs.
+
+use std::collections::VecDeque;
+
+use crate::instruction::Instruction;
+use crate::registers::Registers;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    /// All programs went past the end of the instructions.
+    Terminated,
+    /// All remaining programs are waiting on an empty queue.
+    Deadlock,
+}
+
+#[derive(Debug, Default)]
+pub struct Machine {
+    pub ir: usize,
+    pub regs: Registers<i64>,
+    pub queue: VecDeque<i64>,
+    /// How many values this program sent.
+    pub sent_count: usize,
+}
+
+pub struct Scheduler<'a> {
+    instructions: &'a [Instruction],
+    pub machines: Vec<Machine>,
+}
+
+impl<'a> Scheduler<'a> {
+    pub fn new(instructions: &'a [Instruction], program_count: usize) -> Self {
+        Self {
+            instructions,
+            machines: (0..program_count).map(|_| Machine::default()).collect(),
+        }
+    }
+
+    fn is_terminated(&self, id: usize) -> bool {
+        self.machines[id].ir >= self.instructions.len()
+    }
+
+    // Runs the program until it terminates or waits for a value.
+    // Returns the number of executed instructions.
+    fn run_machine(&mut self, id: usize) -> usize {
+        let target = (id + 1) % self.machines.len();
+        let mut steps = 0;
+        while !self.is_terminated(id) {
+            let machine = &mut self.machines[id];
+            match &self.instructions[machine.ir] {
+                Instruction::Snd(x) => {
+                    let val = machine.regs.get_ic(*x);
+                    machine.sent_count += 1;
+                    machine.ir += 1;
+                    self.machines[target].queue.push_back(val);
+                }
+                Instruction::Rcv(x) => {
+                    if let Some(val) = machine.queue.pop_front() {
+                        machine.regs.set(*x, val);
+                        machine.ir += 1;
+                    } else {
+                        break;
+                    }
+                }
+                ins => ins.execute(&mut machine.ir, &mut machine.regs),
+            }
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Runs all programs until they terminate or are deadlocked.
+    pub fn run(&mut self) -> Outcome {
+        loop {
+            let mut progress = false;
+            for id in 0..self.machines.len() {
+                progress |= self.run_machine(id) > 0;
+            }
+            if !progress {
+                break;
+            }
+        }
+        if (0..self.machines.len()).all(|id| self.is_terminated(id)) {
+            Outcome::Terminated
+        } else {
+            Outcome::Deadlock
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intchar::IntChar;
+
+    fn scheduler(instructions: &[Instruction]) -> Scheduler<'_> {
+        let mut scheduler = Scheduler::new(instructions, 2);
+        scheduler.machines[1].regs.set('p', 1);
+        scheduler
+    }
+
+    #[test]
+    fn test_terminated() {
+        // Each program sends its id and receives the one of the other.
+        let instructions = [Instruction::Snd(IntChar::new("p")), Instruction::Rcv('a')];
+        let mut scheduler = scheduler(&instructions);
+        assert_eq!(scheduler.run(), Outcome::Terminated);
+        assert_eq!(scheduler.machines[0].regs.get('a'), 1);
+        assert_eq!(scheduler.machines[1].regs.get('a'), 0);
+        assert!(scheduler.machines.iter().all(|m| m.sent_count == 1));
+    }
+
+    #[test]
+    fn test_deadlock() {
+        // Both programs wait for a second value that is never sent.
+        let instructions = [
+            Instruction::Snd(IntChar::new("p")),
+            Instruction::Rcv('a'),
+            Instruction::Rcv('a'),
+        ];
+        let mut scheduler = scheduler(&instructions);
+        assert_eq!(scheduler.run(), Outcome::Deadlock);
+        assert!(scheduler.machines.iter().all(|m| m.ir == 2));
+        assert!(scheduler.machines.iter().all(|m| m.queue.is_empty()));
+    }
+}
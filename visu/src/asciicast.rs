@@ -0,0 +1,84 @@
+//! Writer of [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) files,
+//! that can be replayed with `asciinema play`.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::time::Duration;
+
+pub struct Recorder<W: Write> {
+    out: W,
+    time: Duration,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Writes the header of the file.
+    pub fn new(mut out: W, width: usize, height: usize) -> io::Result<Self> {
+        writeln!(
+            out,
+            r#"{{"version": 2, "width": {width}, "height": {height}}}"#
+        )?;
+        Ok(Self {
+            out,
+            time: Duration::ZERO,
+        })
+    }
+
+    /// Adds the output of the terminal at the current time.
+    pub fn output(&mut self, text: &str) -> io::Result<()> {
+        writeln!(
+            self.out,
+            r#"[{:.3}, "o", "{}"]"#,
+            self.time.as_secs_f64(),
+            escape(text)
+        )
+    }
+
+    pub fn wait(&mut self, delay: Duration) {
+        self.time += delay;
+    }
+}
+
+// Escapes a string for JSON.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", u32::from(c));
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a\"b\\c"), r#"a\"b\\c"#);
+        assert_eq!(escape("\x1b[H\r\n"), r"\u001b[H\r\n");
+        assert_eq!(escape("\u{1F534}"), "\u{1F534}");
+    }
+
+    #[test]
+    fn test_recorder() {
+        let mut cast = Vec::new();
+        let mut recorder = Recorder::new(&mut cast, 3, 2).unwrap();
+        recorder.output("a\r\n").unwrap();
+        recorder.wait(Duration::from_millis(250));
+        recorder.output("b").unwrap();
+        assert_eq!(
+            String::from_utf8(cast).unwrap(),
+            "{\"version\": 2, \"width\": 3, \"height\": 2}\n\
+            [0.000, \"o\", \"a\\r\\n\"]\n\
+            [0.250, \"o\", \"b\"]\n"
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Animations of grid-like puzzles in the terminal.
+//!
+//! A puzzle implements [`FrameSource`] to expose its grid and to move to the next step.
+//! The [`Player`] shows it in the terminal, with controls to pause, step and change the speed,
+//! or records it to an [asciicast](https://docs.asciinema.org/manual/asciicast/v2/) file.
+//!
+//! ```
+//! use visu::{Color, FrameSource, Palette, Player};
+//!
+//! struct Counter(usize);
+//!
+//! impl FrameSource for Counter {
+//!     fn size(&self) -> (usize, usize) {
+//!         (1, 5)
+//!     }
+//!
+//!     fn symbol(&self, _row: usize, col: usize) -> char {
+//!         if col < self.0 { '#' } else { '.' }
+//!     }
+//!
+//!     fn advance(&mut self) -> bool {
+//!         if self.0 == 5 {
+//!             return false;
+//!         }
+//!         self.0 += 1;
+//!         true
+//!     }
+//! }
+//!
+//! let player = Player::new(Palette::new().with('#', Color::Green));
+//! let mut cast = Vec::new();
+//! player.record(&mut Counter(0), &mut cast).unwrap();
+//! assert_eq!(String::from_utf8(cast).unwrap().lines().count(), 7);
+//! ```
+
+use crossterm::style::{ResetColor, SetForegroundColor};
+use crossterm::Command;
+pub use ratatui::style::Color;
+
+mod asciicast;
+mod player;
+pub mod terminal;
+
+pub use player::Player;
+
+/// A grid that changes over time, to be animated.
+pub trait FrameSource {
+    /// Size of the grid, as (rows, cols).
+    fn size(&self) -> (usize, usize);
+
+    /// The character shown at this position.
+    fn symbol(&self, row: usize, col: usize) -> char;
+
+    /// The class of the cell, used to pick its color. By default it's the symbol itself.
+    fn class(&self, row: usize, col: usize) -> char {
+        self.symbol(row, col)
+    }
+
+    /// Title shown above the grid, like the current step.
+    fn title(&self) -> String {
+        String::new()
+    }
+
+    /// Moves to the next frame.
+    /// Returns false when there are no more frames, the current one being the last.
+    fn advance(&mut self) -> bool;
+}
+
+/// Colors of the cells, by class.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    colors: Vec<(char, Color)>,
+    default: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Palette {
+    /// A palette showing everything in the terminal default color.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            colors: Vec::new(),
+            default: Color::Reset,
+        }
+    }
+
+    /// Sets the color of a class of cells.
+    #[must_use]
+    pub fn with(mut self, class: char, color: Color) -> Self {
+        self.colors.retain(|(c, _)| *c != class);
+        self.colors.push((class, color));
+        self
+    }
+
+    /// Sets the color of the classes not in the palette.
+    #[must_use]
+    pub fn with_default(mut self, color: Color) -> Self {
+        self.default = color;
+        self
+    }
+
+    #[must_use]
+    pub fn color(&self, class: char) -> Color {
+        self.colors
+            .iter()
+            .find(|(c, _)| *c == class)
+            .map_or(self.default, |(_, color)| *color)
+    }
+}
+
+// The cells of one frame with their colors, row by row.
+fn cells(source: &impl FrameSource, palette: &Palette) -> Vec<Vec<(char, Color)>> {
+    let (rows, cols) = source.size();
+    (0..rows)
+        .map(|row| {
+            (0..cols)
+                .map(|col| {
+                    (
+                        source.symbol(row, col),
+                        palette.color(source.class(row, col)),
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The current frame as text with ANSI color codes, one line per row.
+#[must_use]
+pub fn to_ansi(source: &impl FrameSource, palette: &Palette) -> String {
+    // Writing to a String can't fail, so the results are ignored.
+    let mut text = String::new();
+    for row in cells(source, palette) {
+        let mut current = None;
+        for (symbol, color) in row {
+            if current != Some(color) {
+                let _ = SetForegroundColor(color.into()).write_ansi(&mut text);
+                current = Some(color);
+            }
+            text.push(symbol);
+        }
+        let _ = ResetColor.write_ansi(&mut text);
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub struct Blinker {
+        pub on: bool,
+        pub count: usize,
+    }
+
+    impl FrameSource for Blinker {
+        fn size(&self) -> (usize, usize) {
+            (2, 3)
+        }
+
+        fn symbol(&self, row: usize, col: usize) -> char {
+            if self.on && row == 0 && col == 1 {
+                '#'
+            } else {
+                '.'
+            }
+        }
+
+        fn title(&self) -> String {
+            format!("Step {}", self.count)
+        }
+
+        fn advance(&mut self) -> bool {
+            if self.count == 3 {
+                return false;
+            }
+            self.on = !self.on;
+            self.count += 1;
+            true
+        }
+    }
+
+    #[test]
+    fn test_palette() {
+        let palette = Palette::new()
+            .with('#', Color::Red)
+            .with('#', Color::Green)
+            .with_default(Color::Gray);
+        assert_eq!(palette.color('#'), Color::Green);
+        assert_eq!(palette.color('.'), Color::Gray);
+        assert_eq!(Palette::new().color('.'), Color::Reset);
+    }
+
+    #[test]
+    fn test_to_ansi() {
+        let blinker = Blinker { on: true, count: 0 };
+        let palette = Palette::new().with('#', Color::Red);
+        assert_eq!(
+            to_ansi(&blinker, &palette),
+            "\x1b[39m.\x1b[38;5;1m#\x1b[39m.\x1b[0m\n\x1b[39m...\x1b[0m\n"
+        );
+    }
+}
@@ -0,0 +1,299 @@
+//! Plays the animation in the terminal, or records it.
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::asciicast::Recorder;
+use crate::terminal::{self, Tui};
+use crate::{cells, to_ansi, FrameSource, Palette};
+
+const HOME: &str = "\x1b[H";
+const CLEAR: &str = "\x1b[J";
+
+const MIN_DELAY: Duration = Duration::from_millis(1);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// Shows a [`FrameSource`] in the terminal or records it.
+#[derive(Debug, Clone)]
+pub struct Player {
+    palette: Palette,
+    delay: Duration,
+    max_frames: Option<usize>,
+    paused: bool,
+}
+
+// What the keys do.
+enum Control {
+    Quit,
+    PlayPause,
+    Step,
+    Faster,
+    Slower,
+}
+
+impl Control {
+    fn new(key: KeyEvent) -> Option<Self> {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Self::Quit),
+            KeyCode::Char('q') | KeyCode::Esc => Some(Self::Quit),
+            KeyCode::Char(' ') => Some(Self::PlayPause),
+            KeyCode::Right | KeyCode::Char('n') => Some(Self::Step),
+            KeyCode::Up | KeyCode::Char('+') => Some(Self::Faster),
+            KeyCode::Down | KeyCode::Char('-') => Some(Self::Slower),
+            _ => None,
+        }
+    }
+}
+
+// Position in the animation.
+struct State {
+    frame: usize,
+    over: bool,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            frame: 0,
+            over: false,
+        }
+    }
+
+    fn advance(&mut self, source: &mut impl FrameSource, max_frames: Option<usize>) {
+        if self.over {
+            return;
+        }
+        if max_frames.is_some_and(|max| self.frame + 1 >= max) || !source.advance() {
+            self.over = true;
+        } else {
+            self.frame += 1;
+        }
+    }
+}
+
+impl Player {
+    /// A player with 100 ms between frames.
+    #[must_use]
+    pub fn new(palette: Palette) -> Self {
+        Self {
+            palette,
+            delay: Duration::from_millis(100),
+            max_frames: None,
+            paused: false,
+        }
+    }
+
+    /// Sets the time between two frames.
+    #[must_use]
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay.clamp(MIN_DELAY, MAX_DELAY);
+        self
+    }
+
+    /// Stops after that many frames, for sources that never end.
+    #[must_use]
+    pub fn max_frames(mut self, max_frames: usize) -> Self {
+        self.max_frames = Some(max_frames);
+        self
+    }
+
+    /// Starts paused on the first frame.
+    #[must_use]
+    pub fn paused(mut self) -> Self {
+        self.paused = true;
+        self
+    }
+
+    /// Plays the animation in the terminal until the user quits.
+    ///
+    /// Space plays or pauses, the right arrow or `n` shows the next frame,
+    /// up / down or `+` / `-` change the speed, and `q` quits.
+    ///
+    /// # Errors
+    ///
+    /// If the terminal fails.
+    pub fn play(&self, source: &mut impl FrameSource) -> io::Result<()> {
+        let mut terminal = terminal::init(true)?;
+        let result = self.run(&mut terminal, source);
+        terminal::restore(true)?;
+        result
+    }
+
+    fn run(&self, terminal: &mut Tui, source: &mut impl FrameSource) -> io::Result<()> {
+        let mut state = State::new();
+        let mut paused = self.paused;
+        let mut delay = self.delay;
+        let mut next_frame = Instant::now() + delay;
+        loop {
+            terminal.draw(|frame| {
+                let status = if state.over {
+                    "over".to_string()
+                } else if paused {
+                    "paused".to_string()
+                } else {
+                    format!("{} ms", delay.as_millis())
+                };
+                self.render(frame, source, &format!("Frame {} - {status}", state.frame));
+            })?;
+
+            let running = !paused && !state.over;
+            let timeout = running.then(|| next_frame.saturating_duration_since(Instant::now()));
+            match read_key(timeout)?.and_then(Control::new) {
+                Some(Control::Quit) => return Ok(()),
+                Some(Control::PlayPause) => {
+                    paused = !paused;
+                    next_frame = Instant::now() + delay;
+                }
+                Some(Control::Step) => {
+                    paused = true;
+                    state.advance(source, self.max_frames);
+                }
+                Some(Control::Faster) => delay = (delay / 2).max(MIN_DELAY),
+                Some(Control::Slower) => delay = (delay * 2).min(MAX_DELAY),
+                None => {
+                    if running && Instant::now() >= next_frame {
+                        state.advance(source, self.max_frames);
+                        next_frame = Instant::now() + delay;
+                    }
+                }
+            }
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, source: &impl FrameSource, status: &str) {
+        let lines: Vec<Line> = cells(source, &self.palette)
+            .into_iter()
+            .map(|row| {
+                Line::from(
+                    row.into_iter()
+                        .map(|(symbol, color)| {
+                            Span::styled(symbol.to_string(), Style::default().fg(color))
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        let width = lines.iter().map(Line::width).max().unwrap_or(0);
+
+        let area = frame.size();
+        let grid_area = Rect::new(
+            0,
+            0,
+            u16::try_from(width + 2).unwrap_or(u16::MAX),
+            u16::try_from(lines.len() + 2).unwrap_or(u16::MAX),
+        )
+        .intersection(area);
+        let grid = Paragraph::new(lines).block(
+            Block::default()
+                .title(source.title())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        frame.render_widget(grid, grid_area);
+
+        let help_area = Rect::new(0, grid_area.bottom(), area.width, 1).intersection(area);
+        let help = Line::from(vec![
+            Span::raw(format!("{status}  ")),
+            "<Space>".blue().bold(),
+            " play/pause ".into(),
+            "<Right>".blue().bold(),
+            " step ".into(),
+            "<Up/Down>".blue().bold(),
+            " speed ".into(),
+            "<Q>".blue().bold(),
+            " quit".into(),
+        ]);
+        frame.render_widget(Paragraph::new(help), help_area);
+    }
+
+    /// Records the animation to an asciicast file, without needing a terminal.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn record(&self, source: &mut impl FrameSource, out: impl Write) -> io::Result<()> {
+        let (rows, _) = source.size();
+        let width = cells(source, &self.palette)
+            .iter()
+            .map(|row| Line::raw(row.iter().map(|(s, _)| s).collect::<String>()).width())
+            .chain(std::iter::once(Line::raw(source.title()).width()))
+            .max()
+            .unwrap_or(0);
+        let mut recorder = Recorder::new(out, width, rows + 1)?;
+
+        let mut state = State::new();
+        loop {
+            let grid = to_ansi(source, &self.palette).replace('\n', "\r\n");
+            recorder.output(&format!("{HOME}{CLEAR}{}\r\n{grid}", source.title()))?;
+            state.advance(source, self.max_frames);
+            if state.over {
+                return Ok(());
+            }
+            recorder.wait(self.delay);
+        }
+    }
+}
+
+// Waits for a key press, or until the timeout.
+fn read_key(timeout: Option<Duration>) -> io::Result<Option<KeyEvent>> {
+    if let Some(timeout) = timeout {
+        if !event::poll(timeout)? {
+            return Ok(None);
+        }
+    }
+    Ok(match event::read()? {
+        // Crossterm also emits key release and repeat events on Windows.
+        Event::Key(key) if key.kind == KeyEventKind::Press => Some(key),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Blinker;
+
+    #[test]
+    fn test_record() {
+        let player = Player::new(Palette::new()).delay(Duration::from_millis(500));
+        let mut cast = Vec::new();
+        player
+            .record(
+                &mut Blinker {
+                    on: false,
+                    count: 0,
+                },
+                &mut cast,
+            )
+            .unwrap();
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines[0], r#"{"version": 2, "width": 6, "height": 3}"#);
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with(r#"[0.000, "o", "\u001b[H\u001b[JStep 0\r\n"#));
+        assert!(lines[2].starts_with(r#"[0.500, "o", "\u001b[H\u001b[JStep 1\r\n"#));
+        assert!(lines[4].starts_with(r#"[1.500, "o", "\u001b[H\u001b[JStep 3\r\n"#));
+    }
+
+    #[test]
+    fn test_max_frames() {
+        let player = Player::new(Palette::new()).max_frames(2);
+        let mut cast = Vec::new();
+        let mut blinker = Blinker {
+            on: false,
+            count: 0,
+        };
+        player.record(&mut blinker, &mut cast).unwrap();
+        assert_eq!(String::from_utf8(cast).unwrap().lines().count(), 3);
+        assert_eq!(blinker.count, 1);
+    }
+}
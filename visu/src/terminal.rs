@@ -0,0 +1,51 @@
+//! Setup of the terminal for [Ratatui](https://ratatui.rs), with [Crossterm](https://docs.rs/crossterm).
+
+use std::io::{self, stdout, Stdout};
+use std::panic;
+
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::prelude::{CrosstermBackend, Terminal};
+
+/// The terminal type used by the animations.
+pub type Tui = Terminal<CrosstermBackend<Stdout>>;
+
+/// Initializes the terminal, switching to the alternate screen.
+///
+/// Raw mode is needed to read the keys as they are pressed, but then ctrl-c must be handled by the application.
+/// The terminal is restored if the program panics, so that the panic message is visible.
+///
+/// # Errors
+///
+/// If the terminal can't be set up.
+pub fn init(raw_mode: bool) -> io::Result<Tui> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        // Ignoring errors, we are already panicking.
+        let _ = restore(raw_mode);
+        hook(info);
+    }));
+
+    execute!(stdout(), EnterAlternateScreen)?;
+    if raw_mode {
+        enable_raw_mode()?;
+    }
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    terminal.clear()?;
+    Ok(terminal)
+}
+
+/// Restores the terminal to its original state.
+///
+/// # Errors
+///
+/// If the terminal can't be restored.
+pub fn restore(raw_mode: bool) -> io::Result<()> {
+    execute!(stdout(), LeaveAlternateScreen)?;
+    if raw_mode {
+        disable_raw_mode()?;
+    }
+    Ok(())
+}